@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_game_moves_game_id;
+DROP TABLE IF EXISTS game_moves;
+DROP TABLE IF EXISTS games;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_game_invites_game_id;
+DROP TABLE IF EXISTS game_invites;
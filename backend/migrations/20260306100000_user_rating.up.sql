@@ -0,0 +1,2 @@
+ALTER TABLE users
+ADD COLUMN rating DOUBLE PRECISION NOT NULL DEFAULT 1000.0;
@@ -0,0 +1,2 @@
+-- Renamed usernames are not restored, they would clash again
+SELECT 1;
@@ -0,0 +1,19 @@
+-- Usernames that only differ by case must be made unique before the
+-- case-insensitive index in the next migration can be created. One account
+-- per name keeps it, preferring an all lowercase name; the others get a
+-- suffix from their ID and can pick a new name after signing in.
+UPDATE users
+SET username = username || '_' || LEFT(id::text, 8)
+WHERE id IN (
+    SELECT id
+    FROM (
+        SELECT
+            id,
+            ROW_NUMBER() OVER (
+                PARTITION BY LOWER(username)
+                ORDER BY username <> LOWER(username), id
+            ) AS position
+        FROM users
+    ) ranked
+    WHERE position > 1
+);
@@ -0,0 +1 @@
+CREATE UNIQUE INDEX users_username_lower_key ON users (LOWER(username));
@@ -0,0 +1,10 @@
+-- No foreign keys, so audit rows outlive the games and users they describe
+CREATE TABLE game_creations (
+    game_id UUID PRIMARY KEY,
+    creator_id UUID NOT NULL,
+    world_name TEXT NOT NULL,
+    ip_address TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_game_creations_created_at ON game_creations(created_at);
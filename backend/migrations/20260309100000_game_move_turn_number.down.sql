@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_game_moves_game_id_turn_number;
+
+ALTER TABLE game_moves
+DROP COLUMN IF EXISTS turn_number;
@@ -0,0 +1,4 @@
+ALTER TABLE game_moves
+ADD COLUMN turn_number BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_game_moves_game_id_turn_number ON game_moves(game_id, turn_number);
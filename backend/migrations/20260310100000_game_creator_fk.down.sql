@@ -0,0 +1,2 @@
+ALTER TABLE games
+DROP CONSTRAINT IF EXISTS games_creator_id_fkey;
@@ -0,0 +1,3 @@
+ALTER TABLE games
+ADD CONSTRAINT games_creator_id_fkey
+FOREIGN KEY (creator_id) REFERENCES users(id) ON DELETE RESTRICT;
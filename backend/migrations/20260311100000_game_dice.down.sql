@@ -0,0 +1,13 @@
+UPDATE games
+SET snapshot = jsonb_set(
+    snapshot,
+    '{seed}',
+    to_jsonb(CASE
+        WHEN dice_seed < 0 THEN dice_seed::NUMERIC + 18446744073709551616
+        ELSE dice_seed::NUMERIC
+    END)
+);
+
+ALTER TABLE games
+DROP COLUMN IF EXISTS dice_draws,
+DROP COLUMN IF EXISTS dice_seed;
@@ -0,0 +1,17 @@
+ALTER TABLE games
+ADD COLUMN dice_seed BIGINT NOT NULL DEFAULT 0,
+ADD COLUMN dice_draws BIGINT NOT NULL DEFAULT 0;
+
+-- Move the seed out of the snapshot, which is served to clients. Seeds are
+-- unsigned 64-bit values, stored with the same bits as a signed BIGINT.
+UPDATE games
+SET dice_seed = CASE
+        WHEN (snapshot->>'seed')::NUMERIC >= 9223372036854775808
+        THEN ((snapshot->>'seed')::NUMERIC - 18446744073709551616)::BIGINT
+        ELSE (snapshot->>'seed')::BIGINT
+    END,
+    snapshot = snapshot - 'seed'
+WHERE snapshot ? 'seed';
+
+ALTER TABLE games
+ALTER COLUMN dice_seed DROP DEFAULT;
@@ -17,6 +17,8 @@ pub enum MailType {
     /// Password-reset email.
     #[allow(dead_code)]
     PasswordReset { token: String },
+    /// Welcome email sent once the email address has been verified.
+    Welcome,
 }
 
 /// An outbound application email.
@@ -50,12 +52,20 @@ struct PasswordResetEmailTemplate<'a> {
     reset_url: &'a str,
 }
 
+#[derive(Template)]
+#[template(path = "welcome_email.html")]
+struct WelcomeEmailTemplate<'a> {
+    username: &'a str,
+    play_url: &'a str,
+}
+
 impl Mail {
     /// Email subject line.
     pub fn subject(&self) -> &str {
         match &self.mail_type {
             MailType::EmailVerification { .. } => "Verify your rsdice account",
             MailType::PasswordReset { .. } => "Reset your rsdice password",
+            MailType::Welcome => "Welcome to rsdice",
         }
     }
 
@@ -84,6 +94,14 @@ impl Mail {
                 };
                 template.render()
             }
+            MailType::Welcome => {
+                let play_url = format!("{base}/games");
+                let template = WelcomeEmailTemplate {
+                    username,
+                    play_url: &play_url,
+                };
+                template.render()
+            }
         }
     }
 
@@ -112,6 +130,15 @@ impl Mail {
                      If you did not request this, you can ignore this email."
                 )
             }
+            MailType::Welcome => {
+                let url = format!("{base}/games");
+                format!(
+                    "Hi {username},\n\n\
+                     Your email address is verified and your rsdice account is ready.\n\
+                     Find a game to join here:\n\
+                     {url}"
+                )
+            }
         }
     }
 }
@@ -144,6 +171,16 @@ mod tests {
         }
     }
 
+    fn welcome_mail() -> Mail {
+        Mail {
+            recipient: Recipient {
+                name: "carol".to_string(),
+                email: "carol@example.com".to_string(),
+            },
+            mail_type: MailType::Welcome,
+        }
+    }
+
     // ==== Subject ====
 
     #[test]
@@ -156,6 +193,11 @@ mod tests {
         assert_eq!(reset_mail().subject(), "Reset your rsdice password");
     }
 
+    #[test]
+    fn welcome_subject() {
+        assert_eq!(welcome_mail().subject(), "Welcome to rsdice");
+    }
+
     // ==== Recipient ====
 
     #[test]
@@ -188,6 +230,13 @@ mod tests {
         assert!(text.contains("bob"));
     }
 
+    #[test]
+    fn welcome_text_links_to_games() {
+        let text = welcome_mail().to_text("https://rsdice.example.com/");
+        assert!(text.contains("https://rsdice.example.com/games"));
+        assert!(text.contains("carol"));
+    }
+
     #[test]
     fn text_trims_trailing_slash() {
         let text = verification_mail().to_text("https://rsdice.example.com/");
@@ -212,4 +261,13 @@ mod tests {
         assert!(html.contains("bob"));
         assert!(html.contains("https://rsdice.example.com/reset-password?token=xyz789"));
     }
+
+    #[test]
+    fn welcome_html_renders() {
+        let html = welcome_mail()
+            .to_html("https://rsdice.example.com")
+            .unwrap();
+        assert!(html.contains("carol"));
+        assert!(html.contains("https://rsdice.example.com/games"));
+    }
 }
@@ -1,5 +1,12 @@
 use super::{EmailClient, EmailError, Mail};
-use std::{future::Future, pin::Pin, sync::Mutex};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
 /// Mock [`EmailClient`] that records every [`Mail`] sent.
 ///
@@ -16,6 +23,7 @@ use std::{future::Future, pin::Pin, sync::Mutex};
 #[derive(Debug)]
 pub struct MockEmailClient {
     sent: Mutex<Vec<Mail>>,
+    failing: AtomicBool,
 }
 
 impl MockEmailClient {
@@ -23,9 +31,16 @@ impl MockEmailClient {
     pub fn new() -> Self {
         Self {
             sent: Mutex::new(Vec::new()),
+            failing: AtomicBool::new(false),
         }
     }
 
+    /// Make every following send fail, or succeed again, without recording
+    /// the mail.
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::Relaxed);
+    }
+
     /// Returns a snapshot of all mails that have been sent through this mock.
     pub fn sent(&self) -> Vec<Mail> {
         self.sent.lock().expect("mock email lock poisoned").clone()
@@ -52,6 +67,10 @@ impl EmailClient for MockEmailClient {
         &'a self,
         mail: &'a Mail,
     ) -> Pin<Box<dyn Future<Output = Result<(), EmailError>> + Send + 'a>> {
+        if self.failing.load(Ordering::Relaxed) {
+            return Box::pin(async { Err(EmailError::Send("mock failure".to_string())) });
+        }
+
         self.sent
             .lock()
             .expect("mock email lock poisoned")
@@ -114,4 +133,19 @@ mod tests {
         let latest = mock.latest().expect("should have a latest mail");
         assert_eq!(latest.recipient.email, "bob@example.com");
     }
+    #[tokio::test]
+    async fn failing_mock_returns_send_error() {
+        let mock = MockEmailClient::new();
+        mock.set_failing(true);
+        let mail = Mail {
+            recipient: Recipient {
+                name: "alice".into(),
+                email: "alice@example.com".into(),
+            },
+            mail_type: MailType::EmailVerification { token: "t1".into() },
+        };
+
+        assert!(matches!(mock.send(&mail).await, Err(EmailError::Send(_))));
+        assert!(mock.sent().is_empty());
+    }
 }
@@ -19,6 +19,9 @@ pub enum EmailError {
 
     #[error("Template rendering error: {0}")]
     Template(#[from] askama::Error),
+
+    #[error("Failed to send email: {0}")]
+    Send(String),
 }
 
 /// Trait abstracting email delivery.
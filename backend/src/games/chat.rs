@@ -0,0 +1,138 @@
+use crate::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Longest chat message accepted, in characters.
+pub const CHAT_MESSAGE_MAX_LENGTH: usize = 200;
+
+/// Number of chat messages a user may send within [`CHAT_RATE_LIMIT_WINDOW`].
+pub const CHAT_RATE_LIMIT_MESSAGES: usize = 5;
+
+pub const CHAT_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Strip control characters and surrounding whitespace from a chat message,
+/// rejecting it if nothing is left or it is too long.
+pub fn sanitize_chat_message(message: &str) -> Result<String> {
+    let message: String = message.chars().filter(|c| !c.is_control()).collect();
+    let message = message.trim();
+
+    if message.is_empty() {
+        return Err(Error::BadRequest("chat message is empty".to_string()));
+    }
+    if message.chars().count() > CHAT_MESSAGE_MAX_LENGTH {
+        return Err(Error::BadRequest(format!(
+            "chat message is longer than {CHAT_MESSAGE_MAX_LENGTH} characters"
+        )));
+    }
+
+    Ok(message.to_string())
+}
+
+/// When each user sent their recent chat messages, to rate limit them.
+#[derive(Debug, Default)]
+pub(super) struct ChatRateLimiter {
+    sent: HashMap<Uuid, VecDeque<Instant>>,
+}
+
+impl ChatRateLimiter {
+    /// Record a message from `user_id` sent at `now`. Fails with the time
+    /// left until they may send again if they are over the limit.
+    pub(super) fn try_send(
+        &mut self,
+        user_id: Uuid,
+        now: Instant,
+    ) -> std::result::Result<(), Duration> {
+        let sent = self.sent.entry(user_id).or_default();
+        while sent
+            .front()
+            .is_some_and(|&at| now.duration_since(at) >= CHAT_RATE_LIMIT_WINDOW)
+        {
+            sent.pop_front();
+        }
+
+        if let Some(&oldest) = sent.front()
+            && sent.len() >= CHAT_RATE_LIMIT_MESSAGES
+        {
+            return Err(CHAT_RATE_LIMIT_WINDOW - now.duration_since(oldest));
+        }
+
+        sent.push_back(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ==== sanitize_chat_message ====
+
+    #[test]
+    fn sanitize_strips_control_characters_and_whitespace() {
+        assert_eq!(
+            sanitize_chat_message("  gg\u{7}\r\n wp\t ").unwrap(),
+            "gg wp"
+        );
+    }
+
+    #[test]
+    fn sanitize_rejects_empty_messages() {
+        assert!(sanitize_chat_message("").is_err());
+        assert!(sanitize_chat_message(" \n\u{0} ").is_err());
+    }
+
+    #[test]
+    fn sanitize_limits_length_in_characters() {
+        let longest = "å".repeat(CHAT_MESSAGE_MAX_LENGTH);
+        assert_eq!(sanitize_chat_message(&longest).unwrap(), longest);
+        assert!(sanitize_chat_message(&format!("{longest}a")).is_err());
+    }
+
+    // ==== ChatRateLimiter ====
+
+    #[test]
+    fn rate_limiter_allows_limit_within_window() {
+        let mut limiter = ChatRateLimiter::default();
+        let user_id = Uuid::new_v4();
+        let start = Instant::now();
+
+        for _ in 0..CHAT_RATE_LIMIT_MESSAGES {
+            assert!(limiter.try_send(user_id, start).is_ok());
+        }
+        let cooldown = limiter
+            .try_send(user_id, start + Duration::from_secs(4))
+            .unwrap_err();
+        assert_eq!(cooldown, Duration::from_secs(6));
+    }
+
+    #[test]
+    fn rate_limiter_allows_messages_again_after_cooldown() {
+        let mut limiter = ChatRateLimiter::default();
+        let user_id = Uuid::new_v4();
+        let start = Instant::now();
+
+        for _ in 0..CHAT_RATE_LIMIT_MESSAGES {
+            limiter.try_send(user_id, start).unwrap();
+        }
+        assert!(limiter.try_send(user_id, start).is_err());
+        assert!(
+            limiter
+                .try_send(user_id, start + CHAT_RATE_LIMIT_WINDOW)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rate_limiter_tracks_users_separately() {
+        let mut limiter = ChatRateLimiter::default();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Instant::now();
+
+        for _ in 0..CHAT_RATE_LIMIT_MESSAGES {
+            limiter.try_send(first, now).unwrap();
+        }
+        assert!(limiter.try_send(first, now).is_err());
+        assert!(limiter.try_send(second, now).is_ok());
+    }
+}
@@ -1,47 +1,409 @@
 use super::Creator;
-use serde::{Deserialize, Serialize};
+pub use common::GameCommand;
+use common::{AttackResult, Color};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+use std::collections::HashSet;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "snake_case")]
-pub enum GameCommand {
-    Start,
-    Attack { from_id: Uuid, to_id: Uuid },
-    EndTurn,
-    Ping,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum GameEvent {
+    /// First event on every game socket, the server's
+    /// [`common::PROTOCOL_VERSION`].
+    ProtocolVersion {
+        version: u32,
+    },
     Snapshot {
-        game: common::Game,
+        game: Box<common::Game>,
+    },
+    Diff {
+        diff: common::GameDiff,
     },
     PlayerJoined {
         player_id: Uuid,
         player_name: String,
     },
+    /// A player closed their connection to the game.
+    PlayerLeft {
+        player_id: Uuid,
+        player_name: String,
+    },
+    SpectatorJoined {
+        spectator_id: Uuid,
+        spectator_name: String,
+    },
     GameStarted,
     AttackResolved {
         from_id: Uuid,
         to_id: Uuid,
         player_id: Uuid,
+        result: AttackResult,
+    },
+    /// `player_id` received `amount` bonus dice on the board at the end of
+    /// their turn, spread over `areas_updated`.
+    DiceDistributed {
+        player_id: Uuid,
+        amount: usize,
+        areas_updated: Vec<Uuid>,
+    },
+    /// `player_id` lost their last area, to `eliminated_by` if another
+    /// player took it.
+    PlayerEliminated {
+        player_id: Uuid,
+        eliminated_by: Option<Uuid>,
     },
     TurnEnded {
         player_id: Uuid,
     },
-    Finished {
+    /// `player_id` is now on turn; `turn_number` counts the turns ended
+    /// before it.
+    TurnStarted {
+        player_id: Uuid,
+        turn_number: u32,
+    },
+    TurnTimerStarted {
+        seconds_remaining: u64,
+    },
+    TurnTimerExpired {
+        player_id: Uuid,
+    },
+    /// The player's connection dropped and they did not reconnect in time.
+    TurnSkipped {
+        player_id: Uuid,
+    },
+    /// A chat message, already sanitized. `player_id` may be a spectator.
+    ChatMessage {
+        player_id: Uuid,
+        player_name: String,
+        message: String,
+    },
+    /// The game is over. Sent as `finished` for clients that predate the
+    /// rename.
+    #[serde(rename = "finished", alias = "game_finished")]
+    GameFinished {
         reason: String,
+        winner_id: Option<Uuid>,
+        winner_name: Option<String>,
     },
     Error {
         message: String,
     },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl GameEvent {
+    /// [`GameEvent::TurnStarted`] for the current turn of `game`, if it is
+    /// in progress.
+    pub fn turn_started(game: &common::Game) -> Option<Self> {
+        let common::GameState::InProgress { turn } = game.state else {
+            return None;
+        };
+        Some(Self::TurnStarted {
+            player_id: game.players.get(turn)?.id,
+            turn_number: game.turn_number,
+        })
+    }
+
+    /// The event that brings clients from `previous` to `current`: a
+    /// [`GameEvent::Diff`] when that is smaller than the full snapshot, or a
+    /// [`GameEvent::Snapshot`] when every area changed or the diff would be
+    /// no smaller.
+    pub fn update(previous: &common::Game, current: &common::Game) -> Self {
+        let diff = current.diff_from(previous);
+        if diff.areas.len() < current.world.areas.len()
+            && serialized_len(&diff) < serialized_len(current)
+        {
+            GameEvent::Diff { diff }
+        } else {
+            GameEvent::Snapshot {
+                game: Box::new(current.clone()),
+            }
+        }
+    }
+
+    /// A [`GameEvent::DiceDistributed`] for bonus dice placed on the areas
+    /// in `placed`, one entry per die.
+    pub fn dice_distributed(player_id: Uuid, placed: &[Uuid]) -> Self {
+        let mut areas_updated = placed.to_vec();
+        areas_updated.sort();
+        areas_updated.dedup();
+        GameEvent::DiceDistributed {
+            player_id,
+            amount: placed.len(),
+            areas_updated,
+        }
+    }
+
+    /// The event as seen by a viewer under fog of war who may only see the
+    /// dice on the areas in `visible`. Attacks hide the dice counts of areas
+    /// out of sight, and bonus dice only name the visible areas they went to.
+    pub fn fogged(self, visible: &HashSet<Uuid>) -> Self {
+        match self {
+            GameEvent::AttackResolved {
+                from_id,
+                to_id,
+                player_id,
+                result,
+            } => GameEvent::AttackResolved {
+                from_id,
+                to_id,
+                player_id,
+                result: result.fogged(visible.contains(&from_id), visible.contains(&to_id)),
+            },
+            GameEvent::DiceDistributed {
+                player_id,
+                amount,
+                mut areas_updated,
+            } => {
+                areas_updated.retain(|area_id| visible.contains(area_id));
+                GameEvent::DiceDistributed {
+                    player_id,
+                    amount,
+                    areas_updated,
+                }
+            }
+            event => event,
+        }
+    }
+
+    /// A [`GameEvent::GameFinished`] naming the winner of `game`, if it has
+    /// one.
+    pub fn finished(game: &common::Game, reason: impl Into<String>) -> Self {
+        let winner = game.winner();
+        GameEvent::GameFinished {
+            reason: reason.into(),
+            winner_id: winner.map(|p| p.id),
+            winner_name: winner.map(|p| p.name.clone()),
+        }
+    }
+}
+
+fn serialized_len(value: &impl Serialize) -> usize {
+    serde_json::to_vec(value).map_or(usize::MAX, |bytes| bytes.len())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GameListItem {
     pub id: Uuid,
     pub creator: Creator,
     pub player_count: usize,
+    pub players: Vec<PlayerPreview>,
+    pub spectator_count: usize,
     pub state: common::GameState,
 }
+
+/// A player as shown in the lobby before joining a game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PlayerPreview {
+    pub name: String,
+    /// The player's color as a hex string, e.g. `#FF0000`.
+    #[serde(
+        serialize_with = "serialize_hex_color",
+        deserialize_with = "deserialize_hex_color"
+    )]
+    #[schema(value_type = String, example = "#FF0000")]
+    pub color: Color,
+}
+
+impl From<&common::Player> for PlayerPreview {
+    fn from(player: &common::Player) -> Self {
+        Self {
+            name: player.name.clone(),
+            color: player.color,
+        }
+    }
+}
+
+fn serialize_hex_color<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(color.to_hex())
+}
+
+fn deserialize_hex_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    Color::from_hex(&hex).map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attack_resolved_serializes_roll_values() {
+        let event = GameEvent::AttackResolved {
+            from_id: Uuid::new_v4(),
+            to_id: Uuid::new_v4(),
+            player_id: Uuid::new_v4(),
+            result: AttackResult {
+                attack_roll: 17,
+                defence_roll: 9,
+                captured: true,
+                attacker_remaining_dice: 1,
+                defender_remaining_dice: 4,
+            },
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "attack_resolved");
+        assert_eq!(json["result"]["attack_roll"], 17);
+        assert_eq!(json["result"]["defence_roll"], 9);
+        assert_eq!(json["result"]["captured"], true);
+        assert_eq!(json["result"]["attacker_remaining_dice"], 1);
+        assert_eq!(json["result"]["defender_remaining_dice"], 4);
+    }
+
+    #[test]
+    fn finished_without_winner_serializes_nulls() {
+        let game = common::Game::new(common::World::default());
+        let json = serde_json::to_value(GameEvent::finished(&game, "timed out")).unwrap();
+        assert_eq!(json["type"], "finished");
+        assert_eq!(json["reason"], "timed out");
+        assert!(json["winner_id"].is_null());
+        assert!(json["winner_name"].is_null());
+    }
+
+    #[test]
+    fn game_finished_accepts_both_tags() {
+        for tag in ["finished", "game_finished"] {
+            let json = serde_json::json!({
+                "type": tag,
+                "reason": "Last player standing",
+                "winner_id": null,
+                "winner_name": null,
+            });
+            let event: GameEvent = serde_json::from_value(json).unwrap();
+            assert!(matches!(event, GameEvent::GameFinished { .. }));
+        }
+    }
+
+    #[test]
+    fn player_eliminated_serializes_snake_case() {
+        let player_id = Uuid::new_v4();
+        let json = serde_json::to_value(GameEvent::PlayerEliminated {
+            player_id,
+            eliminated_by: None,
+        })
+        .unwrap();
+        assert_eq!(json["type"], "player_eliminated");
+        assert_eq!(json["player_id"], player_id.to_string());
+        assert!(json["eliminated_by"].is_null());
+    }
+
+    #[test]
+    fn dice_distributed_counts_dice_and_lists_each_area_once() {
+        let player_id = Uuid::new_v4();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let event = GameEvent::dice_distributed(player_id, &[a, b, a]);
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "dice_distributed");
+        assert_eq!(json["amount"], 3);
+        assert_eq!(json["areas_updated"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn fogged_attack_hides_dice_of_areas_out_of_sight() {
+        let (from_id, to_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let result = AttackResult {
+            attack_roll: 17,
+            defence_roll: 9,
+            captured: false,
+            attacker_remaining_dice: 1,
+            defender_remaining_dice: 4,
+        };
+        let event = GameEvent::AttackResolved {
+            from_id,
+            to_id,
+            player_id: Uuid::new_v4(),
+            result,
+        };
+
+        let GameEvent::AttackResolved { result: seen, .. } =
+            event.clone().fogged(&HashSet::from([to_id]))
+        else {
+            panic!("expected an attack");
+        };
+        assert_eq!(seen, result.fogged(false, true));
+
+        let GameEvent::AttackResolved { result: seen, .. } = event.fogged(&HashSet::new()) else {
+            panic!("expected an attack");
+        };
+        assert_eq!(seen, result.fogged(false, false));
+    }
+
+    #[test]
+    fn fogged_dice_distributed_lists_only_visible_areas() {
+        let (near, far) = (Uuid::new_v4(), Uuid::new_v4());
+        let event = GameEvent::dice_distributed(Uuid::new_v4(), &[near, far, far]);
+
+        let GameEvent::DiceDistributed {
+            amount,
+            areas_updated,
+            ..
+        } = event.fogged(&HashSet::from([near]))
+        else {
+            panic!("expected dice distributed");
+        };
+        assert_eq!(amount, 3);
+        assert_eq!(areas_updated, [near]);
+    }
+
+    #[test]
+    fn player_preview_serializes_color_as_hex() {
+        let preview = PlayerPreview {
+            name: "alice".to_string(),
+            color: Color::Green,
+        };
+
+        let json = serde_json::to_value(&preview).unwrap();
+        assert_eq!(json["name"], "alice");
+        assert_eq!(json["color"], "#00CC44");
+
+        let back: PlayerPreview = serde_json::from_value(json).unwrap();
+        assert_eq!(back, preview);
+    }
+
+    #[test]
+    fn player_preview_rejects_unknown_color() {
+        let json = serde_json::json!({ "name": "alice", "color": "#123456" });
+        assert!(serde_json::from_value::<PlayerPreview>(json).is_err());
+    }
+
+    fn game_with_areas(n: usize) -> common::Game {
+        let areas = (0..n)
+            .map(|y| common::Area::new([common::Tile::new(0, y)].into()))
+            .map(|area| (area.id, area))
+            .collect();
+        common::Game::new(common::World {
+            areas,
+            ..common::World::default()
+        })
+    }
+
+    #[test]
+    fn update_sends_diff_for_single_area_change() {
+        let previous = game_with_areas(10);
+        let mut current = previous.clone();
+        let area = current.world.areas.values_mut().next().unwrap();
+        area.stack.increment().unwrap();
+
+        let GameEvent::Diff { diff } = GameEvent::update(&previous, &current) else {
+            panic!("expected a diff");
+        };
+        assert_eq!(diff.areas.len(), 1);
+    }
+
+    #[test]
+    fn update_sends_snapshot_when_every_area_changed() {
+        let previous = game_with_areas(10);
+        let mut current = previous.clone();
+        let owner = Uuid::new_v4();
+        for area in current.world.areas.values_mut() {
+            area.owner = Some(owner);
+        }
+
+        assert!(matches!(
+            GameEvent::update(&previous, &current),
+            GameEvent::Snapshot { .. }
+        ));
+    }
+}
@@ -1,16 +1,26 @@
-use super::{GameEvent, GameListItem};
+use super::chat::{ChatRateLimiter, sanitize_chat_message};
+use super::{GameEvent, GameListItem, PlayerPreview};
+use crate::metrics::Metrics;
 use crate::models::User;
 use crate::prelude::*;
+use crate::rating::{self, GameParticipant};
+use crate::repositories::{GameRepository, UserRepository};
+use common::MoveRecord;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tokio::sync::{RwLock, broadcast, watch};
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
+use tokio::task::AbortHandle;
+use tracing::{debug, warn};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-const GAME_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
 const GAME_TIMEOUT_TICK: Duration = Duration::from_secs(15);
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Creator {
     pub id: Uuid,
     pub name: String,
@@ -20,50 +30,148 @@ impl From<User> for Creator {
     fn from(user: User) -> Self {
         Self {
             id: user.id,
-            name: user.username,
+            name: user.public_name().to_string(),
         }
     }
 }
 
+/// A move waiting to be stored, and who to tell once it is.
+type PendingMove = (MoveRecord, oneshot::Sender<()>);
+
+/// WebSocket presence of a player.
+#[derive(Debug, Clone, Copy)]
+struct Connection {
+    open_sockets: usize,
+    last_connected_at: Instant,
+}
+
+mod locked {
+    use crate::prelude::*;
+    use std::sync::Arc;
+    use std::time::Instant;
+    use tokio::sync::{RwLock, RwLockReadGuard, watch};
+
+    /// Game state behind a lock that can only be written through
+    /// [`LockedGame::write`], so every mutation refreshes the idle timer.
+    #[derive(Debug, Clone)]
+    pub(super) struct LockedGame {
+        inner: Arc<RwLock<common::Game>>,
+        activity_tx: watch::Sender<Instant>,
+    }
+
+    impl LockedGame {
+        pub(super) fn new(game: common::Game) -> Self {
+            let (activity_tx, _) = watch::channel(Instant::now());
+            Self {
+                inner: Arc::new(RwLock::new(game)),
+                activity_tx,
+            }
+        }
+
+        pub(super) async fn read(&self) -> RwLockReadGuard<'_, common::Game> {
+            self.inner.read().await
+        }
+
+        /// Run `f` under the write lock. Activity is recorded once `f`
+        /// succeeds; a rejected command does not keep the game alive.
+        pub(super) async fn write<T>(
+            &self,
+            f: impl AsyncFnOnce(&mut common::Game) -> Result<T>,
+        ) -> Result<T> {
+            let mut inner = self.inner.write().await;
+            let value = f(&mut inner).await?;
+            self.touch();
+            Ok(value)
+        }
+
+        pub(super) fn touch(&self) {
+            self.activity_tx.send_replace(Instant::now());
+        }
+
+        pub(super) fn last_activity(&self) -> Instant {
+            *self.activity_tx.borrow()
+        }
+    }
+}
+
+use locked::LockedGame;
+
 #[derive(Debug, Clone)]
 pub struct Game {
     pub id: Uuid,
-    inner: Arc<RwLock<common::Game>>,
+    inner: LockedGame,
     snapshot_tx: watch::Sender<common::Game>,
     event_tx: broadcast::Sender<GameEvent>,
-    activity_tx: watch::Sender<Instant>,
+    /// Moves queued for storage, in the order they were played.
+    move_tx: mpsc::UnboundedSender<PendingMove>,
+    turn_timer: Arc<Mutex<Option<AbortHandle>>>,
+    /// The timeout and persistence tasks, aborted by [`Game::stop`].
+    tasks: Arc<Mutex<Vec<AbortHandle>>>,
+    connections: Arc<Mutex<HashMap<Uuid, Connection>>>,
+    chat_limiter: Arc<Mutex<ChatRateLimiter>>,
+    /// Number of players, kept next to the game state so it can be read
+    /// without taking the state's lock.
+    player_count: Arc<AtomicUsize>,
+    /// When the game was first seen finished, on the Tokio clock so tests
+    /// can pause it.
+    finished_at: Arc<Mutex<Option<tokio::time::Instant>>>,
+    db: PgPool,
+    metrics: Metrics,
     pub creator: Creator,
 }
 
 impl Game {
-    pub fn new(world: common::World, creator: Creator) -> Self {
-        let inner = common::Game::new(world);
+    /// Wrap a game state that is already stored, e.g. one restored from the
+    /// database, and start its background tasks. `channel_size` is the
+    /// number of events buffered for subscribers.
+    pub fn from_snapshot(
+        inner: common::Game,
+        creator: Creator,
+        db: PgPool,
+        channel_size: usize,
+        metrics: Metrics,
+    ) -> Self {
         let (snapshot_tx, _) = watch::channel(inner.clone());
-        let (event_tx, _) = broadcast::channel(64);
-        let (activity_tx, _) = watch::channel(Instant::now());
+        let (event_tx, _) = broadcast::channel(channel_size);
+        let (move_tx, move_rx) = mpsc::unbounded_channel();
 
         let game = Self {
             id: inner.id,
-            inner: Arc::new(RwLock::new(inner)),
+            inner: LockedGame::new(inner.clone()),
             snapshot_tx,
             event_tx,
-            activity_tx,
+            move_tx,
+            turn_timer: Arc::new(Mutex::new(None)),
+            tasks: Arc::new(Mutex::new(Vec::new())),
+            connections: Arc::new(Mutex::new(HashMap::new())),
+            chat_limiter: Arc::new(Mutex::new(ChatRateLimiter::default())),
+            player_count: Arc::new(AtomicUsize::new(inner.players.len())),
+            finished_at: Arc::new(Mutex::new(
+                (inner.state == common::GameState::Finished).then(tokio::time::Instant::now),
+            )),
+            db,
+            metrics,
             creator,
         };
 
         game.spawn_timeout_task();
+        game.spawn_persistence_task();
+        game.spawn_move_persistence_task(move_rx);
+        game.restart_turn_timer(&inner);
         game
     }
 
     pub async fn join_player(&self, player_id: Uuid, player_name: String) -> Result<()> {
         let event_name = player_name.clone();
-        let snapshot = {
-            let mut inner = self.inner.write().await;
-            inner.join_player(player_id, player_name)?;
-            inner.clone()
-        };
+        let snapshot = self
+            .with_write_lock(async |inner| {
+                inner.join_player(player_id, player_name)?;
+                self.player_count
+                    .store(inner.players.len(), Ordering::Relaxed);
+                Ok(inner.clone())
+            })
+            .await?;
 
-        self.touch_activity();
         self.publish_event(GameEvent::PlayerJoined {
             player_id,
             player_name: event_name,
@@ -72,64 +180,346 @@ impl Game {
         Ok(())
     }
 
+    pub async fn join_spectator(&self, spectator_id: Uuid, spectator_name: String) -> Result<()> {
+        let event_name = spectator_name.clone();
+        let snapshot = self
+            .with_write_lock(async |inner| {
+                inner.join_spectator(spectator_id, spectator_name)?;
+                Ok(inner.clone())
+            })
+            .await?;
+
+        self.publish_event(GameEvent::SpectatorJoined {
+            spectator_id,
+            spectator_name: event_name,
+        });
+        self.publish_snapshot(snapshot);
+        Ok(())
+    }
+
+    /// Number of players who have joined the game.
+    pub fn player_count(&self) -> usize {
+        self.player_count.load(Ordering::Relaxed)
+    }
+
+    /// Whether no more players can join the game.
+    pub async fn is_full(&self) -> bool {
+        self.inner.read().await.is_full()
+    }
+
+    pub async fn has_player(&self, user_id: Uuid) -> bool {
+        self.inner
+            .read()
+            .await
+            .players
+            .iter()
+            .any(|p| p.id == user_id)
+    }
+
+    pub async fn is_spectator(&self, user_id: Uuid) -> bool {
+        self.inner.read().await.is_spectator(user_id)
+    }
+
     pub async fn start_game(&self) -> Result<()> {
-        let snapshot = {
-            let mut inner = self.inner.write().await;
-            inner.start()?;
-            inner.clone()
-        };
+        let snapshot = self
+            .with_write_lock(async |inner| {
+                inner.start()?;
+                Ok(inner.clone())
+            })
+            .await?;
 
-        self.touch_activity();
         self.publish_event(GameEvent::GameStarted);
+        self.publish_turn_started(&snapshot);
+        self.restart_turn_timer(&snapshot);
         self.publish_snapshot(snapshot);
         Ok(())
     }
 
     pub async fn attack(&self, from_id: Uuid, to_id: Uuid, player_id: Uuid) -> Result<()> {
-        let snapshot = {
-            let mut inner = self.inner.write().await;
-            inner.attack(from_id, to_id, player_id)?;
-            inner.clone()
-        };
+        let (result, defender, snapshot, saved) = self
+            .with_write_lock(async |inner| {
+                let defender = inner.world.areas.get(&to_id).and_then(|area| area.owner);
+                let result = inner.attack(from_id, to_id, player_id)?;
+                let saved = self.queue_last_move(inner);
+                Ok((result, defender, inner.clone(), saved))
+            })
+            .await?;
+        move_saved(saved).await;
 
-        self.touch_activity();
+        self.metrics.record_attack(&result);
         self.publish_event(GameEvent::AttackResolved {
             from_id,
             to_id,
             player_id,
+            result,
         });
+
+        if let Some(defender) = defender
+            && result.captured
+            && snapshot.world.is_player_eliminated(defender)
+        {
+            self.publish_event(GameEvent::PlayerEliminated {
+                player_id: defender,
+                eliminated_by: Some(player_id),
+            });
+        }
+
+        if snapshot.state == common::GameState::Finished {
+            self.publish_event(GameEvent::finished(&snapshot, "Last player standing"));
+            self.update_ratings(player_id, &snapshot.players).await;
+        }
+
+        self.restart_turn_timer(&snapshot);
         self.publish_snapshot(snapshot);
         Ok(())
     }
 
     pub async fn end_turn(&self, player_id: Uuid) -> Result<()> {
-        let snapshot = {
-            let mut inner = self.inner.write().await;
+        let (snapshot, placed, saved) = self
+            .with_write_lock(async |inner| {
+                let placed = inner.end_turn(player_id)?;
+                let saved = self.queue_last_move(inner);
+                Ok((inner.clone(), placed, saved))
+            })
+            .await?;
+        move_saved(saved).await;
 
-            if let common::GameState::InProgress { turn } = inner.state
-                && inner.players[turn].id != player_id
-            {
-                return Err(common::GameError::NotPlayerTurn.into());
-            }
+        self.publish_event(GameEvent::dice_distributed(player_id, &placed));
+        self.publish_event(GameEvent::TurnEnded { player_id });
+        self.publish_turn_started(&snapshot);
+        self.restart_turn_timer(&snapshot);
+        self.publish_snapshot(snapshot);
+        Ok(())
+    }
 
-            inner.end_turn()?;
-            inner.clone()
-        };
+    /// Let `player_id` give up the game, leaving their areas unowned. The
+    /// game finishes if a single player is left.
+    pub async fn resign(&self, player_id: Uuid) -> Result<()> {
+        let (winner, turn_passed, snapshot, saved) = self
+            .with_write_lock(async |inner| {
+                let turn_number = inner.turn_number;
+                let winner = inner.resign(player_id)?;
+                let saved = self.queue_last_move(inner);
+                Ok((
+                    winner,
+                    inner.turn_number != turn_number,
+                    inner.clone(),
+                    saved,
+                ))
+            })
+            .await?;
+        move_saved(saved).await;
+
+        self.publish_event(GameEvent::PlayerEliminated {
+            player_id,
+            eliminated_by: None,
+        });
+
+        if let Some(winner) = winner {
+            self.publish_event(GameEvent::finished(&snapshot, "Last player standing"));
+            self.update_ratings(winner, &snapshot.players).await;
+            self.restart_turn_timer(&snapshot);
+        } else if turn_passed {
+            self.publish_turn_started(&snapshot);
+            self.restart_turn_timer(&snapshot);
+        }
+
+        self.publish_snapshot(snapshot);
+        Ok(())
+    }
+
+    /// Send a chat message from `user_id` to everyone in the game. Messages
+    /// are not stored, only broadcast to the sockets connected right now.
+    pub fn chat(&self, user_id: Uuid, user_name: &str, message: &str) -> Result<()> {
+        let message = sanitize_chat_message(message)?;
+        self.chat_limiter
+            .lock()
+            .expect("chat limiter lock poisoned")
+            .try_send(user_id, Instant::now())
+            .map_err(|cooldown| {
+                Error::BadRequest(format!(
+                    "too many chat messages, try again in {} seconds",
+                    cooldown.as_secs().max(1)
+                ))
+            })?;
 
         self.touch_activity();
-        self.publish_event(GameEvent::TurnEnded { player_id });
+        self.publish_event(GameEvent::ChatMessage {
+            player_id: user_id,
+            player_name: user_name.to_string(),
+            message,
+        });
+        Ok(())
+    }
+
+    /// End the game without a winner, telling everyone connected why. The
+    /// final snapshot is stored right away, so the game is not restored
+    /// even if it is stopped before the persistence task catches up.
+    pub async fn cancel(&self, reason: &str) -> Result<()> {
+        self.cancel_if(reason, true).await
+    }
+
+    /// Like [`Self::cancel`], but refuses with [`Error::Forbidden`] if the
+    /// game is in progress. The state is checked under the same lock that
+    /// ends the game, so it cannot start in between.
+    pub async fn cancel_unless_in_progress(&self, reason: &str) -> Result<()> {
+        self.cancel_if(reason, false).await
+    }
+
+    async fn cancel_if(&self, reason: &str, allow_in_progress: bool) -> Result<()> {
+        let snapshot = self
+            .with_write_lock(async |inner| {
+                if !allow_in_progress && matches!(inner.state, common::GameState::InProgress { .. })
+                {
+                    return Err(Error::Forbidden(
+                        "a game in progress can only be deleted by an admin".to_string(),
+                    ));
+                }
+                inner.state = common::GameState::Finished;
+                Ok(inner.clone())
+            })
+            .await?;
+        GameRepository::new(&self.db)
+            .save_snapshot(self.id, &snapshot)
+            .await?;
+
+        self.publish_event(GameEvent::GameFinished {
+            reason: reason.to_string(),
+            winner_id: None,
+            winner_name: None,
+        });
+        self.restart_turn_timer(&snapshot);
         self.publish_snapshot(snapshot);
         Ok(())
     }
 
+    /// Let everyone know that `player_id` closed their connection.
+    pub fn player_left(&self, player_id: Uuid, player_name: String) {
+        self.publish_event(GameEvent::PlayerLeft {
+            player_id,
+            player_name,
+        });
+    }
+
+    /// Record that `player_id` opened a connection to the game.
+    pub fn player_connected(&self, player_id: Uuid) {
+        let mut connections = self.connections.lock().expect("connections lock poisoned");
+        let connection = connections.entry(player_id).or_insert(Connection {
+            open_sockets: 0,
+            last_connected_at: Instant::now(),
+        });
+        connection.open_sockets += 1;
+        connection.last_connected_at = Instant::now();
+    }
+
+    /// Record that one of `player_id`'s connections closed. Once their last
+    /// connection is gone they have the reconnect grace period to come back
+    /// before their turn is skipped.
+    pub async fn player_disconnected(&self, player_id: Uuid) {
+        {
+            let mut connections = self.connections.lock().expect("connections lock poisoned");
+            let Some(connection) = connections.get_mut(&player_id) else {
+                return;
+            };
+            connection.open_sockets = connection.open_sockets.saturating_sub(1);
+            connection.last_connected_at = Instant::now();
+            if connection.open_sockets > 0 {
+                return;
+            }
+        }
+
+        let grace = self.inner.read().await.config.reconnect_grace_period();
+        let game = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(grace).await;
+            game.skip_turn_if_absent(player_id).await;
+        });
+    }
+
+    /// Whether `player_id` is connected, or lost their connection less than
+    /// `grace` ago. Players who never connected count as present.
+    fn is_present(&self, player_id: Uuid, grace: Duration) -> bool {
+        let connections = self.connections.lock().expect("connections lock poisoned");
+        connections.get(&player_id).is_none_or(|connection| {
+            connection.open_sockets > 0 || connection.last_connected_at.elapsed() < grace
+        })
+    }
+
+    /// How long `player_id` still has to reconnect, if they are disconnected
+    /// but within the grace period.
+    fn grace_remaining(&self, player_id: Uuid, grace: Duration) -> Option<Duration> {
+        let connections = self.connections.lock().expect("connections lock poisoned");
+        let connection = connections.get(&player_id)?;
+        if connection.open_sockets > 0 {
+            return None;
+        }
+        grace
+            .checked_sub(connection.last_connected_at.elapsed())
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// End `player_id`'s turn if it is theirs and they are no longer
+    /// present. Nothing is skipped when nobody is present, leaving the game
+    /// to time out instead of cycling through absent players.
+    async fn skip_turn_if_absent(&self, player_id: Uuid) {
+        let snapshot = self.snapshot().await;
+        let grace = snapshot.config.reconnect_grace_period();
+        let common::GameState::InProgress { turn } = snapshot.state else {
+            return;
+        };
+        if snapshot.players.get(turn).is_none_or(|p| p.id != player_id)
+            || self.is_present(player_id, grace)
+            || !snapshot
+                .players
+                .iter()
+                .any(|p| self.is_present(p.id, grace))
+        {
+            return;
+        }
+
+        self.publish_event(GameEvent::TurnSkipped { player_id });
+        if let Err(err) = self.end_turn(player_id).await {
+            debug!(game_id = %self.id, %player_id, "Skipped turn was already over: {err}");
+        }
+    }
+
+    /// Keep the game from timing out without changing it, e.g. on a ping.
     pub fn touch_activity(&self) {
-        let _ = self.activity_tx.send(Instant::now());
+        self.inner.touch();
+    }
+
+    /// Mutate the game state. Every mutation counts as activity, so the
+    /// idle timeout cannot be forgotten when adding a new command.
+    async fn with_write_lock<T>(
+        &self,
+        f: impl AsyncFnOnce(&mut common::Game) -> Result<T>,
+    ) -> Result<T> {
+        self.inner.write(f).await
+    }
+
+    /// When the game state last changed or the game was last pinged.
+    pub fn last_activity(&self) -> Instant {
+        self.inner.last_activity()
     }
 
     pub async fn snapshot(&self) -> common::Game {
         self.inner.read().await.clone()
     }
 
+    pub async fn has_fog_of_war(&self) -> bool {
+        self.inner.read().await.config.fog_of_war
+    }
+
+    /// Areas whose dice `user_id` may see, or `None` without fog of war.
+    pub async fn visible_areas_for(&self, user_id: Uuid) -> Option<HashSet<Uuid>> {
+        self.inner.read().await.visible_areas(user_id)
+    }
+
+    /// The current game state as seen by `user_id`, respecting fog of war.
+    pub async fn snapshot_for(&self, user_id: Uuid) -> common::Game {
+        self.inner.read().await.snapshot_for_player(user_id)
+    }
+
     pub fn subscribe_events(&self) -> broadcast::Receiver<GameEvent> {
         self.event_tx.subscribe()
     }
@@ -144,52 +534,252 @@ impl Game {
             id: self.id,
             creator: self.creator.clone(),
             player_count: snapshot.players.len(),
+            players: snapshot.players.iter().map(PlayerPreview::from).collect(),
+            spectator_count: snapshot.spectators.len(),
             state: snapshot.state,
         }
     }
 
     fn spawn_timeout_task(&self) {
         let game = self.clone();
-        tokio::spawn(async move {
+        let handle = tokio::spawn(async move {
             game.run_timeout_loop().await;
         });
+        self.track_task(handle.abort_handle());
+    }
+
+    fn track_task(&self, handle: AbortHandle) {
+        self.tasks.lock().expect("tasks lock poisoned").push(handle);
+    }
+
+    /// Stop the game's background tasks and turn timer, e.g. when it is
+    /// removed from the store. Nothing is persisted after this.
+    pub fn stop(&self) {
+        for handle in self.tasks.lock().expect("tasks lock poisoned").drain(..) {
+            handle.abort();
+        }
+        if let Some(handle) = self
+            .turn_timer
+            .lock()
+            .expect("turn timer lock poisoned")
+            .take()
+        {
+            handle.abort();
+        }
+    }
+
+    /// When the game finished, or `None` while it is still going.
+    pub fn finished_at(&self) -> Option<tokio::time::Instant> {
+        *self.finished_at.lock().expect("finished_at lock poisoned")
     }
 
     async fn run_timeout_loop(self) {
+        let idle_timeout = self.inner.read().await.config.idle_timeout();
         let mut ticker = tokio::time::interval(GAME_TIMEOUT_TICK);
 
         loop {
             ticker.tick().await;
 
-            let last_activity = *self.activity_tx.borrow();
-            if last_activity.elapsed() < GAME_IDLE_TIMEOUT {
+            if self.last_activity().elapsed() < idle_timeout {
                 continue;
             }
 
-            let timed_out_snapshot = {
-                let mut inner = self.inner.write().await;
-                if !matches!(inner.state, common::GameState::InProgress { .. }) {
-                    continue;
-                }
+            let timed_out = self
+                .with_write_lock(async |inner| {
+                    if !matches!(inner.state, common::GameState::InProgress { .. }) {
+                        return Ok(None);
+                    }
 
-                inner.state = common::GameState::Finished;
-                inner.clone()
+                    inner.state = common::GameState::Finished;
+                    Ok(Some(inner.clone()))
+                })
+                .await;
+            let Ok(Some(timed_out_snapshot)) = timed_out else {
+                continue;
             };
 
-            self.publish_event(GameEvent::Finished {
-                reason: "Game timed out due to inactivity".to_string(),
-            });
+            self.publish_event(GameEvent::finished(
+                &timed_out_snapshot,
+                "Game timed out due to inactivity",
+            ));
+            self.restart_turn_timer(&timed_out_snapshot);
             self.publish_snapshot(timed_out_snapshot);
             break;
         }
     }
 
+    /// Cancel the running turn timer and, if the game is in progress and has
+    /// a turn time limit, start a new one for the current player. A turn
+    /// passed to a player who is no longer present is skipped right away.
+    fn restart_turn_timer(&self, snapshot: &common::Game) {
+        let mut turn_timer = self.turn_timer.lock().expect("turn timer lock poisoned");
+        if let Some(handle) = turn_timer.take() {
+            handle.abort();
+        }
+
+        let common::GameState::InProgress { turn } = &snapshot.state else {
+            return;
+        };
+        let Some(player) = snapshot.players.get(*turn) else {
+            return;
+        };
+
+        let grace = snapshot.config.reconnect_grace_period();
+        if !self.is_present(player.id, grace) {
+            let game = self.clone();
+            let player_id = player.id;
+            tokio::spawn(async move { game.skip_turn_if_absent(player_id).await });
+        }
+
+        let Some(limit) = snapshot.config.turn_time_limit else {
+            return;
+        };
+
+        self.publish_event(GameEvent::TurnTimerStarted {
+            seconds_remaining: limit.as_secs(),
+        });
+
+        let game = self.clone();
+        let player_id = player.id;
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(limit).await;
+            // A player who just lost their connection gets to reconnect first
+            while let Some(remaining) = game.grace_remaining(player_id, grace) {
+                tokio::time::sleep(remaining).await;
+            }
+            game.expire_turn(player_id).await;
+        });
+        *turn_timer = Some(task.abort_handle());
+    }
+
+    /// End `player_id`'s turn after their time ran out.
+    async fn expire_turn(&self, player_id: Uuid) {
+        // Forget our own handle so ending the turn does not abort this task
+        self.turn_timer
+            .lock()
+            .expect("turn timer lock poisoned")
+            .take();
+
+        self.publish_event(GameEvent::TurnTimerExpired { player_id });
+        if let Err(err) = self.end_turn(player_id).await {
+            debug!(game_id = %self.id, %player_id, "Expired turn was already over: {err}");
+        }
+    }
+
+    /// Apply ELO updates for a game won by `winner_id`. All other players
+    /// share second place.
+    async fn update_ratings(&self, winner_id: Uuid, players: &[common::Player]) {
+        let repo = UserRepository::new(&self.db);
+        let mut participants = Vec::with_capacity(players.len());
+
+        for player in players {
+            match repo.find_by_id(player.id).await {
+                Ok(Some(user)) => participants.push(GameParticipant {
+                    user_id: user.id,
+                    rating: user.rating,
+                    placement: if user.id == winner_id { 1 } else { 2 },
+                }),
+                Ok(None) => {}
+                Err(err) => {
+                    warn!(game_id = %self.id, "Failed to load players for rating update: {err}");
+                    return;
+                }
+            }
+        }
+
+        for (user_id, new_rating) in rating::update_ratings(&participants) {
+            if let Err(err) = repo.update_rating(user_id, new_rating).await {
+                warn!(game_id = %self.id, %user_id, "Failed to store rating: {err}");
+            }
+        }
+    }
+
+    /// Queue the move just added to `game`'s move log for storage. Called
+    /// while holding the game's write lock so moves are stored in the order
+    /// they were played, without holding the lock for the database write.
+    fn queue_last_move(&self, game: &common::Game) -> Option<oneshot::Receiver<()>> {
+        let move_record = game.move_log().last()?.clone();
+        let (saved_tx, saved_rx) = oneshot::channel();
+        self.move_tx.send((move_record, saved_tx)).ok()?;
+        Some(saved_rx)
+    }
+
+    fn spawn_move_persistence_task(&self, mut moves: mpsc::UnboundedReceiver<PendingMove>) {
+        let game = self.clone();
+        let handle = tokio::spawn(async move {
+            let repo = GameRepository::new(&game.db);
+            while let Some((move_record, saved_tx)) = moves.recv().await {
+                if let Err(err) = repo
+                    .save_move(game.id, move_record.turn(), &move_record)
+                    .await
+                {
+                    warn!(game_id = %game.id, "Failed to persist game move: {err}");
+                }
+                let _ = saved_tx.send(());
+            }
+        });
+        self.track_task(handle.abort_handle());
+    }
+
+    fn spawn_persistence_task(&self) {
+        // Subscribe before spawning so snapshots published before the task
+        // first runs are still stored
+        let snapshots = self.subscribe_snapshot();
+        let game = self.clone();
+        let handle = tokio::spawn(async move {
+            game.run_persistence_loop(snapshots).await;
+        });
+        self.track_task(handle.abort_handle());
+    }
+
+    /// Store every published snapshot until the game has finished.
+    async fn run_persistence_loop(self, mut snapshots: watch::Receiver<common::Game>) {
+        let repo = GameRepository::new(&self.db);
+
+        while snapshots.changed().await.is_ok() {
+            let snapshot = snapshots.borrow_and_update().clone();
+            if let Err(err) = repo.save_snapshot(self.id, &snapshot).await {
+                warn!(game_id = %self.id, "Failed to persist game snapshot: {err}");
+            }
+
+            if snapshot.state == common::GameState::Finished {
+                break;
+            }
+        }
+    }
+
     fn publish_snapshot(&self, snapshot: common::Game) {
-        let _ = self.snapshot_tx.send(snapshot.clone());
-        self.publish_event(GameEvent::Snapshot { game: snapshot });
+        if snapshot.state == common::GameState::Finished {
+            self.finished_at
+                .lock()
+                .expect("finished_at lock poisoned")
+                .get_or_insert_with(tokio::time::Instant::now);
+        }
+
+        // Publish while holding the watch lock so diffs go out in the order
+        // they were computed and always apply to the previous snapshot
+        self.snapshot_tx.send_modify(|current| {
+            let event = GameEvent::update(current, &snapshot);
+            *current = snapshot;
+            self.publish_event(event);
+        });
+    }
+
+    fn publish_turn_started(&self, snapshot: &common::Game) {
+        if let Some(event) = GameEvent::turn_started(snapshot) {
+            self.publish_event(event);
+        }
     }
 
     fn publish_event(&self, event: GameEvent) {
         let _ = self.event_tx.send(event);
     }
 }
+
+/// Wait until a move queued by [`Game::queue_last_move`] has been stored,
+/// so it is in the game's history once the command returns.
+async fn move_saved(saved: Option<oneshot::Receiver<()>>) {
+    if let Some(saved) = saved {
+        let _ = saved.await;
+    }
+}
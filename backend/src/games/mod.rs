@@ -1,53 +1,167 @@
+mod chat;
 mod event;
 mod game;
+mod worlds;
 
+use crate::metrics::Metrics;
+use crate::prelude::*;
+use crate::repositories::GameRepository;
+pub use chat::*;
 pub use event::*;
 pub use game::*;
+use sqlx::PgPool;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{RwLock, watch};
 use uuid::Uuid;
+pub use worlds::*;
+
+/// How long a finished game stays in the store, so players can still look
+/// at the final board, before [`Games::cleanup_finished`] removes it.
+pub const FINISHED_GAME_EXPIRY: Duration = Duration::from_secs(10 * 60);
+
+const FINISHED_GAME_CLEANUP_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+type GameMap = RwLock<HashMap<Uuid, Game>>;
 
 #[derive(Debug, Clone)]
 pub struct Games {
-    games: Arc<RwLock<HashMap<Uuid, Game>>>,
+    games: Arc<GameMap>,
     list_tx: watch::Sender<Vec<GameListItem>>,
+    db: PgPool,
+    channel_size: usize,
+    metrics: Metrics,
 }
 
-impl Default for Games {
-    fn default() -> Self {
+impl Games {
+    /// Create an empty game store. Each game buffers up to `channel_size`
+    /// events for its subscribers. Finished games are cleaned up in the
+    /// background, until the last clone of the store is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a Tokio runtime, as the cleanup task is
+    /// spawned on it.
+    pub fn new(db: PgPool, channel_size: usize) -> Self {
         let (list_tx, _) = watch::channel(Vec::new());
-        Self {
+        let games = Self {
             games: Arc::new(RwLock::new(HashMap::new())),
             list_tx,
-        }
+            db,
+            channel_size,
+            metrics: Metrics::new(),
+        };
+        games.spawn_cleanup_task();
+        games
     }
-}
 
-impl Games {
-    pub async fn create_game(&self, world: common::World, creator: Creator) -> Game {
-        let game = Game::new(world, creator);
-        let game_id = game.id;
-        self.games.write().await.insert(game_id, game.clone());
+    /// Counters shared by every game in the store.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
 
-        let mut snapshots = game.subscribe_snapshot();
-        let games = self.clone();
-        tokio::spawn(async move {
-            while snapshots.changed().await.is_ok() {
-                games.publish_list_snapshot().await;
-            }
-        });
+    pub async fn create_game(
+        &self,
+        world: common::World,
+        config: common::GameConfig,
+        creator: Creator,
+    ) -> Result<Game> {
+        world
+            .validate()
+            .map_err(|err| Error::BadRequest(format!("Invalid world: {err}")))?;
+
+        // Store the game before its tasks start, so a failed insert leaves
+        // nothing running
+        let snapshot = common::Game::with_config(world, config);
+        GameRepository::new(&self.db)
+            .create(&creator, &snapshot)
+            .await?;
+        self.metrics.games_created_total.inc();
+
+        let game = Game::from_snapshot(
+            snapshot,
+            creator,
+            self.db.clone(),
+            self.channel_size,
+            self.metrics.clone(),
+        );
 
+        self.insert_game(game.clone()).await;
         self.publish_list_snapshot().await;
-        game
+        Ok(game)
+    }
+
+    /// Restore all unfinished games stored in the database.
+    pub async fn load_from_db(db: &PgPool, channel_size: usize) -> Result<Self> {
+        let games = Self::new(db.clone(), channel_size);
+
+        for (creator, snapshot) in GameRepository::new(db).find_unfinished().await? {
+            let game = Game::from_snapshot(
+                snapshot,
+                creator,
+                db.clone(),
+                channel_size,
+                games.metrics.clone(),
+            );
+            games.insert_game(game).await;
+        }
+
+        games.publish_list_snapshot().await;
+        Ok(games)
     }
 
     pub async fn get_game(&self, game_id: &Uuid) -> Option<Game> {
         self.games.read().await.get(game_id).cloned()
     }
 
+    /// Remove a game from the store and stop it. Returns whether it existed.
+    /// The game stays in the database.
+    pub async fn remove_game(&self, game_id: Uuid) -> bool {
+        let Some(game) = self.games.write().await.remove(&game_id) else {
+            return false;
+        };
+
+        game.stop();
+        self.publish_list_snapshot().await;
+        true
+    }
+
+    /// Remove every game that finished more than [`FINISHED_GAME_EXPIRY`]
+    /// ago, returning how many were removed.
+    pub async fn cleanup_finished(&self) -> usize {
+        Self::remove_expired(&self.games, &self.list_tx).await
+    }
+
+    async fn remove_expired(games: &GameMap, list_tx: &watch::Sender<Vec<GameListItem>>) -> usize {
+        let removed: Vec<Game> = {
+            let mut games = games.write().await;
+            let expired: Vec<Uuid> = games
+                .values()
+                .filter(|game| {
+                    game.finished_at()
+                        .is_some_and(|at| at.elapsed() >= FINISHED_GAME_EXPIRY)
+                })
+                .map(|game| game.id)
+                .collect();
+            expired.iter().filter_map(|id| games.remove(id)).collect()
+        };
+
+        for game in &removed {
+            game.stop();
+        }
+        if !removed.is_empty() {
+            Self::publish_list(games, list_tx).await;
+        }
+        removed.len()
+    }
+
     pub async fn list_games(&self) -> Vec<GameListItem> {
-        let games: Vec<Game> = self.games.read().await.values().cloned().collect();
+        Self::list_items(&self.games).await
+    }
+
+    async fn list_items(games: &GameMap) -> Vec<GameListItem> {
+        let games: Vec<Game> = games.read().await.values().cloned().collect();
         let mut out = Vec::with_capacity(games.len());
 
         for game in games {
@@ -57,12 +171,106 @@ impl Games {
         out
     }
 
+    /// All games, in any state, that `player_id` has joined as a player.
+    pub async fn for_player(&self, player_id: Uuid) -> Vec<GameListItem> {
+        let games: Vec<Game> = self.games.read().await.values().cloned().collect();
+        let mut out = Vec::new();
+
+        for game in games {
+            if game.has_player(player_id).await {
+                out.push(game.list_item().await);
+            }
+        }
+
+        out
+    }
+
+    /// Number of players taking part in games that are currently in progress.
+    pub async fn total_active_player_count(&self) -> usize {
+        let games: Vec<Game> = self.games.read().await.values().cloned().collect();
+        let mut total = 0;
+
+        for game in games {
+            let snapshot = game.snapshot().await;
+            if matches!(snapshot.state, common::GameState::InProgress { .. }) {
+                total += snapshot.players.len();
+            }
+        }
+
+        total
+    }
+
     pub fn subscribe_list(&self) -> watch::Receiver<Vec<GameListItem>> {
         self.list_tx.subscribe()
     }
 
+    /// Watch the full state of a single game, or `None` if it does not exist.
+    pub async fn subscribe_game(&self, game_id: &Uuid) -> Option<watch::Receiver<common::Game>> {
+        self.games
+            .read()
+            .await
+            .get(game_id)
+            .map(Game::subscribe_snapshot)
+    }
+
+    /// Background tasks only hold a [`Weak`](std::sync::Weak) reference to
+    /// the games, so they end once the store is dropped instead of keeping
+    /// it alive.
+    fn spawn_cleanup_task(&self) {
+        let games = Arc::downgrade(&self.games);
+        let list_tx = self.list_tx.clone();
+        tokio::spawn(async move {
+            let start = tokio::time::Instant::now() + FINISHED_GAME_CLEANUP_INTERVAL;
+            let mut ticker = tokio::time::interval_at(start, FINISHED_GAME_CLEANUP_INTERVAL);
+            loop {
+                ticker.tick().await;
+                let Some(games) = games.upgrade() else {
+                    break;
+                };
+                Self::remove_expired(&games, &list_tx).await;
+            }
+        });
+    }
+
+    async fn insert_game(&self, game: Game) {
+        self.games.write().await.insert(game.id, game.clone());
+
+        let mut snapshots = game.subscribe_snapshot();
+        let games = Arc::downgrade(&self.games);
+        let list_tx = self.list_tx.clone();
+        tokio::spawn(async move {
+            while snapshots.changed().await.is_ok() {
+                let Some(games) = games.upgrade() else {
+                    break;
+                };
+                Self::publish_list(&games, &list_tx).await;
+            }
+        });
+    }
+
     async fn publish_list_snapshot(&self) {
-        let snapshot = self.list_games().await;
-        let _ = self.list_tx.send(snapshot);
+        Self::publish_list(&self.games, &self.list_tx).await;
+    }
+
+    async fn publish_list(games: &GameMap, list_tx: &watch::Sender<Vec<GameListItem>>) {
+        let snapshot = Self::list_items(games).await;
+        list_tx.send_replace(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::DEFAULT_GAME_BROADCAST_CHANNEL_SIZE;
+
+    #[tokio::test]
+    async fn dropping_store_releases_games() {
+        let db = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let games = Games::new(db, DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+        let map = Arc::downgrade(&games.games);
+
+        drop(games);
+
+        assert!(map.upgrade().is_none());
     }
 }
@@ -0,0 +1,161 @@
+use serde::Serialize;
+use std::sync::Arc;
+use thiserror::Error;
+use utoipa::ToSchema;
+
+/// Name of the world used when a game is created without picking one.
+pub const DEFAULT_WORLD: &str = "default";
+
+/// Presets embedded in the binary as `(name, source)`.
+const EMBEDDED_WORLDS: &[(&str, &str)] = &[
+    (DEFAULT_WORLD, include_str!("../../worlds/default.world")),
+    ("small", include_str!("../../worlds/small.world")),
+    ("large", include_str!("../../worlds/large.world")),
+];
+
+#[derive(Debug, Error)]
+#[error("world '{name}' is invalid: {source}")]
+pub struct InvalidWorldError {
+    pub name: String,
+    #[source]
+    pub source: common::WorldError,
+}
+
+/// Public description of a world preset. `name` is the key used to pick
+/// the preset; the rest comes from the world file's metadata header.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, ToSchema)]
+pub struct WorldMeta {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub author: String,
+    pub min_players: usize,
+    pub max_players: usize,
+    pub area_count: usize,
+}
+
+#[derive(Debug)]
+struct WorldPreset {
+    meta: WorldMeta,
+    source: &'static str,
+}
+
+/// The world presets embedded in the binary.
+#[derive(Debug, Clone)]
+pub struct WorldStore {
+    presets: Arc<Vec<WorldPreset>>,
+}
+
+impl WorldStore {
+    /// Load the embedded presets, failing if any of them is not playable.
+    pub fn new() -> Result<Self, InvalidWorldError> {
+        Self::from_sources(EMBEDDED_WORLDS)
+    }
+
+    fn from_sources(sources: &[(&str, &'static str)]) -> Result<Self, InvalidWorldError> {
+        let mut presets = Vec::with_capacity(sources.len());
+        for &(name, source) in sources {
+            let world =
+                common::World::from_str_checked(source).map_err(|err| InvalidWorldError {
+                    name: name.to_string(),
+                    source: err,
+                })?;
+
+            let area_count = world.areas.len();
+            let metadata = world.metadata;
+            presets.push(WorldPreset {
+                meta: WorldMeta {
+                    name: name.to_string(),
+                    title: metadata.name,
+                    description: metadata.description,
+                    author: metadata.author,
+                    min_players: metadata.min_players,
+                    max_players: metadata.max_players,
+                    area_count,
+                },
+                source,
+            });
+        }
+
+        Ok(Self {
+            presets: Arc::new(presets),
+        })
+    }
+
+    pub fn list(&self) -> Vec<WorldMeta> {
+        self.presets
+            .iter()
+            .map(|preset| preset.meta.clone())
+            .collect()
+    }
+
+    /// Build a fresh world from the preset called `name`.
+    pub fn load(&self, name: &str) -> Option<common::World> {
+        self.presets
+            .iter()
+            .find(|preset| preset.meta.name == name)
+            .and_then(|preset| common::World::from_str_checked(preset.source).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_worlds_are_valid() {
+        let store = WorldStore::new().unwrap();
+        for meta in store.list() {
+            let world = store.load(&meta.name).unwrap();
+            assert_eq!(world.areas.len(), meta.area_count);
+            assert!(meta.min_players <= meta.max_players);
+        }
+    }
+
+    #[test]
+    fn default_world_metadata_is_read_from_file() {
+        let store = WorldStore::new().unwrap();
+        let meta = store
+            .list()
+            .into_iter()
+            .find(|meta| meta.name == DEFAULT_WORLD)
+            .unwrap();
+
+        assert_eq!(meta.title, "Twin Ridges");
+        assert_eq!(meta.author, "rsdice");
+        assert_eq!((meta.min_players, meta.max_players), (2, 2));
+        assert!(!meta.description.is_empty());
+    }
+
+    #[test]
+    fn malformed_world_is_rejected() {
+        let err = WorldStore::from_sources(&[("broken", "0,0 1,0\nnot a tile")]).unwrap_err();
+        assert_eq!(err.name, "broken");
+        assert_eq!(err.source, common::WorldError::NotEnoughAreas(1));
+    }
+
+    #[test]
+    fn non_contiguous_area_is_rejected_with_its_index() {
+        let err = WorldStore::from_sources(&[("split", "0,0 1,0\n2,0 3,0 5,0")]).unwrap_err();
+        assert_eq!(
+            err.source,
+            common::WorldError::Parse(common::WorldParseError::NonContiguousArea {
+                area_index: 1,
+                tile_count: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn load_unknown_world_returns_none() {
+        assert!(WorldStore::new().unwrap().load("unknown").is_none());
+    }
+
+    #[test]
+    fn loaded_worlds_get_fresh_area_ids() {
+        let store = WorldStore::new().unwrap();
+        let first = store.load("small").unwrap();
+        let second = store.load("small").unwrap();
+        assert!(first.areas.keys().all(|id| !second.areas.contains_key(id)));
+    }
+}
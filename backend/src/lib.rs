@@ -1,6 +1,8 @@
 pub mod email;
 pub mod games;
+pub mod metrics;
 pub mod models;
 pub mod prelude;
+pub mod rating;
 pub mod repositories;
 pub mod routes;
@@ -1,11 +1,12 @@
-use std::net::Ipv4Addr;
+use std::net::{Ipv4Addr, SocketAddr};
 
-use axum::Router;
+use backend::games::{Games, WorldStore};
 use backend::prelude::*;
 use backend::routes;
 use sqlx::PgPool;
 use thiserror::Error;
 use tokio::net::TcpListener;
+use tokio::signal;
 use tower_http::trace::TraceLayer;
 use tracing::{debug, error, info};
 use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
@@ -23,6 +24,9 @@ enum AppError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Game restore error: {0}")]
+    Games(#[from] Error),
 }
 
 #[tokio::main]
@@ -45,6 +49,14 @@ async fn app() -> std::result::Result<(), AppError> {
     let config = Config::from_env()?;
     debug!("Configuration loaded: {:?}", config);
 
+    let worlds = match WorldStore::new() {
+        Ok(worlds) => worlds,
+        Err(e) => {
+            error!("Embedded world files are invalid: {e}");
+            std::process::exit(1);
+        }
+    };
+
     let db = PgPool::connect(&config.database_url).await?;
     info!("Connected to database");
 
@@ -52,10 +64,16 @@ async fn app() -> std::result::Result<(), AppError> {
     sqlx::migrate!("./migrations").run(&db).await?;
     info!("Migrations complete");
 
-    let state = AppState::new(config.clone(), db);
+    let games = Games::load_from_db(&db, config.game_broadcast_channel_size).await?;
+    info!(
+        "Restored {} unfinished games",
+        games.list_games().await.len()
+    );
+
+    let state = AppState::new(config.clone(), db, games, worlds);
+    let shutdown = state.shutdown.clone();
 
-    let app = Router::new()
-        .nest("/api", routes::routes())
+    let app = routes::app(&config)
         .layer(TraceLayer::new_for_http())
         .with_state(state);
 
@@ -63,7 +81,42 @@ async fn app() -> std::result::Result<(), AppError> {
 
     info!("listening on {}", listener.local_addr().unwrap());
 
-    axum::serve(listener, app).await.expect("server error");
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        info!("Shutting down, closing open connections...");
+        let _ = shutdown.send(());
+    })
+    .await
+    .expect("server error");
 
     Ok(())
 }
+
+/// Resolve once the process receives SIGINT (Ctrl+C) or SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
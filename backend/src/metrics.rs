@@ -0,0 +1,121 @@
+use common::AttackResult;
+use prometheus::{Encoder, IntCounter, Registry, TextEncoder};
+
+/// Prometheus counters exported on `/metrics`.
+///
+/// Cloning is cheap and every clone updates the same counters.
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    registry: Registry,
+    pub attacks_total: IntCounter,
+    pub attacks_won_attacker: IntCounter,
+    pub attacks_won_defender: IntCounter,
+    pub games_created_total: IntCounter,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let counter = |name: &str, help: &str| {
+            let counter = IntCounter::new(name, help).expect("valid counter");
+            registry
+                .register(Box::new(counter.clone()))
+                .expect("counter registered once");
+            counter
+        };
+
+        Self {
+            attacks_total: counter("attacks_total", "Attacks resolved"),
+            attacks_won_attacker: counter(
+                "attacks_won_attacker",
+                "Attacks where the attacker captured the area",
+            ),
+            attacks_won_defender: counter(
+                "attacks_won_defender",
+                "Attacks where the defender held the area",
+            ),
+            games_created_total: counter("games_created_total", "Games created"),
+            registry,
+        }
+    }
+
+    pub fn record_attack(&self, result: &AttackResult) {
+        self.attacks_total.inc();
+        if result.captured {
+            self.attacks_won_attacker.inc();
+        } else {
+            self.attacks_won_defender.inc();
+        }
+    }
+
+    /// All metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding does not fail");
+        String::from_utf8(buffer).expect("text encoding is UTF-8")
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attack(captured: bool) -> AttackResult {
+        AttackResult {
+            attack_roll: 10,
+            defence_roll: 10,
+            captured,
+            attacker_remaining_dice: 1,
+            defender_remaining_dice: 1,
+        }
+    }
+
+    // ==== record_attack ====
+
+    #[test]
+    fn record_attack_counts_every_attack() {
+        let metrics = Metrics::new();
+
+        for i in 0..100 {
+            metrics.record_attack(&attack(i % 3 == 0));
+        }
+
+        assert_eq!(metrics.attacks_total.get(), 100);
+        assert_eq!(metrics.attacks_won_attacker.get(), 34);
+        assert_eq!(metrics.attacks_won_defender.get(), 66);
+        assert_eq!(
+            metrics.attacks_won_attacker.get() + metrics.attacks_won_defender.get(),
+            metrics.attacks_total.get()
+        );
+    }
+
+    #[test]
+    fn clones_share_counters() {
+        let metrics = Metrics::new();
+        metrics.clone().record_attack(&attack(true));
+        assert_eq!(metrics.attacks_total.get(), 1);
+    }
+
+    // ==== render ====
+
+    #[test]
+    fn render_includes_all_counters() {
+        let metrics = Metrics::new();
+        metrics.games_created_total.inc();
+
+        let text = metrics.render();
+
+        assert!(text.contains("attacks_total 0"));
+        assert!(text.contains("attacks_won_attacker 0"));
+        assert!(text.contains("attacks_won_defender 0"));
+        assert!(text.contains("games_created_total 1"));
+    }
+}
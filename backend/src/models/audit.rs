@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Audit record of who created a game, from where and when.
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GameCreation {
+    pub game_id: Uuid,
+    pub creator_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub world_name: String,
+    pub ip_address: Option<String>,
+}
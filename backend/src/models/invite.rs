@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum InviteError {
+    #[error("Invite code not found.")]
+    NotFound,
+
+    #[error("Invite code has already been used.")]
+    AlreadyUsed,
+
+    #[error("The game has already started.")]
+    GameStarted,
+}
+
+/// A single-use code that lets a user join a specific game.
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Invite {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub code: String,
+    pub created_by: Uuid,
+    pub used_by: Option<Uuid>,
+}
+
+impl Invite {
+    /// Length of generated invite codes.
+    pub const CODE_LENGTH: usize = 8;
+
+    pub fn new(game_id: Uuid, created_by: Uuid) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            game_id,
+            code: Self::generate_code(),
+            created_by,
+            used_by: None,
+        }
+    }
+
+    /// Normalise user input so codes can be typed in any case.
+    pub fn normalize_code(code: &str) -> String {
+        code.trim().to_ascii_uppercase()
+    }
+
+    pub fn is_used(&self) -> bool {
+        self.used_by.is_some()
+    }
+
+    fn generate_code() -> String {
+        Uuid::new_v4().simple().to_string()[..Self::CODE_LENGTH].to_ascii_uppercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_invite_has_unused_alphanumeric_code() {
+        let invite = Invite::new(Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(invite.code.len(), Invite::CODE_LENGTH);
+        assert!(invite.code.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert!(!invite.is_used());
+    }
+
+    #[test]
+    fn new_invites_have_different_codes() {
+        let a = Invite::new(Uuid::new_v4(), Uuid::new_v4());
+        let b = Invite::new(Uuid::new_v4(), Uuid::new_v4());
+        assert_ne!(a.code, b.code);
+    }
+
+    #[test]
+    fn normalize_code_trims_and_uppercases() {
+        assert_eq!(Invite::normalize_code("  ab12cd34 "), "AB12CD34");
+    }
+}
@@ -1,3 +1,7 @@
+mod audit;
+mod invite;
 mod user;
 
+pub use audit::*;
+pub use invite::*;
 pub use user::*;
@@ -1,3 +1,4 @@
+use crate::rating::DEFAULT_RATING;
 use argon2::{
     Argon2, PasswordHash,
     password_hash::{PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
@@ -6,6 +7,7 @@ use email_address::EmailAddress;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use thiserror::Error;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -21,6 +23,12 @@ pub enum UserError {
     #[error("Username must be at least 3 characters.")]
     UsernameTooShort,
 
+    #[error("Username may only contain letters, digits, underscores and hyphens.")]
+    InvalidUsernameCharacters,
+
+    #[error("Display name must be at most 50 characters.")]
+    DisplayNameTooLong,
+
     #[error("Username already exists.")]
     UsernameExists,
 
@@ -38,28 +46,40 @@ pub enum UserError {
 
     #[error("Email is already verified.")]
     EmailAlreadyVerified,
+
+    #[error("Password has been used recently, please choose a different one.")]
+    PasswordPreviouslyUsed,
 }
 
 pub type Result<T> = std::result::Result<T, UserError>;
 
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct User {
     pub id: Uuid,
 
     pub username: String,
 
+    #[serde(default)]
+    pub display_name: Option<String>,
+
     pub email: String,
 
     #[serde(skip_serializing)]
+    #[schema(ignore)]
     pub password_hash: String,
 
     #[serde(default)]
     pub email_verified: bool,
 
     pub admin: bool,
+
+    pub rating: f64,
 }
 
 impl User {
+    /// Maximum length of a display name, in characters.
+    pub const DISPLAY_NAME_MAX_LENGTH: usize = 50;
+
     pub fn new(
         username: impl Into<String>,
         email: impl Into<String>,
@@ -80,19 +100,49 @@ impl User {
         Ok(Self {
             id: Uuid::new_v4(),
             username,
+            display_name: None,
             email,
             password_hash,
             email_verified: false,
             admin,
+            rating: DEFAULT_RATING,
         })
     }
 
+    /// The name shown to other players: the display name if set, otherwise
+    /// the username.
+    pub fn public_name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.username)
+    }
+
+    /// Trim a requested display name and check its length.
+    /// Blank names clear the display name.
+    pub fn normalize_display_name(display_name: Option<&str>) -> Result<Option<String>> {
+        let Some(display_name) = display_name.map(str::trim).filter(|name| !name.is_empty()) else {
+            return Ok(None);
+        };
+
+        if display_name.chars().count() > Self::DISPLAY_NAME_MAX_LENGTH {
+            return Err(UserError::DisplayNameTooLong);
+        }
+
+        Ok(Some(display_name.to_string()))
+    }
+
     pub fn verify_password(&self, password: &str) -> Result<()> {
         let parsed_hash = PasswordHash::new(&self.password_hash)?;
         Argon2::default().verify_password(password.as_bytes(), &parsed_hash)?;
         Ok(())
     }
 
+    /// Check whether `password` matches a stored password hash.
+    /// Unparseable hashes never match.
+    pub fn password_matches_hash(password: &str, password_hash: &str) -> bool {
+        PasswordHash::new(password_hash)
+            .and_then(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed))
+            .is_ok()
+    }
+
     /// Hash a password without creating a full User.
     /// Useful for password updates where we only need the hash.
     pub fn hash_password(password: &str) -> Result<String> {
@@ -104,10 +154,16 @@ impl User {
         Ok(password_hash)
     }
 
-    fn validate_username(username: &str) -> Result<()> {
+    pub fn validate_username(username: &str) -> Result<()> {
         if username.len() < 3 {
             return Err(UserError::UsernameTooShort);
         }
+        if !username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(UserError::InvalidUsernameCharacters);
+        }
         Ok(())
     }
 
@@ -232,6 +288,24 @@ mod tests {
         assert!(user.verify_password("").is_err());
     }
 
+    #[test]
+    fn password_matches_hash_detects_same_password() {
+        let user = User::new("testuser", "test@example.com", "Abcdefgh1!", false).unwrap();
+        assert!(User::password_matches_hash(
+            "Abcdefgh1!",
+            &user.password_hash
+        ));
+        assert!(!User::password_matches_hash(
+            "Other1234!",
+            &user.password_hash
+        ));
+    }
+
+    #[test]
+    fn password_matches_hash_rejects_invalid_hash() {
+        assert!(!User::password_matches_hash("Abcdefgh1!", "hash"));
+    }
+
     #[test]
     fn verify_password_is_case_sensitive() {
         let user = User::new("testuser", "test@example.com", "Abcdefgh1!", false).unwrap();
@@ -272,6 +346,72 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn validate_accepts_underscores_hyphens_and_digits() {
+        assert!(User::validate_username("alice").is_ok());
+        assert!(User::validate_username("al_ice-99").is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_whitespace_in_username() {
+        let result = User::validate_username("al ice");
+        assert!(matches!(result, Err(UserError::InvalidUsernameCharacters)));
+        let result = User::validate_username("al\nice");
+        assert!(matches!(result, Err(UserError::InvalidUsernameCharacters)));
+    }
+
+    #[test]
+    fn validate_rejects_symbols_in_username() {
+        let result = User::validate_username("alice!");
+        assert!(matches!(result, Err(UserError::InvalidUsernameCharacters)));
+        let result = User::validate_username("al<ice>");
+        assert!(matches!(result, Err(UserError::InvalidUsernameCharacters)));
+    }
+
+    #[test]
+    fn validate_rejects_non_ascii_username() {
+        let result = User::validate_username("ålice");
+        assert!(matches!(result, Err(UserError::InvalidUsernameCharacters)));
+    }
+
+    // ========================================================================
+    // Display Name Tests
+    // ========================================================================
+
+    #[test]
+    fn public_name_falls_back_to_username() {
+        let mut user = User::new("testuser", "test@example.com", "Abcdefgh1!", false).unwrap();
+        assert_eq!(user.public_name(), "testuser");
+
+        user.display_name = Some("Tärning Mästare".to_string());
+        assert_eq!(user.public_name(), "Tärning Mästare");
+    }
+
+    #[test]
+    fn normalize_display_name_trims_and_clears_blank() {
+        assert_eq!(
+            User::normalize_display_name(Some("  Dice Lord  ")).unwrap(),
+            Some("Dice Lord".to_string())
+        );
+        assert_eq!(User::normalize_display_name(Some("   ")).unwrap(), None);
+        assert_eq!(User::normalize_display_name(None).unwrap(), None);
+    }
+
+    #[test]
+    fn normalize_display_name_counts_characters_not_bytes() {
+        let name = "ä".repeat(User::DISPLAY_NAME_MAX_LENGTH);
+        assert!(User::normalize_display_name(Some(&name)).is_ok());
+    }
+
+    #[test]
+    fn normalize_display_name_rejects_too_long() {
+        let name = "a".repeat(User::DISPLAY_NAME_MAX_LENGTH + 1);
+        assert!(matches!(
+            User::normalize_display_name(Some(&name)),
+            Err(UserError::DisplayNameTooLong)
+        ));
+    }
+
     // ========================================================================
     // Email Validation Tests
     // ========================================================================
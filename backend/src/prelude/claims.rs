@@ -1,6 +1,6 @@
 use crate::prelude::*;
 use axum::{
-    extract::FromRequestParts,
+    extract::{FromRequestParts, OptionalFromRequestParts},
     http::{StatusCode, header, request::Parts},
     response::IntoResponse,
 };
@@ -65,10 +65,15 @@ impl Claims {
     }
 
     pub fn decode(token: &str, secret: &str) -> ClaimsResult<Self> {
+        // Tokens are only ever validated by the server that issued them, so
+        // there is no clock skew to allow for.
+        let mut validation = Validation::default();
+        validation.leeway = 0;
+
         let token = jsonwebtoken::decode::<Claims>(
             token,
             &DecodingKey::from_secret(secret.as_ref()),
-            &Validation::default(),
+            &validation,
         )?;
 
         Ok(token.claims)
@@ -95,3 +100,90 @@ impl FromRequestParts<AppState> for Claims {
         }
     }
 }
+
+/// Lets handlers take `Option<Claims>` for endpoints that work both with and
+/// without a session. A missing token gives `None`, an invalid one is still
+/// rejected.
+impl OptionalFromRequestParts<AppState> for Claims {
+    type Rejection = ClaimsError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> ClaimsResult<Option<Self>> {
+        match <Claims as FromRequestParts<AppState>>::from_request_parts(parts, state).await {
+            Ok(claims) => Ok(Some(claims)),
+            Err(ClaimsError::TokenMissing) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "test-jwt-secret-that-is-long-enough";
+
+    fn claims(admin: bool) -> Claims {
+        Claims::new(Uuid::new_v4(), admin, "alice", Duration::minutes(15))
+    }
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        let original = claims(true);
+        let token = original.encode(SECRET).unwrap();
+
+        let decoded = Claims::decode(&token, SECRET).unwrap();
+        assert_eq!(decoded.sub, original.sub);
+        assert!(decoded.admin);
+        assert_eq!(decoded.username, "alice");
+        assert_eq!(decoded.exp, original.exp);
+        assert_eq!(decoded.iat, original.iat);
+    }
+
+    #[test]
+    fn non_admin_claim_survives_roundtrip() {
+        let token = claims(false).encode(SECRET).unwrap();
+        assert!(!Claims::decode(&token, SECRET).unwrap().admin);
+    }
+
+    #[test]
+    fn decode_with_wrong_secret_is_invalid() {
+        let token = claims(false).encode(SECRET).unwrap();
+        let err = Claims::decode(&token, "another-secret-that-is-long-enough").unwrap_err();
+        assert!(matches!(err, ClaimsError::TokenInvalid(_)));
+    }
+
+    #[test]
+    fn decode_expired_token_fails() {
+        let expired = Claims::new(Uuid::new_v4(), false, "alice", Duration::seconds(-60));
+        let token = expired.encode(SECRET).unwrap();
+        assert!(matches!(
+            Claims::decode(&token, SECRET),
+            Err(ClaimsError::TokenInvalid(_))
+        ));
+    }
+
+    #[test]
+    fn decode_tampered_token_fails() {
+        let token = claims(false).encode(SECRET).unwrap();
+
+        // Flip a character of the payload, keeping the token well-formed
+        let payload_start = token.find('.').unwrap() + 1;
+        let mut bytes = token.into_bytes();
+        bytes[payload_start] = if bytes[payload_start] == b'A' {
+            b'B'
+        } else {
+            b'A'
+        };
+        let tampered = String::from_utf8(bytes).unwrap();
+
+        assert!(Claims::decode(&tampered, SECRET).is_err());
+    }
+
+    #[test]
+    fn decode_garbage_fails() {
+        assert!(matches!(
+            Claims::decode("not-a-token", SECRET),
+            Err(ClaimsError::TokenInvalid(_))
+        ));
+    }
+}
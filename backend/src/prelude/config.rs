@@ -1,3 +1,4 @@
+use axum::http::HeaderValue;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,10 +9,36 @@ pub enum ConfigError {
     InvalidEnvVar(String),
 }
 
+/// Access token lifetime used when `JWT_EXPIRY_SECONDS` is not set.
+const DEFAULT_JWT_EXPIRY_SECONDS: i64 = 15 * 60;
+
+/// Per-game event channel capacity used when `GAME_BROADCAST_CHANNEL_SIZE`
+/// is not set.
+pub const DEFAULT_GAME_BROADCAST_CHANNEL_SIZE: usize = 64;
+
+/// Time between WebSocket heartbeat pings used when
+/// `WS_HEARTBEAT_INTERVAL_SECONDS` is not set.
+const DEFAULT_WS_HEARTBEAT_INTERVAL_SECONDS: u64 = 30;
+
+/// Content-Security-Policy used when `CSP_POLICY` is not set. Inline styles
+/// are allowed for the Swagger UI.
+pub const DEFAULT_CSP_POLICY: &str =
+    "default-src 'self'; img-src 'self' data:; style-src 'self' 'unsafe-inline'";
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub port: u16,
     pub jwt_secret: String,
+    pub jwt_expiry_seconds: i64,
+    /// Number of events buffered per game before slow subscribers lag.
+    pub game_broadcast_channel_size: usize,
+    /// Seconds between pings sent to game WebSocket clients.
+    pub ws_heartbeat_interval_seconds: u64,
+    /// Value of the `Content-Security-Policy` header sent with responses.
+    pub csp_policy: String,
+    /// Whether `?debug=1` may add internal details to error responses.
+    /// Never enable this in production.
+    pub debug_mode: bool,
     pub database_url: String,
     pub mailjet_api_key: String,
     pub mailjet_api_secret: String,
@@ -30,6 +57,51 @@ impl Config {
         let jwt_secret = std::env::var("JWT_SECRET")
             .map_err(|_| ConfigError::MissingEnvVar("JWT_SECRET".to_string()))?;
 
+        let jwt_expiry_seconds = match std::env::var("JWT_EXPIRY_SECONDS") {
+            Ok(value) => value
+                .parse::<i64>()
+                .ok()
+                .filter(|seconds| *seconds > 0)
+                .ok_or_else(|| ConfigError::InvalidEnvVar("JWT_EXPIRY_SECONDS".to_string()))?,
+            Err(_) => DEFAULT_JWT_EXPIRY_SECONDS,
+        };
+
+        let game_broadcast_channel_size = match std::env::var("GAME_BROADCAST_CHANNEL_SIZE") {
+            Ok(value) => value
+                .parse::<usize>()
+                .ok()
+                .filter(|size| *size > 0)
+                .ok_or_else(|| {
+                    ConfigError::InvalidEnvVar("GAME_BROADCAST_CHANNEL_SIZE".to_string())
+                })?,
+            Err(_) => DEFAULT_GAME_BROADCAST_CHANNEL_SIZE,
+        };
+
+        let ws_heartbeat_interval_seconds = match std::env::var("WS_HEARTBEAT_INTERVAL_SECONDS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .ok()
+                .filter(|seconds| *seconds > 0)
+                .ok_or_else(|| {
+                    ConfigError::InvalidEnvVar("WS_HEARTBEAT_INTERVAL_SECONDS".to_string())
+                })?,
+            Err(_) => DEFAULT_WS_HEARTBEAT_INTERVAL_SECONDS,
+        };
+
+        let csp_policy = match std::env::var("CSP_POLICY") {
+            Ok(value) => Some(value)
+                .filter(|policy| HeaderValue::from_str(policy).is_ok())
+                .ok_or_else(|| ConfigError::InvalidEnvVar("CSP_POLICY".to_string()))?,
+            Err(_) => DEFAULT_CSP_POLICY.to_string(),
+        };
+
+        let debug_mode = match std::env::var("DEBUG_MODE") {
+            Ok(value) => value
+                .parse::<bool>()
+                .map_err(|_| ConfigError::InvalidEnvVar("DEBUG_MODE".to_string()))?,
+            Err(_) => false,
+        };
+
         let database_url = std::env::var("DATABASE_URL")
             .map_err(|_| ConfigError::MissingEnvVar("DATABASE_URL".to_string()))?;
 
@@ -51,6 +123,11 @@ impl Config {
         Ok(Config {
             port,
             jwt_secret,
+            jwt_expiry_seconds,
+            game_broadcast_channel_size,
+            ws_heartbeat_interval_seconds,
+            csp_policy,
+            debug_mode,
             database_url,
             mailjet_api_key,
             mailjet_api_secret,
@@ -60,3 +137,100 @@ impl Config {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    const REQUIRED: &[(&str, &str)] = &[
+        ("PORT", "3000"),
+        ("JWT_SECRET", "secret"),
+        ("DATABASE_URL", "postgres://localhost/rsdice"),
+        ("MAILJET_API_KEY", "key"),
+        ("MAILJET_API_SECRET", "mailjet-secret"),
+        ("URL", "http://localhost:3000"),
+        ("MAIL_FROM_EMAIL", "noreply@example.com"),
+        ("MAIL_FROM_NAME", "rsdice"),
+    ];
+
+    const OPTIONAL: &[&str] = &[
+        "JWT_EXPIRY_SECONDS",
+        "GAME_BROADCAST_CHANNEL_SIZE",
+        "WS_HEARTBEAT_INTERVAL_SECONDS",
+        "CSP_POLICY",
+        "DEBUG_MODE",
+    ];
+
+    /// Sets every required variable and clears the optional ones.
+    ///
+    /// Only call this from `#[serial]` tests, no other thread may touch the
+    /// environment at the same time.
+    fn set_env() {
+        // SAFETY: tests touching the environment are serialized.
+        unsafe {
+            for (name, value) in REQUIRED {
+                std::env::set_var(name, value);
+            }
+            for name in OPTIONAL {
+                std::env::remove_var(name);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn from_env_with_all_vars() {
+        set_env();
+
+        let config = Config::from_env().unwrap();
+
+        assert_eq!(config.port, 3000);
+        assert_eq!(config.jwt_secret, "secret");
+        assert_eq!(config.database_url, "postgres://localhost/rsdice");
+        assert_eq!(config.mailjet_api_key, "key");
+        assert_eq!(config.mailjet_api_secret, "mailjet-secret");
+        assert_eq!(config.url, "http://localhost:3000");
+        assert_eq!(config.mail_from_email, "noreply@example.com");
+        assert_eq!(config.mail_from_name, "rsdice");
+        assert_eq!(config.jwt_expiry_seconds, DEFAULT_JWT_EXPIRY_SECONDS);
+        assert_eq!(
+            config.game_broadcast_channel_size,
+            DEFAULT_GAME_BROADCAST_CHANNEL_SIZE
+        );
+        assert_eq!(
+            config.ws_heartbeat_interval_seconds,
+            DEFAULT_WS_HEARTBEAT_INTERVAL_SECONDS
+        );
+        assert_eq!(config.csp_policy, DEFAULT_CSP_POLICY);
+        assert!(!config.debug_mode);
+    }
+
+    #[test]
+    #[serial]
+    fn from_env_missing_var() {
+        for (name, _) in REQUIRED {
+            set_env();
+            // SAFETY: tests touching the environment are serialized.
+            unsafe { std::env::remove_var(name) };
+
+            match Config::from_env() {
+                Err(ConfigError::MissingEnvVar(missing)) => assert_eq!(missing, *name),
+                other => panic!("expected {name} to be missing, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn from_env_invalid_port() {
+        set_env();
+        // SAFETY: tests touching the environment are serialized.
+        unsafe { std::env::set_var("PORT", "not_a_number") };
+
+        match Config::from_env() {
+            Err(ConfigError::InvalidEnvVar(name)) => assert_eq!(name, "PORT"),
+            other => panic!("expected PORT to be invalid, got {other:?}"),
+        }
+    }
+}
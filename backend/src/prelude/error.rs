@@ -1,7 +1,11 @@
 use super::ClaimsError;
-use crate::{email::EmailError, models::UserError};
+use crate::{
+    email::EmailError,
+    models::{InviteError, UserError},
+};
 use axum::{http::StatusCode, response::IntoResponse};
 use thiserror::Error;
+use tracing::error;
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -17,23 +21,58 @@ pub enum Error {
     #[error("Not found")]
     NotFound,
 
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Email error: {0}")]
     Email(#[from] EmailError),
 
     #[error("Game error: {0}")]
     GameError(#[from] common::GameError),
+
+    #[error("Invite error: {0}")]
+    Invite(#[from] InviteError),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Details of an internal error, attached to the response's extensions.
+/// Only sent to clients in debug mode.
+#[derive(Debug, Clone)]
+pub struct InternalMessage(pub String);
+
 impl IntoResponse for Error {
     fn into_response(self) -> axum::response::Response {
         match self {
             Error::Claims(e) => e.into_response(),
             Error::User(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
             Error::NotFound => StatusCode::NOT_FOUND.into_response(),
+            Error::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+            Error::Conflict(message) => (StatusCode::CONFLICT, message).into_response(),
+            Error::Forbidden(message) => (StatusCode::FORBIDDEN, message).into_response(),
             Error::GameError(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
-            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response(),
+            Error::Invite(e) => {
+                let status = match e {
+                    InviteError::NotFound => StatusCode::NOT_FOUND,
+                    InviteError::AlreadyUsed => StatusCode::GONE,
+                    InviteError::GameStarted => StatusCode::CONFLICT,
+                };
+                (status, e.to_string()).into_response()
+            }
+            _ => {
+                let message = self.to_string();
+                error!("Internal error: {message}");
+                let mut response =
+                    (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response();
+                response.extensions_mut().insert(InternalMessage(message));
+                response
+            }
         }
     }
 }
@@ -1,9 +1,11 @@
 mod claims;
 mod config;
 mod error;
+mod page;
 mod state;
 
 pub use claims::*;
 pub use config::*;
 pub use error::*;
+pub use page::*;
 pub use state::*;
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Page size used when a request does not ask for one.
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+/// Largest page size a request may ask for.
+pub const MAX_PAGE_LIMIT: usize = 100;
+
+/// `?offset=&limit=` query parameters accepted by list endpoints.
+#[derive(Debug, Clone, Copy, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Pagination {
+    /// Number of items to skip.
+    offset: Option<usize>,
+    /// Most items to return, at most 100.
+    limit: Option<usize>,
+}
+
+impl Pagination {
+    pub fn new(offset: usize, limit: usize) -> Self {
+        Self {
+            offset: Some(offset),
+            limit: Some(limit),
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset.unwrap_or(0)
+    }
+
+    /// Requested page size, clamped to `1..=MAX_PAGE_LIMIT`.
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, MAX_PAGE_LIMIT)
+    }
+}
+
+/// One page of a list endpoint's results, along with the total number of
+/// items available.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl<T> Page<T> {
+    /// Wrap a page that has already been fetched, e.g. with SQL `LIMIT`.
+    pub fn new(items: Vec<T>, total: usize, pagination: Pagination) -> Self {
+        Self {
+            items,
+            total,
+            offset: pagination.offset(),
+            limit: pagination.limit(),
+        }
+    }
+
+    /// Cut the page described by `pagination` out of the complete list.
+    pub fn from_vec(all: Vec<T>, pagination: Pagination) -> Self {
+        let total = all.len();
+        let items = all
+            .into_iter()
+            .skip(pagination.offset())
+            .take(pagination.limit())
+            .collect();
+        Self::new(items, total, pagination)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_page_of_short_list_holds_everything() {
+        let page = Page::from_vec(vec![1, 2, 3], Pagination::new(0, 5));
+        assert_eq!(page.items, vec![1, 2, 3]);
+        assert_eq!(page.total, 3);
+        assert_eq!((page.offset, page.limit), (0, 5));
+    }
+
+    #[test]
+    fn offset_past_end_is_empty() {
+        let page = Page::from_vec(vec![1, 2, 3], Pagination::new(5, 5));
+        assert!(page.items.is_empty());
+        assert_eq!(page.total, 3);
+    }
+
+    #[test]
+    fn limit_is_clamped() {
+        assert_eq!(Pagination::new(0, 0).limit(), 1);
+        assert_eq!(Pagination::new(0, 1000).limit(), MAX_PAGE_LIMIT);
+        assert_eq!(Pagination::default().limit(), DEFAULT_PAGE_LIMIT);
+    }
+}
@@ -1,8 +1,9 @@
 use crate::email::{EmailClient, MailjetClient};
-use crate::games::Games;
+use crate::games::{Games, WorldStore};
 use crate::prelude::*;
 use sqlx::PgPool;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 
 #[derive(Debug, Clone)]
 pub struct AppState {
@@ -10,15 +11,21 @@ pub struct AppState {
     pub db: PgPool,
     pub email: Arc<dyn EmailClient>,
     pub games: Games,
+    pub worlds: WorldStore,
+    /// Fires once when the server starts shutting down, so long-lived
+    /// connections can close cleanly.
+    pub shutdown: broadcast::Sender<()>,
 }
 
 impl AppState {
-    pub fn new(config: Config, db: PgPool) -> Self {
+    pub fn new(config: Config, db: PgPool, games: Games, worlds: WorldStore) -> Self {
         Self {
             email: Arc::new(MailjetClient::new(&config)),
             config: Arc::new(config),
             db,
-            games: Games::default(),
+            games,
+            worlds,
+            shutdown: broadcast::channel(1).0,
         }
     }
 
@@ -28,10 +35,12 @@ impl AppState {
     /// replaces the real mail provider.
     pub fn with_email(config: Config, db: PgPool, email: Arc<dyn EmailClient>) -> Self {
         Self {
+            games: Games::new(db.clone(), config.game_broadcast_channel_size),
             config: Arc::new(config),
             db,
             email,
-            games: Games::default(),
+            worlds: WorldStore::new().expect("embedded worlds are valid"),
+            shutdown: broadcast::channel(1).0,
         }
     }
 }
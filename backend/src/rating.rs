@@ -0,0 +1,137 @@
+use uuid::Uuid;
+
+/// Rating assigned to new users.
+pub const DEFAULT_RATING: f64 = 1000.0;
+
+/// Maximum rating change for a single game.
+pub const K_FACTOR: f64 = 32.0;
+
+/// A user's standing at the end of a game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameParticipant {
+    pub user_id: Uuid,
+    /// Rating before the game.
+    pub rating: f64,
+    /// Finishing position, where 1 is the winner. Equal placements count as
+    /// a draw between those participants.
+    pub placement: usize,
+}
+
+/// Compute new ratings for all participants of a finished game.
+///
+/// The game is scored as a set of pairwise matches between every pair of
+/// participants, and the K-factor is divided by the number of opponents so
+/// a single game moves a rating by at most [`K_FACTOR`] points.
+///
+/// Returns `(user_id, new_rating)` pairs in the same order as `players`.
+/// Games with fewer than two participants leave ratings unchanged.
+pub fn update_ratings(players: &[GameParticipant]) -> Vec<(Uuid, f64)> {
+    if players.len() < 2 {
+        return players.iter().map(|p| (p.user_id, p.rating)).collect();
+    }
+
+    let k = K_FACTOR / (players.len() - 1) as f64;
+
+    players
+        .iter()
+        .map(|player| {
+            let delta: f64 = players
+                .iter()
+                .filter(|other| other.user_id != player.user_id)
+                .map(|other| {
+                    let score = match player.placement.cmp(&other.placement) {
+                        std::cmp::Ordering::Less => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Greater => 0.0,
+                    };
+                    score - expected_score(player.rating, other.rating)
+                })
+                .sum();
+
+            (player.user_id, player.rating + k * delta)
+        })
+        .collect()
+}
+
+/// Probability that a player rated `rating` beats one rated `opponent`.
+fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(rating: f64, placement: usize) -> GameParticipant {
+        GameParticipant {
+            user_id: Uuid::new_v4(),
+            rating,
+            placement,
+        }
+    }
+
+    #[test]
+    fn winner_gains_and_loser_loses() {
+        let winner = participant(DEFAULT_RATING, 1);
+        let loser = participant(DEFAULT_RATING, 2);
+
+        let updated = update_ratings(&[winner, loser]);
+
+        assert_eq!(updated[0].0, winner.user_id);
+        assert!((updated[0].1 - (DEFAULT_RATING + K_FACTOR / 2.0)).abs() < 1e-9);
+        assert_eq!(updated[1].0, loser.user_id);
+        assert!((updated[1].1 - (DEFAULT_RATING - K_FACTOR / 2.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn upset_win_gains_more_than_expected_win() {
+        let underdog = update_ratings(&[participant(900.0, 1), participant(1100.0, 2)]);
+        let favourite = update_ratings(&[participant(1100.0, 1), participant(900.0, 2)]);
+
+        let underdog_gain = underdog[0].1 - 900.0;
+        let favourite_gain = favourite[0].1 - 1100.0;
+        assert!(underdog_gain > favourite_gain);
+    }
+
+    #[test]
+    fn multi_player_losers_share_loss_equally() {
+        let players = [
+            participant(DEFAULT_RATING, 1),
+            participant(DEFAULT_RATING, 2),
+            participant(DEFAULT_RATING, 2),
+            participant(DEFAULT_RATING, 2),
+        ];
+
+        let updated = update_ratings(&players);
+
+        assert!(updated[0].1 > DEFAULT_RATING);
+        for (_, rating) in &updated[1..] {
+            assert!(*rating < DEFAULT_RATING);
+            assert!((rating - updated[1].1).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn rating_changes_sum_to_zero() {
+        let players = [
+            participant(1200.0, 1),
+            participant(1000.0, 2),
+            participant(950.0, 3),
+        ];
+
+        let updated = update_ratings(&players);
+        let total_change: f64 = players
+            .iter()
+            .zip(&updated)
+            .map(|(before, (_, after))| after - before.rating)
+            .sum();
+
+        assert!(total_change.abs() < 1e-9);
+    }
+
+    #[test]
+    fn single_participant_is_unchanged() {
+        let solo = participant(1234.0, 1);
+        assert_eq!(update_ratings(&[solo]), vec![(solo.user_id, 1234.0)]);
+    }
+}
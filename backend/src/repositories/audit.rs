@@ -0,0 +1,71 @@
+use crate::models::GameCreation;
+use crate::prelude::*;
+use sqlx::PgPool;
+use std::net::IpAddr;
+use uuid::Uuid;
+
+pub struct AuditRepository<'a> {
+    db: &'a PgPool,
+}
+
+impl<'a> AuditRepository<'a> {
+    pub fn new(db: &'a PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Record that `creator_id` created `game_id` from the given world.
+    pub async fn record_game_creation(
+        &self,
+        game_id: Uuid,
+        creator_id: Uuid,
+        world_name: &str,
+        ip_address: Option<IpAddr>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO game_creations (game_id, creator_id, world_name, ip_address)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            game_id,
+            creator_id,
+            world_name,
+            ip_address.map(|ip| ip.to_string()),
+        )
+        .execute(self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List game creation records, newest first.
+    pub async fn list_game_creations(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<GameCreation>> {
+        let records = sqlx::query_as!(
+            GameCreation,
+            r#"
+            SELECT game_id, creator_id, created_at, world_name, ip_address
+            FROM game_creations
+            ORDER BY created_at DESC, game_id
+            LIMIT $1 OFFSET $2
+            "#,
+            limit as i64,
+            offset as i64,
+        )
+        .fetch_all(self.db)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Total number of game creation records.
+    pub async fn count_game_creations(&self) -> Result<usize> {
+        let count = sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!" FROM game_creations"#)
+            .fetch_one(self.db)
+            .await?;
+
+        Ok(count as usize)
+    }
+}
@@ -0,0 +1,182 @@
+use crate::games::Creator;
+use crate::prelude::*;
+use common::MoveRecord;
+use sqlx::{PgPool, types::Json};
+use uuid::Uuid;
+
+pub struct GameRepository<'a> {
+    db: &'a PgPool,
+}
+
+impl<'a> GameRepository<'a> {
+    pub fn new(db: &'a PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Store a newly created game together with its creator. The dice seed
+    /// is kept out of the snapshot, which clients may see.
+    pub async fn create(&self, creator: &Creator, snapshot: &common::Game) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO games (id, creator_id, creator_name, snapshot, dice_seed, dice_draws)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            snapshot.id,
+            creator.id,
+            creator.name,
+            Json(snapshot) as _,
+            snapshot.seed as i64,
+            snapshot.dice_draws() as i64,
+        )
+        .execute(self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replace the stored snapshot of an existing game, along with how far
+    /// its dice have been rolled.
+    pub async fn save_snapshot(&self, game_id: Uuid, snapshot: &common::Game) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE games
+            SET snapshot = $1, dice_draws = $2, updated_at = NOW()
+            WHERE id = $3
+            "#,
+            Json(snapshot) as _,
+            snapshot.dice_draws() as i64,
+            game_id,
+        )
+        .execute(self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Load the latest stored snapshot of a game.
+    pub async fn load_snapshot(&self, game_id: Uuid) -> Result<Option<common::Game>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT snapshot as "snapshot: Json<common::Game>", dice_seed, dice_draws
+            FROM games
+            WHERE id = $1
+            "#,
+            game_id,
+        )
+        .fetch_optional(self.db)
+        .await?;
+
+        Ok(row.map(|record| restored(record.snapshot.0, record.dice_seed, record.dice_draws)))
+    }
+
+    /// Load the creator and latest snapshot of every game that has not finished.
+    pub async fn find_unfinished(&self) -> Result<Vec<(Creator, common::Game)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT creator_id, creator_name, snapshot as "snapshot: Json<common::Game>",
+                dice_seed, dice_draws
+            FROM games
+            WHERE snapshot->'state' <> '"Finished"'::jsonb
+            ORDER BY created_at
+            "#,
+        )
+        .fetch_all(self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|record| {
+                let creator = Creator {
+                    id: record.creator_id,
+                    name: record.creator_name,
+                };
+                let snapshot = restored(record.snapshot.0, record.dice_seed, record.dice_draws);
+                (creator, snapshot)
+            })
+            .collect())
+    }
+
+    /// Append a move made during turn `turn_number` to a game's history.
+    pub async fn save_move(
+        &self,
+        game_id: Uuid,
+        turn_number: u32,
+        move_record: &MoveRecord,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO game_moves (id, game_id, turn_number, record)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            Uuid::new_v4(),
+            game_id,
+            i64::from(turn_number),
+            Json(move_record) as _,
+        )
+        .execute(self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// All moves of a game in the order they were played, by turn and then
+    /// by the time they were stored.
+    pub async fn moves_for_game(&self, game_id: Uuid) -> Result<Vec<MoveRecord>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT record as "record: Json<MoveRecord>"
+            FROM game_moves
+            WHERE game_id = $1
+            ORDER BY turn_number, created_at, id
+            "#,
+            game_id,
+        )
+        .fetch_all(self.db)
+        .await?;
+
+        Ok(rows.into_iter().map(|record| record.record.0).collect())
+    }
+
+    /// One page of a game's moves, in the order of [`Self::moves_for_game`].
+    pub async fn list_moves(
+        &self,
+        game_id: Uuid,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<MoveRecord>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT record as "record: Json<MoveRecord>"
+            FROM game_moves
+            WHERE game_id = $1
+            ORDER BY turn_number, created_at, id
+            LIMIT $2 OFFSET $3
+            "#,
+            game_id,
+            limit as i64,
+            offset as i64,
+        )
+        .fetch_all(self.db)
+        .await?;
+
+        Ok(rows.into_iter().map(|record| record.record.0).collect())
+    }
+
+    /// Total number of moves stored for a game.
+    pub async fn count_moves(&self, game_id: Uuid) -> Result<usize> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM game_moves WHERE game_id = $1"#,
+            game_id,
+        )
+        .fetch_one(self.db)
+        .await?;
+
+        Ok(count as usize)
+    }
+}
+
+/// A stored snapshot with its dice continuing where they left off.
+fn restored(mut snapshot: common::Game, dice_seed: i64, dice_draws: i64) -> common::Game {
+    snapshot.restore_dice(dice_seed as u64, dice_draws as u64);
+    snapshot
+}
@@ -0,0 +1,90 @@
+use crate::models::Invite;
+use crate::prelude::*;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct InviteRepository<'a> {
+    db: &'a PgPool,
+}
+
+impl<'a> InviteRepository<'a> {
+    pub fn new(db: &'a PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Create a new invite code for a game.
+    pub async fn create(&self, game_id: Uuid, created_by: Uuid) -> Result<Invite> {
+        let invite = Invite::new(game_id, created_by);
+
+        sqlx::query!(
+            r#"
+            INSERT INTO game_invites (id, game_id, code, created_by)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            invite.id,
+            invite.game_id,
+            invite.code,
+            invite.created_by,
+        )
+        .execute(self.db)
+        .await?;
+
+        Ok(invite)
+    }
+
+    /// Find an invite by its code.
+    pub async fn find_by_code(&self, code: &str) -> Result<Option<Invite>> {
+        let invite = sqlx::query_as!(
+            Invite,
+            r#"
+            SELECT id, game_id, code, created_by, used_by
+            FROM game_invites
+            WHERE code = $1
+            "#,
+            Invite::normalize_code(code),
+        )
+        .fetch_optional(self.db)
+        .await?;
+
+        Ok(invite)
+    }
+
+    /// Marks an invite as used by `user_id`.
+    ///
+    /// Returns `false` if the invite was already used.
+    pub async fn consume(&self, invite_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE game_invites
+            SET used_by = $1, used_at = NOW()
+            WHERE id = $2
+              AND used_at IS NULL
+            "#,
+            user_id,
+            invite_id,
+        )
+        .execute(self.db)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Makes an invite consumed by `user_id` usable again, e.g. when they
+    /// could not join the game after all.
+    pub async fn release(&self, invite_id: Uuid, user_id: Uuid) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE game_invites
+            SET used_by = NULL, used_at = NULL
+            WHERE id = $1
+              AND used_by = $2
+            "#,
+            invite_id,
+            user_id,
+        )
+        .execute(self.db)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,3 +1,9 @@
+mod audit;
+mod game;
+mod invite;
 mod user;
 
+pub use audit::*;
+pub use game::*;
+pub use invite::*;
 pub use user::*;
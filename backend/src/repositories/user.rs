@@ -2,9 +2,13 @@ use crate::models::{User, UserError};
 use crate::prelude::*;
 use chrono::Duration;
 use sha2::{Digest, Sha256};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
+/// Number of most recent passwords per user, the current one included,
+/// that may not be reused. All but the current one are kept in the history.
+const PASSWORD_HISTORY_SIZE: i64 = 5;
+
 pub struct UserRepository<'a> {
     db: &'a PgPool,
 }
@@ -29,6 +33,24 @@ impl<'a> UserRepository<'a> {
         format!("{digest:x}")
     }
 
+    /// Translate unique constraint violations on `users` into user errors.
+    fn map_unique_violation(e: sqlx::Error) -> Error {
+        if let sqlx::Error::Database(ref db_err) = e {
+            if matches!(
+                db_err.constraint(),
+                Some("users_username_key" | "users_username_lower_key")
+            ) {
+                return Error::User(UserError::UsernameExists);
+            }
+
+            if matches!(db_err.constraint(), Some("users_email_key")) {
+                return Error::User(UserError::EmailExists);
+            }
+        }
+
+        Error::Database(e)
+    }
+
     /// Create a new user in the database.
     pub async fn create(
         &self,
@@ -53,19 +75,7 @@ impl<'a> UserRepository<'a> {
         )
         .execute(self.db)
         .await
-        .map_err(|e| {
-            if let sqlx::Error::Database(ref db_err) = e {
-                if matches!(db_err.constraint(), Some("users_username_key")) {
-                    return Error::User(UserError::UsernameExists);
-                }
-
-                if matches!(db_err.constraint(), Some("users_email_key")) {
-                    return Error::User(UserError::EmailExists);
-                }
-            }
-
-            Error::Database(e)
-        })?;
+        .map_err(Self::map_unique_violation)?;
 
         Ok(user)
     }
@@ -101,8 +111,9 @@ impl<'a> UserRepository<'a> {
         Ok(token)
     }
 
-    /// Verifies an email token and marks the corresponding user as verified.
-    pub async fn verify_email_token(&self, token: &str) -> Result<bool> {
+    /// Consumes an email verification token, returning the id of the user
+    /// it was issued to, or `None` if the token is unknown, used or expired.
+    pub async fn verify_email_token(&self, token: &str) -> Result<Option<Uuid>> {
         let token_hash = Self::hash_verification_token(token);
 
         let token_row = sqlx::query!(
@@ -119,22 +130,24 @@ impl<'a> UserRepository<'a> {
         .fetch_optional(self.db)
         .await?;
 
-        let Some(row) = token_row else {
-            return Ok(false);
-        };
+        Ok(token_row.map(|row| row.user_id))
+    }
 
-        sqlx::query!(
+    /// Marks a user's email as verified. Returns `false` if it already was.
+    pub async fn mark_email_verified(&self, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query!(
             r#"
             UPDATE users
             SET email_verified = TRUE
             WHERE id = $1
+              AND NOT email_verified
             "#,
-            row.user_id,
+            user_id,
         )
         .execute(self.db)
         .await?;
 
-        Ok(true)
+        Ok(result.rows_affected() > 0)
     }
 
     /// Creates and stores a refresh token, returning the raw token.
@@ -230,19 +243,7 @@ impl<'a> UserRepository<'a> {
             return Ok(false);
         };
 
-        let password_hash = User::hash_password(password)?;
-
-        sqlx::query!(
-            r#"
-            UPDATE users
-            SET password_hash = $1
-            WHERE id = $2
-            "#,
-            password_hash,
-            row.user_id,
-        )
-        .execute(&mut *transaction)
-        .await?;
+        Self::replace_password(&mut transaction, row.user_id, password).await?;
 
         sqlx::query!(
             r#"
@@ -304,9 +305,10 @@ impl<'a> UserRepository<'a> {
         let user = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool"
+            SELECT id, username, display_name, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool", rating
             FROM users
             WHERE id = $1
+              AND deleted_at IS NULL
             "#,
             id,
         )
@@ -316,14 +318,17 @@ impl<'a> UserRepository<'a> {
         Ok(user)
     }
 
-    /// Find a user by their username.
+    /// Find a user by their username, ignoring case.
     pub async fn find_by_username(&self, username: &str) -> Result<Option<User>> {
+        let username = username.trim();
+
         let user = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool"
+            SELECT id, username, display_name, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool", rating
             FROM users
-            WHERE username = $1
+            WHERE LOWER(username) = LOWER($1)
+              AND deleted_at IS NULL
             "#,
             username,
         )
@@ -340,9 +345,10 @@ impl<'a> UserRepository<'a> {
         let user = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool"
+            SELECT id, username, display_name, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool", rating
             FROM users
             WHERE email = $1
+              AND deleted_at IS NULL
             "#,
             normalized_email,
         )
@@ -352,7 +358,7 @@ impl<'a> UserRepository<'a> {
         Ok(user)
     }
 
-    /// Find a user by username or email.
+    /// Find a user by username (ignoring case) or email.
     pub async fn find_by_username_or_email(&self, identifier: &str) -> Result<Option<User>> {
         let trimmed_identifier = identifier.trim();
         if trimmed_identifier.is_empty() {
@@ -364,9 +370,10 @@ impl<'a> UserRepository<'a> {
         let user = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool"
+            SELECT id, username, display_name, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool", rating
             FROM users
-            WHERE username = $1 OR email = $2
+            WHERE (LOWER(username) = LOWER($1) OR email = $2)
+              AND deleted_at IS NULL
             "#,
             trimmed_identifier,
             normalized_email,
@@ -377,12 +384,77 @@ impl<'a> UserRepository<'a> {
         Ok(user)
     }
 
+    /// Find a user by their ID, including soft-deleted users (admin use).
+    pub async fn find_by_id_including_deleted(&self, id: Uuid) -> Result<Option<User>> {
+        let user = sqlx::query_as!(
+            User,
+            r#"
+            SELECT id, username, display_name, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool", rating
+            FROM users
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_optional(self.db)
+        .await?;
+
+        Ok(user)
+    }
+
     /// Get all users from the database.
     pub async fn find_all(&self) -> Result<Vec<User>> {
         let users = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool"
+            SELECT id, username, display_name, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool", rating
+            FROM users
+            WHERE deleted_at IS NULL
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(self.db)
+        .await?;
+
+        Ok(users)
+    }
+
+    /// One page of users, in the order of [`Self::find_all`].
+    pub async fn list(&self, offset: usize, limit: usize) -> Result<Vec<User>> {
+        let users = sqlx::query_as!(
+            User,
+            r#"
+            SELECT id, username, display_name, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool", rating
+            FROM users
+            WHERE deleted_at IS NULL
+            ORDER BY id
+            LIMIT $1 OFFSET $2
+            "#,
+            limit as i64,
+            offset as i64,
+        )
+        .fetch_all(self.db)
+        .await?;
+
+        Ok(users)
+    }
+
+    /// Total number of users that have not been deleted.
+    pub async fn count(&self) -> Result<usize> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM users WHERE deleted_at IS NULL"#
+        )
+        .fetch_one(self.db)
+        .await?;
+
+        Ok(count as usize)
+    }
+
+    /// Get all users from the database, including soft-deleted users (admin use).
+    pub async fn find_all_including_deleted(&self) -> Result<Vec<User>> {
+        let users = sqlx::query_as!(
+            User,
+            r#"
+            SELECT id, username, display_name, email, password_hash, email_verified as "email_verified: bool", admin as "admin: bool", rating
             FROM users
             ORDER BY id
             "#,
@@ -395,6 +467,8 @@ impl<'a> UserRepository<'a> {
 
     /// Update a user's information.
     pub async fn update(&self, id: Uuid, username: &str, admin: bool) -> Result<Option<User>> {
+        User::validate_username(username)?;
+
         let result = sqlx::query!(
             r#"
             UPDATE users
@@ -406,6 +480,35 @@ impl<'a> UserRepository<'a> {
             id,
         )
         .execute(self.db)
+        .await
+        .map_err(Self::map_unique_violation)?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        self.find_by_id(id).await
+    }
+
+    /// Set or clear a user's display name.
+    pub async fn update_display_name(
+        &self,
+        id: Uuid,
+        display_name: Option<&str>,
+    ) -> Result<Option<User>> {
+        let display_name = User::normalize_display_name(display_name)?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET display_name = $1
+            WHERE id = $2
+              AND deleted_at IS NULL
+            "#,
+            display_name,
+            id,
+        )
+        .execute(self.db)
         .await?;
 
         if result.rows_affected() == 0 {
@@ -415,26 +518,173 @@ impl<'a> UserRepository<'a> {
         self.find_by_id(id).await
     }
 
+    /// Store a user's new rating.
+    pub async fn update_rating(&self, id: Uuid, rating: f64) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET rating = $1
+            WHERE id = $2
+            "#,
+            rating,
+            id,
+        )
+        .execute(self.db)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Update a user's password.
     pub async fn update_password(&self, id: Uuid, password: &str) -> Result<bool> {
+        let mut transaction = self.db.begin().await?;
+        let updated = Self::replace_password(&mut transaction, id, password).await?;
+        transaction.commit().await?;
+        Ok(updated)
+    }
+
+    /// Replaces a user's password, rejecting any of the last
+    /// [`PASSWORD_HISTORY_SIZE`] passwords, the current one included.
+    ///
+    /// The replaced hash is moved into the history table and entries beyond
+    /// the history size are pruned. Returns `false` if the user does not exist.
+    async fn replace_password(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        password: &str,
+    ) -> Result<bool> {
         let password_hash = User::hash_password(password)?;
 
-        let result = sqlx::query!(
+        let current = sqlx::query!(
+            r#"
+            SELECT password_hash
+            FROM users
+            WHERE id = $1
+              AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+            user_id,
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        let Some(current) = current else {
+            return Ok(false);
+        };
+
+        let history = sqlx::query!(
+            r#"
+            SELECT password_hash
+            FROM password_history
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+            user_id,
+            PASSWORD_HISTORY_SIZE - 1,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let previously_used = std::iter::once(&current.password_hash)
+            .chain(history.iter().map(|record| &record.password_hash))
+            .any(|hash| User::password_matches_hash(password, hash));
+
+        if previously_used {
+            return Err(Error::User(UserError::PasswordPreviouslyUsed));
+        }
+
+        sqlx::query!(
             r#"
             UPDATE users
             SET password_hash = $1
             WHERE id = $2
             "#,
             password_hash,
-            id,
+            user_id,
         )
-        .execute(self.db)
+        .execute(&mut *conn)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        sqlx::query!(
+            r#"
+            INSERT INTO password_history (id, user_id, password_hash)
+            VALUES ($1, $2, $3)
+            "#,
+            Uuid::new_v4(),
+            user_id,
+            current.password_hash,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM password_history
+            WHERE user_id = $1
+              AND id NOT IN (
+                SELECT id
+                FROM password_history
+                WHERE user_id = $1
+                ORDER BY created_at DESC
+                LIMIT $2
+              )
+            "#,
+            user_id,
+            PASSWORD_HISTORY_SIZE - 1,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(true)
     }
 
-    /// Delete a user by their ID.
+    /// Mark a user as deleted without removing their row, so references from
+    /// other tables stay intact. Only the ID is kept: the username and email
+    /// are replaced with `deleted-<id>`, freeing them for new accounts, and
+    /// the password hash with one that never matches. Revokes all of the
+    /// user's refresh tokens and forgets their previous passwords.
+    pub async fn soft_delete(&self, user_id: Uuid) -> Result<bool> {
+        let mut transaction = self.db.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET deleted_at = NOW(),
+                username = 'deleted-' || id,
+                email = 'deleted-' || id,
+                display_name = NULL,
+                password_hash = ''
+            WHERE id = $1
+              AND deleted_at IS NULL
+            "#,
+            user_id,
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            DELETE FROM password_history
+            WHERE user_id = $1
+            "#,
+            user_id,
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        self.revoke_all_refresh_tokens(user_id).await?;
+        Ok(true)
+    }
+
+    /// Delete a user by their ID. Users who created games cannot be removed,
+    /// as the games still reference them; soft delete those instead.
     pub async fn delete(&self, id: Uuid) -> Result<bool> {
         let result = sqlx::query!(
             r#"
@@ -444,7 +694,13 @@ impl<'a> UserRepository<'a> {
             id,
         )
         .execute(self.db)
-        .await?;
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db_err) if db_err.is_foreign_key_violation() => {
+                Error::Conflict("the user has created games".to_string())
+            }
+            e => Error::Database(e),
+        })?;
 
         Ok(result.rows_affected() > 0)
     }
@@ -0,0 +1,41 @@
+use crate::{models::GameCreation, prelude::*, repositories::AuditRepository};
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use tracing::{debug, warn};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+pub fn routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(list_game_creations))
+}
+
+/// List game creation records, newest first (admin only).
+#[utoipa::path(
+    get,
+    path = "/audit/games",
+    tag = "admin",
+    params(Pagination),
+    responses(
+        (status = 200, body = Page<GameCreation>),
+        (status = 404, description = "The requester is not an admin"),
+    )
+)]
+async fn list_game_creations(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<GameCreation>>> {
+    debug!(requester_id = %claims.sub, is_admin = claims.admin, "Game creation audit log requested");
+    if !claims.admin {
+        warn!(requester_id = %claims.sub, "Non-admin attempted to read the game creation audit log");
+        return Err(Error::NotFound);
+    }
+
+    let repo = AuditRepository::new(&state.db);
+    let records = repo
+        .list_game_creations(pagination.offset(), pagination.limit())
+        .await?;
+    let total = repo.count_game_creations().await?;
+    Ok(Json(Page::new(records, total, pagination)))
+}
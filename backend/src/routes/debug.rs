@@ -0,0 +1,45 @@
+use crate::prelude::InternalMessage;
+use axum::{
+    Json, Router,
+    extract::Request,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+
+/// When `enabled`, let requests with `?debug=1` see the details of internal
+/// errors as an `internal_message` field. Without it the router is returned
+/// untouched.
+pub fn with_debug_errors<S>(router: Router<S>, enabled: bool) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if enabled {
+        router.layer(middleware::from_fn(debug_errors))
+    } else {
+        router
+    }
+}
+
+async fn debug_errors(request: Request, next: Next) -> Response {
+    let debug = request
+        .uri()
+        .query()
+        .is_some_and(|query| query.split('&').any(|pair| pair == "debug=1"));
+    let response = next.run(request).await;
+    if !debug {
+        return response;
+    }
+
+    let Some(InternalMessage(message)) = response.extensions().get::<InternalMessage>().cloned()
+    else {
+        return response;
+    };
+
+    let status = response.status();
+    let body = json!({
+        "error": status.canonical_reason().unwrap_or("Error"),
+        "internal_message": message,
+    });
+    (status, Json(body)).into_response()
+}
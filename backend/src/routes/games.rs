@@ -1,109 +1,549 @@
 use crate::{
-    games::{Game, GameCommand, GameEvent, GameListItem},
+    games::{DEFAULT_WORLD, Game, GameCommand, GameEvent, GameListItem, WorldMeta},
+    models::{Invite, InviteError},
     prelude::*,
-    repositories::UserRepository,
+    repositories::{AuditRepository, GameRepository, InviteRepository, UserRepository},
 };
 use axum::{
-    Json, Router,
+    Extension, Json,
+    body::Bytes,
     extract::{
-        Path, State, WebSocketUpgrade,
-        ws::{Message, WebSocket},
+        ConnectInfo, Path, Query, State, WebSocketUpgrade,
+        ws::{CloseFrame, Message, WebSocket, close_code},
     },
     response::{
         IntoResponse,
         sse::{Event, KeepAlive, Sse},
     },
-    routing::get,
 };
-use std::{convert::Infallible, time::Duration};
+use serde::Deserialize;
+use std::{convert::Infallible, net::SocketAddr, time::Duration};
+use tokio::{sync::broadcast, time::Instant};
 use tokio_stream::{StreamExt, wrappers::WatchStream};
+use tracing::{Instrument, Span, debug, field, info, instrument, warn};
+use utoipa::{IntoParams, ToSchema};
+use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/", get(list_games).put(create_game))
-        .route("/stream", get(list_games_sse))
-        .route("/{id}", get(get_game))
-        .route("/{id}/ws", get(game_ws))
+/// Shortest time between two game list updates sent to an SSE client.
+const GAME_LIST_SSE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Payload of the pings sent to game WebSocket clients.
+const HEARTBEAT_PAYLOAD: &[u8] = b"heartbeat";
+
+/// Binary message a client sends to switch its connection from JSON text
+/// frames to MessagePack binary frames.
+const MSGPACK_HANDSHAKE: &[u8] = b"msgpack";
+
+/// Longest time a client may take to answer a heartbeat ping. Short
+/// heartbeat intervals shorten it to half the interval.
+const HEARTBEAT_PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(list_games, create_game))
+        .routes(routes!(list_games_sse))
+        .routes(routes!(list_worlds))
+        .routes(routes!(join_by_code))
+        .routes(routes!(get_game, delete_game))
+        .routes(routes!(get_game_history))
+        .routes(routes!(create_invite))
+        .routes(routes!(game_ws))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ListGamesFilter {
+    /// Only list games the signed-in user has joined as a player.
+    #[serde(default)]
+    player_me: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "games",
+    params(ListGamesFilter, Pagination),
+    responses(
+        (status = 200, body = Page<GameListItem>),
+        (status = 401, description = "`player_me` was requested without signing in"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = claims.as_ref().map(|c| field::display(c.sub))))]
+async fn list_games(
+    State(state): State<AppState>,
+    claims: Option<Claims>,
+    Query(filter): Query<ListGamesFilter>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<GameListItem>>> {
+    let mut games = if filter.player_me {
+        let claims = claims.ok_or(ClaimsError::TokenMissing)?;
+        state.games.for_player(claims.sub).await
+    } else {
+        state.games.list_games().await
+    };
+    // Games are kept in a map, so give the pages a stable order
+    games.sort_by_key(|game| game.id);
+    Ok(Json(Page::from_vec(games, pagination)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/worlds",
+    tag = "games",
+    responses((status = 200, body = Vec<WorldMeta>))
+)]
+#[instrument(skip_all)]
+async fn list_worlds(State(state): State<AppState>) -> Json<Vec<WorldMeta>> {
+    Json(state.worlds.list())
 }
 
-async fn list_games(State(state): State<AppState>) -> Json<Vec<GameListItem>> {
-    Json(state.games.list_games().await)
+#[derive(Deserialize, Default, ToSchema)]
+struct CreateGameRequest {
+    /// World preset to play on, the default world if missing.
+    world_name: Option<String>,
+    /// Game settings, defaults for any that are missing.
+    config: Option<common::GameConfig>,
 }
 
-async fn create_game(State(state): State<AppState>, claims: Claims) -> Result<Json<common::Game>> {
+#[utoipa::path(
+    put,
+    path = "/",
+    tag = "games",
+    request_body = CreateGameRequest,
+    responses(
+        (status = 200, body = common::Game),
+        (status = 400, description = "No such world or invalid settings"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub, game_id = field::Empty))]
+async fn create_game(
+    State(state): State<AppState>,
+    claims: Claims,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    body: Bytes,
+) -> Result<Json<common::Game>> {
+    // Clients may send a JSON content type without a body
+    let payload: CreateGameRequest = if body.is_empty() {
+        CreateGameRequest::default()
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|err| Error::BadRequest(format!("Invalid request body: {err}")))?
+    };
+    let world_name = payload.world_name.as_deref().unwrap_or(DEFAULT_WORLD);
+    let world = state
+        .worlds
+        .load(world_name)
+        .ok_or_else(|| Error::BadRequest(format!("Unknown world '{world_name}'")))?;
+    let config = payload.config.unwrap_or_default();
+    config
+        .validate()
+        .map_err(|err| Error::BadRequest(format!("Invalid game settings: {err}")))?;
+
     let repo = UserRepository::new(&state.db);
     let user = repo.find_by_id(claims.sub).await?.ok_or(Error::NotFound)?;
     let creator = user.into();
 
-    let world = common::World::from_string(include_str!("../../worlds/default.world"));
-    let game = state.games.create_game(world, creator).await;
+    let game = state.games.create_game(world, config, creator).await?;
+    Span::current().record("game_id", field::display(game.id));
+    debug!(world = world_name, "Game created");
+
+    let ip_address = connect_info.map(|Extension(ConnectInfo(addr))| addr.ip());
+    if let Err(err) = AuditRepository::new(&state.db)
+        .record_game_creation(game.id, claims.sub, world_name, ip_address)
+        .await
+    {
+        warn!("Failed to record game creation: {err}");
+    }
+
     Ok(Json(game.snapshot().await))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = "games",
+    params(("id" = Uuid, Path, description = "Game ID")),
+    responses(
+        (status = 200, body = common::Game),
+        (status = 404, description = "No such game"),
+    )
+)]
+#[instrument(skip_all, fields(game_id = %id))]
 async fn get_game(
     State(state): State<AppState>,
     Path(id): Path<uuid::Uuid>,
 ) -> Result<Json<common::Game>> {
+    debug!("Fetching game");
+    let game = state.games.get_game(&id).await.ok_or(Error::NotFound)?;
+    // Anonymous viewers own no areas, so fog of war hides every dice count
+    Ok(Json(game.snapshot_for(Uuid::nil()).await))
+}
+
+/// Cancel and remove a game. Its creator may delete it before it starts or
+/// after it has finished, admins at any time.
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = "games",
+    params(("id" = Uuid, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "Game deleted"),
+        (status = 403, description = "The requester is not the creator or an admin, or the game is in progress"),
+        (status = 404, description = "No such game"),
+    )
+)]
+#[instrument(skip_all, fields(game_id = %id, user_id = %claims.sub))]
+async fn delete_game(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+    claims: Claims,
+) -> Result<()> {
     let game = state.games.get_game(&id).await.ok_or(Error::NotFound)?;
+    let is_creator = game.creator.id == claims.sub;
+    if !is_creator && !claims.admin {
+        warn!("Non-creator attempted to delete game");
+        return Err(Error::Forbidden(
+            "only the creator or an admin can delete a game".to_string(),
+        ));
+    }
+
+    let reason = if is_creator {
+        "Game was cancelled by the creator"
+    } else {
+        "Game was cancelled by an admin"
+    };
+    if claims.admin {
+        game.cancel(reason).await?;
+    } else {
+        game.cancel_unless_in_progress(reason).await?;
+    }
+    state.games.remove_game(id).await;
+    info!(is_admin = claims.admin, "Game deleted");
+    Ok(())
+}
+
+/// Every move played in a game so far, oldest first, a page at a time.
+/// History is public, so no sign-in is needed. Finished games that have been
+/// removed from memory are served from the database. Moves reveal dice
+/// counts, so the history of a fog of war game is withheld until it has
+/// finished.
+#[utoipa::path(
+    get,
+    path = "/{id}/history",
+    tag = "games",
+    params(("id" = Uuid, Path, description = "Game ID"), Pagination),
+    responses(
+        (status = 200, body = Page<common::MoveRecord>),
+        (status = 403, description = "The game has fog of war and is still in progress"),
+        (status = 404, description = "No such game"),
+    )
+)]
+#[instrument(skip_all, fields(game_id = %id, user_id = claims.as_ref().map(|c| field::display(c.sub))))]
+async fn get_game_history(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+    claims: Option<Claims>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<common::MoveRecord>>> {
+    debug!("Fetching game history");
+    let repo = GameRepository::new(&state.db);
+    let snapshot = match state.games.get_game(&id).await {
+        Some(game) => game.snapshot().await,
+        None => repo.load_snapshot(id).await?.ok_or(Error::NotFound)?,
+    };
+    if snapshot.config.fog_of_war && snapshot.state != common::GameState::Finished {
+        return Err(Error::Forbidden(
+            "the history of a fog of war game is only available once it has finished".to_string(),
+        ));
+    }
+
+    let moves = repo
+        .list_moves(id, pagination.offset(), pagination.limit())
+        .await?;
+    let total = repo.count_moves(id).await?;
+    Ok(Json(Page::new(moves, total, pagination)))
+}
+
+/// Create a single-use invite code for a game. Only its creator and players
+/// may invite, and only while the game is waiting for players.
+#[utoipa::path(
+    post,
+    path = "/{id}/invite",
+    tag = "games",
+    params(("id" = Uuid, Path, description = "Game ID")),
+    responses(
+        (status = 200, body = Invite),
+        (status = 403, description = "The requester is not the creator or a player"),
+        (status = 404, description = "No such game"),
+        (status = 409, description = "The game has already started"),
+    )
+)]
+#[instrument(skip_all, fields(game_id = %id, user_id = %claims.sub))]
+async fn create_invite(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+    claims: Claims,
+) -> Result<Json<Invite>> {
+    let game = state.games.get_game(&id).await.ok_or(Error::NotFound)?;
+    if game.creator.id != claims.sub && !game.has_player(claims.sub).await {
+        warn!("Non-member attempted to create an invite");
+        return Err(Error::Forbidden(
+            "only the creator or a player can invite to a game".to_string(),
+        ));
+    }
+    if game.snapshot().await.state != common::GameState::WaitingForPlayers {
+        return Err(InviteError::GameStarted.into());
+    }
+
+    let repo = InviteRepository::new(&state.db);
+    let invite = repo.create(game.id, claims.sub).await?;
+    Ok(Json(invite))
+}
+
+#[derive(Deserialize, ToSchema)]
+struct JoinByCodeRequest {
+    code: String,
+}
+
+/// Join a game using an invite code, consuming the code.
+#[utoipa::path(
+    post,
+    path = "/join-by-code",
+    tag = "games",
+    request_body = JoinByCodeRequest,
+    responses(
+        (status = 200, body = common::Game),
+        (status = 404, description = "Unknown invite code"),
+        (status = 409, description = "The game has already started"),
+        (status = 410, description = "The invite code has been used"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub, game_id = field::Empty))]
+async fn join_by_code(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<JoinByCodeRequest>,
+) -> Result<Json<common::Game>> {
+    let users = UserRepository::new(&state.db);
+    let user = users.find_by_id(claims.sub).await?.ok_or(Error::NotFound)?;
+
+    let invites = InviteRepository::new(&state.db);
+    let invite = invites
+        .find_by_code(&payload.code)
+        .await?
+        .ok_or(InviteError::NotFound)?;
+
+    if invite.is_used() {
+        return Err(InviteError::AlreadyUsed.into());
+    }
+
+    let game = state
+        .games
+        .get_game(&invite.game_id)
+        .await
+        .ok_or(Error::NotFound)?;
+    Span::current().record("game_id", field::display(game.id));
+
+    if game.snapshot().await.state != common::GameState::WaitingForPlayers {
+        return Err(InviteError::GameStarted.into());
+    }
+
+    // Claim the code first so two users cannot both join with it, and hand
+    // it back if the join fails
+    if !invites.consume(invite.id, user.id).await? {
+        return Err(InviteError::AlreadyUsed.into());
+    }
+
+    if let Err(err) = game
+        .join_player(user.id, user.public_name().to_string())
+        .await
+    {
+        invites.release(invite.id, user.id).await?;
+        return Err(err);
+    }
     Ok(Json(game.snapshot().await))
 }
 
+/// Encoding of the messages on a game WebSocket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WireFormat {
+    Json,
+    MessagePack,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct GameSocketParams {
+    /// Join as a spectator instead of a player.
+    #[serde(default)]
+    spectate: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/ws",
+    tag = "games",
+    params(("id" = Uuid, Path, description = "Game ID"), GameSocketParams),
+    responses(
+        (status = 101, description = "WebSocket of game commands and events"),
+        (status = 404, description = "No such game"),
+        (status = 409, description = "The game is full"),
+    )
+)]
+#[instrument(skip_all, fields(game_id = %id, user_id = %claims.sub))]
 async fn game_ws(
     ws: WebSocketUpgrade,
     State(state): State<AppState>,
     Path(id): Path<uuid::Uuid>,
+    Query(params): Query<GameSocketParams>,
     claims: Claims,
 ) -> Result<impl IntoResponse> {
     let repo = UserRepository::new(&state.db);
     let user = repo.find_by_id(claims.sub).await?.ok_or(Error::NotFound)?;
-    let player_name = user.username.clone();
+    let user_name = user.public_name().to_string();
 
     let game = state.games.get_game(&id).await.ok_or(Error::NotFound)?;
-    match game.join_player(user.id, player_name).await {
+    if !params.spectate && game.is_full().await && !game.has_player(user.id).await {
+        return Err(Error::Conflict(common::GameError::GameFull.to_string()));
+    }
+
+    let joined = if params.spectate {
+        game.join_spectator(user.id, user_name.clone()).await
+    } else {
+        game.join_player(user.id, user_name.clone()).await
+    };
+    match joined {
         Ok(()) => {}
         Err(Error::GameError(common::GameError::PlayerAlreadyInGame)) => {}
         Err(err) => return Err(err),
     }
 
-    Ok(ws.on_upgrade(move |socket| handle_game_socket(socket, user.id, game)))
+    let is_player = game.has_player(user.id).await;
+    let heartbeat = Duration::from_secs(state.config.ws_heartbeat_interval_seconds);
+    let shutdown = state.shutdown.subscribe();
+    let span = Span::current();
+    Ok(ws.on_upgrade(move |socket| {
+        async move {
+            // Track players' presence so dropped connections can be given a
+            // chance to reconnect before their turns are skipped
+            if is_player {
+                game.player_connected(user.id);
+            }
+            let closed = handle_game_socket(
+                socket,
+                user.id,
+                user_name.clone(),
+                game.clone(),
+                heartbeat,
+                shutdown,
+            )
+            .await;
+            if is_player {
+                if closed {
+                    game.player_left(user.id, user_name);
+                }
+                game.player_disconnected(user.id).await;
+            }
+        }
+        .instrument(span)
+    }))
 }
 
-async fn handle_game_socket(mut socket: WebSocket, user_id: Uuid, game: Game) {
+/// Serve a game socket until it closes. Returns whether the client closed
+/// it with a close frame.
+async fn handle_game_socket(
+    mut socket: WebSocket,
+    user_id: Uuid,
+    user_name: String,
+    game: Game,
+    heartbeat_interval: Duration,
+    mut shutdown: broadcast::Receiver<()>,
+) -> bool {
+    let mut format = WireFormat::Json;
+    let version = GameEvent::ProtocolVersion {
+        version: common::PROTOCOL_VERSION,
+    };
+    if send_event(&mut socket, format, version).await.is_err() {
+        return false;
+    }
+
+    // Subscribe before taking the snapshot, so no diff published in between
+    // is lost. Diffs hold whole values, so one the snapshot already includes
+    // is harmless.
+    let mut events = game.subscribe_events();
+    let snapshot = game.snapshot_for(user_id).await;
     if send_event(
         &mut socket,
+        format,
         GameEvent::Snapshot {
-            game: game.snapshot().await,
+            game: Box::new(snapshot),
         },
     )
     .await
     .is_err()
     {
-        return;
+        return false;
     }
 
-    let mut events = game.subscribe_events();
+    let mut heartbeat =
+        tokio::time::interval_at(Instant::now() + heartbeat_interval, heartbeat_interval);
+    let pong_timeout = HEARTBEAT_PONG_TIMEOUT.min(heartbeat_interval / 2);
+    let mut pong_deadline: Option<Instant> = None;
 
     loop {
         tokio::select! {
+            _ = shutdown.recv() => {
+                let _ = socket
+                    .send(Message::Close(Some(CloseFrame {
+                        code: close_code::AWAY,
+                        reason: "server shutting down".into(),
+                    })))
+                    .await;
+                break;
+            }
+            _ = heartbeat.tick() => {
+                if socket.send(Message::Ping(HEARTBEAT_PAYLOAD.into())).await.is_err() {
+                    break;
+                }
+                pong_deadline.get_or_insert(Instant::now() + pong_timeout);
+            }
+            _ = pong_timeout_elapsed(pong_deadline) => {
+                debug!(%user_id, "Closing game socket after missed heartbeat");
+                let _ = socket
+                    .send(Message::Close(Some(CloseFrame {
+                        code: close_code::AWAY,
+                        reason: "heartbeat timeout".into(),
+                    })))
+                    .await;
+                break;
+            }
             message = socket.recv() => {
                 match message {
                     Some(Ok(Message::Text(text))) => {
-                        match serde_json::from_str::<GameCommand>(&text) {
-                            Ok(command) => {
-                                if let Err(err) = execute_command(&game, user_id, command).await {
-                                    let _ = send_event(
-                                        &mut socket,
-                                        GameEvent::Error { message: err.to_string() }
-                                    ).await;
-                                }
-                            }
-                            Err(err) => {
-                                let _ = send_event(
-                                    &mut socket,
-                                    GameEvent::Error { message: format!("invalid command payload: {err}") }
-                                ).await;
-                            }
+                        let command = serde_json::from_str(&text).map_err(|err| err.to_string());
+                        if !run_command(&mut socket, format, &game, user_id, &user_name, command).await {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Binary(data))) if format == WireFormat::Json => {
+                        if data != MSGPACK_HANDSHAKE {
+                            continue;
+                        }
+                        // Resend the state so the client has it in its new format
+                        format = WireFormat::MessagePack;
+                        if send_event(
+                            &mut socket,
+                            format,
+                            GameEvent::Snapshot { game: Box::new(game.snapshot_for(user_id).await) }
+                        ).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Binary(data))) => {
+                        let command = rmp_serde::from_slice(&data).map_err(|err| err.to_string());
+                        if !run_command(&mut socket, format, &game, user_id, &user_name, command).await {
+                            break;
                         }
                     }
                     Some(Ok(Message::Ping(payload))) => {
@@ -111,22 +551,25 @@ async fn handle_game_socket(mut socket: WebSocket, user_id: Uuid, game: Game) {
                             break;
                         }
                     }
-                    Some(Ok(Message::Close(_))) | None => break,
-                    Some(Ok(_)) => {}
+                    Some(Ok(Message::Pong(_))) => pong_deadline = None,
+                    Some(Ok(Message::Close(_))) => return true,
+                    None => break,
                     Some(Err(_)) => break,
                 }
             }
             event = events.recv() => {
                 match event {
                     Ok(event) => {
-                        if send_event(&mut socket, event).await.is_err() {
+                        let event = personalise_event(&game, user_id, event).await;
+                        if send_event(&mut socket, format, event).await.is_err() {
                             break;
                         }
                     }
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
                         if send_event(
                             &mut socket,
-                            GameEvent::Snapshot { game: game.snapshot().await }
+                            format,
+                            GameEvent::Snapshot { game: Box::new(game.snapshot_for(user_id).await) }
                         ).await.is_err() {
                             break;
                         }
@@ -136,27 +579,150 @@ async fn handle_game_socket(mut socket: WebSocket, user_id: Uuid, game: Game) {
             }
         }
     }
+
+    false
+}
+
+/// Resolve once `deadline` has passed, or never if no pong is awaited.
+async fn pong_timeout_elapsed(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
 }
 
-async fn execute_command(game: &Game, user_id: Uuid, command: GameCommand) -> Result<()> {
+/// Adapt a broadcast event to what `user_id` may see. Snapshots are put
+/// through fog of war, and since diffs cannot be, fogged games send a fresh
+/// snapshot instead. Attacks and bonus dice hide the dice of areas out of
+/// sight.
+async fn personalise_event(game: &Game, user_id: Uuid, event: GameEvent) -> GameEvent {
+    match event {
+        GameEvent::Snapshot { game: snapshot } => GameEvent::Snapshot {
+            game: Box::new(snapshot.snapshot_for_player(user_id)),
+        },
+        GameEvent::Diff { .. } if game.has_fog_of_war().await => GameEvent::Snapshot {
+            game: Box::new(game.snapshot_for(user_id).await),
+        },
+        event @ (GameEvent::AttackResolved { .. } | GameEvent::DiceDistributed { .. }) => {
+            match game.visible_areas_for(user_id).await {
+                Some(visible) => event.fogged(&visible),
+                None => event,
+            }
+        }
+        event => event,
+    }
+}
+
+/// Run a command from the socket, answering failures with an error event.
+/// Returns `false` if the socket was closed, either because the client
+/// speaks another protocol version or because the player resigned.
+async fn run_command(
+    socket: &mut WebSocket,
+    format: WireFormat,
+    game: &Game,
+    user_id: Uuid,
+    user_name: &str,
+    command: std::result::Result<GameCommand, String>,
+) -> bool {
+    if let Ok(GameCommand::ProtocolVersion { version }) = command
+        && version != common::PROTOCOL_VERSION
+    {
+        debug!(%user_id, version, "Closing game socket of client with another protocol version");
+        let _ = socket
+            .send(Message::Close(Some(CloseFrame {
+                code: close_code::PROTOCOL,
+                reason: format!(
+                    "unsupported protocol version {version}, expected {}",
+                    common::PROTOCOL_VERSION
+                )
+                .into(),
+            })))
+            .await;
+        return false;
+    }
+
+    let resigning = matches!(command, Ok(GameCommand::Resign));
+    let message = match command {
+        Ok(command) => match execute_command(game, user_id, user_name, command).await {
+            Ok(()) if resigning => {
+                close_resigned_socket(socket, format, game, user_id).await;
+                return false;
+            }
+            Ok(()) => return true,
+            Err(err) => err.to_string(),
+        },
+        Err(err) => format!("invalid command payload: {err}"),
+    };
+    let _ = send_event(socket, format, GameEvent::Error { message }).await;
+    true
+}
+
+/// The game is over for a player who resigned, whether or not the others
+/// play on. Tell them so and close their socket.
+async fn close_resigned_socket(
+    socket: &mut WebSocket,
+    format: WireFormat,
+    game: &Game,
+    user_id: Uuid,
+) {
+    let snapshot = game.snapshot_for(user_id).await;
+    let _ = send_event(socket, format, GameEvent::finished(&snapshot, "Resigned")).await;
+    let _ = socket
+        .send(Message::Close(Some(CloseFrame {
+            code: close_code::NORMAL,
+            reason: "resigned".into(),
+        })))
+        .await;
+}
+
+async fn execute_command(
+    game: &Game,
+    user_id: Uuid,
+    user_name: &str,
+    command: GameCommand,
+) -> Result<()> {
+    if !command.is_allowed_for_spectator() && game.is_spectator(user_id).await {
+        return Err(common::GameError::SpectatorActionNotAllowed.into());
+    }
+
     match command {
         GameCommand::Start => game.start_game().await,
         GameCommand::Attack { from_id, to_id } => game.attack(from_id, to_id, user_id).await,
         GameCommand::EndTurn => game.end_turn(user_id).await,
+        GameCommand::Resign => game.resign(user_id).await,
+        GameCommand::Chat { message } => game.chat(user_id, user_name, &message),
+        GameCommand::JoinAsSpectator => game.join_spectator(user_id, user_name.to_string()).await,
         GameCommand::Ping => {
             game.touch_activity();
             Ok(())
         }
+        // Mismatched versions never get here, see `run_command`
+        GameCommand::ProtocolVersion { .. } => Ok(()),
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/stream",
+    tag = "games",
+    responses((
+        status = 200,
+        content_type = "text/event-stream",
+        description = "`games` events carrying the current game list",
+    ))
+)]
+#[instrument(skip_all)]
 async fn list_games_sse(
     State(state): State<AppState>,
 ) -> Sse<impl tokio_stream::Stream<Item = std::result::Result<Event, Infallible>>> {
-    let stream = WatchStream::new(state.games.subscribe_list()).map(|games| {
-        let data = serde_json::to_string(&games).unwrap_or_else(|_| "[]".to_string());
-        Ok(Event::default().event("games").data(data))
-    });
+    // Throttling the watch stream drops intermediate lists, so a burst of
+    // changes is sent as its latest state at most once per interval
+    let stream = WatchStream::new(state.games.subscribe_list())
+        .throttle(GAME_LIST_SSE_INTERVAL)
+        .map(|games| {
+            let data = serde_json::to_string(&games).unwrap_or_else(|_| "[]".to_string());
+            Ok(Event::default().event("games").data(data))
+        });
 
     Sse::new(stream).keep_alive(
         KeepAlive::new()
@@ -165,10 +731,16 @@ async fn list_games_sse(
     )
 }
 
-async fn send_event(socket: &mut WebSocket, event: GameEvent) -> std::result::Result<(), ()> {
-    let payload = serde_json::to_string(&event).map_err(|_| ())?;
-    socket
-        .send(Message::Text(payload.into()))
-        .await
-        .map_err(|_| ())
+async fn send_event(
+    socket: &mut WebSocket,
+    format: WireFormat,
+    event: GameEvent,
+) -> std::result::Result<(), ()> {
+    let message = match format {
+        WireFormat::Json => Message::Text(serde_json::to_string(&event).map_err(|_| ())?.into()),
+        WireFormat::MessagePack => {
+            Message::Binary(rmp_serde::to_vec_named(&event).map_err(|_| ())?.into())
+        }
+    };
+    socket.send(message).await.map_err(|_| ())
 }
@@ -1,10 +1,11 @@
 use crate::prelude::*;
-use axum::{Router, routing::get};
+use utoipa_axum::{router::OpenApiRouter, routes};
 
-pub fn routes() -> Router<AppState> {
-    Router::new().route("/", get(health))
+pub fn routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(health))
 }
 
+#[utoipa::path(get, path = "/", tag = "health", responses((status = 200, body = String)))]
 async fn health() -> &'static str {
     "OK"
 }
@@ -0,0 +1,14 @@
+use crate::prelude::*;
+use axum::{Router, extract::State, http::header, response::IntoResponse, routing::get};
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/metrics", get(metrics))
+}
+
+/// Prometheus scrape endpoint, kept out of the versioned API.
+async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.games.metrics().render(),
+    )
+}
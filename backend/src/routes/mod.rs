@@ -1,13 +1,69 @@
+mod admin;
+mod debug;
 mod games;
 mod health;
+mod metrics;
+mod security;
 mod users;
 
 use crate::prelude::*;
-use axum::Router;
+use axum::{Router, http::HeaderValue, middleware::map_response, response::Response};
+use utoipa::OpenApi;
+use utoipa_axum::router::OpenApiRouter;
+use utoipa_swagger_ui::SwaggerUi;
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
+pub use debug::with_debug_errors;
+pub use security::with_security_headers;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "rsdice", description = "HTTP API of the rsdice game server"),
+    tags(
+        (name = "health", description = "Service health"),
+        (name = "users", description = "Accounts and authentication"),
+        (name = "games", description = "Lobby, games and invites"),
+        (name = "admin", description = "Administration, admins only"),
+    )
+)]
+struct ApiDoc;
+
+pub fn routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
         .nest("/health", health::routes())
         .nest("/users", users::routes())
         .nest("/games", games::routes())
+        .nest("/admin", admin::routes())
+}
+
+/// All API routes, served under `/api/v1` and under the deprecated,
+/// unversioned `/api` prefix.
+///
+/// The OpenAPI document is served at `/api/docs` with a Swagger UI at
+/// `/api/docs/ui`. Only the canonical `/api/v1` paths are documented.
+/// Prometheus metrics are served at `/metrics`.
+pub fn api() -> Router<AppState> {
+    let (v1, openapi) = OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .nest("/api/v1", routes())
+        .split_for_parts();
+    let (deprecated, _) = routes().split_for_parts();
+
+    Router::new()
+        .merge(v1)
+        .nest("/api", deprecated.layer(map_response(mark_deprecated)))
+        .merge(SwaggerUi::new("/api/docs/ui").url("/api/docs", openapi))
+        .merge(metrics::routes())
+}
+
+/// The application as served: [`api`] with security headers on every
+/// response and, if enabled, debug details on internal errors.
+pub fn app(config: &Config) -> Router<AppState> {
+    let app = with_debug_errors(api(), config.debug_mode);
+    with_security_headers(app, &config.csp_policy)
+}
+
+async fn mark_deprecated(mut response: Response) -> Response {
+    response
+        .headers_mut()
+        .insert("deprecation", HeaderValue::from_static("true"));
+    response
 }
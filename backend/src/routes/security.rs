@@ -0,0 +1,75 @@
+use axum::{
+    Router,
+    http::{
+        HeaderName, HeaderValue,
+        header::{
+            CONTENT_SECURITY_POLICY, REFERRER_POLICY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+        },
+    },
+};
+use tower_http::set_header::SetResponseHeaderLayer;
+
+/// Add security headers to every response of `router`. Headers a handler
+/// sets itself are left untouched.
+pub fn with_security_headers<S>(router: Router<S>, csp_policy: &str) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let csp_policy =
+        HeaderValue::from_str(csp_policy).expect("CSP policy is validated by Config::from_env");
+
+    [
+        (CONTENT_SECURITY_POLICY, csp_policy),
+        (X_FRAME_OPTIONS, HeaderValue::from_static("DENY")),
+        (X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff")),
+        (
+            REFERRER_POLICY,
+            HeaderValue::from_static("strict-origin-when-cross-origin"),
+        ),
+    ]
+    .into_iter()
+    .fold(
+        router,
+        |router, (name, value): (HeaderName, HeaderValue)| {
+            router.layer(SetResponseHeaderLayer::if_not_present(name, value))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, routing::get};
+    use tower::ServiceExt;
+
+    async fn headers_of(router: Router) -> axum::http::HeaderMap {
+        let response = with_security_headers(router, "default-src 'none'")
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        response.headers().clone()
+    }
+
+    #[tokio::test]
+    async fn adds_all_security_headers() {
+        let headers = headers_of(Router::new().route("/", get(|| async { "OK" }))).await;
+
+        assert_eq!(headers[CONTENT_SECURITY_POLICY], "default-src 'none'");
+        assert_eq!(headers[X_FRAME_OPTIONS], "DENY");
+        assert_eq!(headers[X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(headers[REFERRER_POLICY], "strict-origin-when-cross-origin");
+    }
+
+    #[tokio::test]
+    async fn keeps_headers_set_by_handler() {
+        let router = Router::new().route(
+            "/",
+            get(|| async { ([(X_FRAME_OPTIONS, "SAMEORIGIN")], "OK") }),
+        );
+
+        let headers = headers_of(router).await;
+
+        assert_eq!(headers[X_FRAME_OPTIONS], "SAMEORIGIN");
+        assert_eq!(headers[X_CONTENT_TYPE_OPTIONS], "nosniff");
+    }
+}
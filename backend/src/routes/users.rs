@@ -5,9 +5,9 @@ use crate::{
     repositories::UserRepository,
 };
 use axum::{
-    Json, Router,
-    extract::{Path, State},
-    routing::{get, post},
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
 };
 use axum_extra::extract::{
     CookieJar,
@@ -15,31 +15,33 @@ use axum_extra::extract::{
 };
 use chrono::Duration;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, warn};
+use tracing::{debug, info, instrument, warn};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
-const ACCESS_TOKEN_LIFETIME: Duration = Duration::minutes(15);
 const REFRESH_TOKEN_LIFETIME: Duration = Duration::days(30);
 const ACCESS_COOKIE: &str = "token";
 const REFRESH_COOKIE: &str = "refresh_token";
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/me", get(me))
-        .route("/", get(list_users).post(create_user))
-        .route("/{id}", get(get_user).put(update_user).delete(delete_user))
-        .route("/{id}/password", post(update_password))
-        .route("/auth", post(authenticate))
-        .route("/register", post(register))
-        .route("/refresh", post(refresh))
-        .route("/resend-verification", post(resend_verification))
-        .route("/verify-email", post(verify_email))
-        .route("/request-password-reset", post(request_password_reset))
-        .route("/reset-password", post(reset_password))
-        .route("/logout", post(logout))
-}
-
-#[derive(Serialize)]
+pub fn routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(me, update_me, delete_me))
+        .routes(routes!(update_profile))
+        .routes(routes!(list_users, create_user))
+        .routes(routes!(get_user, update_user, delete_user))
+        .routes(routes!(update_password))
+        .routes(routes!(authenticate))
+        .routes(routes!(register))
+        .routes(routes!(refresh))
+        .routes(routes!(resend_verification))
+        .routes(routes!(verify_email))
+        .routes(routes!(request_password_reset))
+        .routes(routes!(reset_password))
+        .routes(routes!(logout))
+}
+
+#[derive(Serialize, ToSchema)]
 struct AuthResponse {
     #[serde(flatten)]
     user: User,
@@ -52,8 +54,13 @@ async fn issue_session(
     repo: &UserRepository<'_>,
     user: User,
 ) -> Result<(CookieJar, Json<AuthResponse>)> {
-    let access_token = Claims::new(user.id, user.admin, &user.username, ACCESS_TOKEN_LIFETIME)
-        .encode(&state.config.jwt_secret)?;
+    let access_token = Claims::new(
+        user.id,
+        user.admin,
+        &user.username,
+        Duration::seconds(state.config.jwt_expiry_seconds),
+    )
+    .encode(&state.config.jwt_secret)?;
 
     let refresh_token = repo
         .create_refresh_token(user.id, REFRESH_TOKEN_LIFETIME)
@@ -98,6 +105,19 @@ async fn send_verification_email(
     Ok(())
 }
 
+async fn send_welcome_email(state: &AppState, user: &User) -> Result<()> {
+    let mail = Mail {
+        recipient: Recipient {
+            name: user.username.clone(),
+            email: user.email.clone(),
+        },
+        mail_type: MailType::Welcome,
+    };
+
+    state.email.send(&mail).await?;
+    Ok(())
+}
+
 async fn send_password_reset_email(
     state: &AppState,
     repo: &UserRepository<'_>,
@@ -118,6 +138,16 @@ async fn send_password_reset_email(
 }
 
 /// Get the current authenticated user.
+#[utoipa::path(
+    get,
+    path = "/me",
+    tag = "users",
+    responses(
+        (status = 200, body = User),
+        (status = 401, description = "Not signed in"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub))]
 async fn me(State(state): State<AppState>, claims: Claims) -> Result<Json<User>> {
     debug!(user_id = %claims.sub, "Fetching current user profile");
     let repo = UserRepository::new(&state.db);
@@ -126,20 +156,143 @@ async fn me(State(state): State<AppState>, claims: Claims) -> Result<Json<User>>
     Ok(Json(user))
 }
 
+#[derive(Deserialize, ToSchema)]
+struct UpdateMeRequest {
+    username: String,
+    password: String,
+}
+
+/// Change the current user's username. Requires the current password so a
+/// hijacked session cannot silently rename the account.
+#[utoipa::path(
+    patch,
+    path = "/me",
+    tag = "users",
+    request_body = UpdateMeRequest,
+    responses(
+        (status = 200, body = User),
+        (status = 400, description = "Invalid username or wrong password"),
+        (status = 409, description = "The username is taken"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub))]
+async fn update_me(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<UpdateMeRequest>,
+) -> Result<Json<User>> {
+    info!(user_id = %claims.sub, "Username change requested");
+    let repo = UserRepository::new(&state.db);
+    let user = repo.find_by_id(claims.sub).await?.ok_or(Error::NotFound)?;
+    user.verify_password(&payload.password)?;
+
+    let user = repo
+        .update(claims.sub, &payload.username, user.admin)
+        .await
+        .map_err(|err| match err {
+            Error::User(err @ UserError::UsernameExists) => Error::Conflict(err.to_string()),
+            err => err,
+        })?
+        .ok_or(Error::NotFound)?;
+    info!(user_id = %claims.sub, username = %user.username, "Username changed");
+    Ok(Json(user))
+}
+
+#[derive(Deserialize, ToSchema)]
+struct DeleteMeRequest {
+    password: String,
+}
+
+/// Delete the current user's account after confirming their password, and
+/// end their session.
+#[utoipa::path(
+    delete,
+    path = "/me",
+    tag = "users",
+    request_body = DeleteMeRequest,
+    responses(
+        (status = 204, description = "Account deleted and session ended"),
+        (status = 400, description = "Wrong password"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub))]
+async fn delete_me(
+    State(state): State<AppState>,
+    cookies: CookieJar,
+    claims: Claims,
+    Json(payload): Json<DeleteMeRequest>,
+) -> Result<(CookieJar, StatusCode)> {
+    info!(user_id = %claims.sub, "Account deletion requested");
+    let repo = UserRepository::new(&state.db);
+    let user = repo.find_by_id(claims.sub).await?.ok_or(Error::NotFound)?;
+    user.verify_password(&payload.password)?;
+
+    if !repo.soft_delete(claims.sub).await? {
+        return Err(Error::NotFound);
+    }
+    info!(user_id = %claims.sub, "Account deleted");
+    Ok((clear_session_cookies(cookies), StatusCode::NO_CONTENT))
+}
+
+#[derive(Deserialize, ToSchema)]
+struct UpdateProfileRequest {
+    display_name: Option<String>,
+}
+
+/// Update the current user's public profile.
+#[utoipa::path(
+    patch,
+    path = "/me/profile",
+    tag = "users",
+    request_body = UpdateProfileRequest,
+    responses((status = 200, body = User))
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub))]
+async fn update_profile(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<UpdateProfileRequest>,
+) -> Result<Json<User>> {
+    info!(user_id = %claims.sub, clear_display_name = payload.display_name.is_none(), "Profile update requested");
+    let repo = UserRepository::new(&state.db);
+    let user = repo
+        .update_display_name(claims.sub, payload.display_name.as_deref())
+        .await?
+        .ok_or(Error::NotFound)?;
+    info!(user_id = %claims.sub, "Profile updated");
+    Ok(Json(user))
+}
+
 /// List all users (admin only).
-async fn list_users(State(state): State<AppState>, claims: Claims) -> Result<Json<Vec<User>>> {
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "users",
+    params(Pagination),
+    responses(
+        (status = 200, body = Page<User>),
+        (status = 404, description = "The requester is not an admin"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub))]
+async fn list_users(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<User>>> {
     debug!(requester_id = %claims.sub, is_admin = claims.admin, "Listing users requested");
     if !claims.admin {
         warn!(requester_id = %claims.sub, "Non-admin attempted to list users");
         return Err(Error::NotFound);
     }
     let repo = UserRepository::new(&state.db);
-    let users = repo.find_all().await?;
-    debug!(requester_id = %claims.sub, user_count = users.len(), "Listed users");
-    Ok(Json(users))
+    let users = repo.list(pagination.offset(), pagination.limit()).await?;
+    let total = repo.count().await?;
+    debug!(requester_id = %claims.sub, user_count = users.len(), total, "Listed users");
+    Ok(Json(Page::new(users, total, pagination)))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateUserRequest {
     username: String,
     email: String,
@@ -149,6 +302,17 @@ struct CreateUserRequest {
 }
 
 /// Create a new user (admin only).
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = "users",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 200, body = User),
+        (status = 404, description = "The requester is not an admin"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub))]
 async fn create_user(
     State(state): State<AppState>,
     claims: Claims,
@@ -173,6 +337,17 @@ async fn create_user(
 }
 
 /// Get a user by ID.
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = "users",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, body = User),
+        (status = 404, description = "No such user, or not visible to the requester"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub, target_user_id = %id))]
 async fn get_user(
     State(state): State<AppState>,
     claims: Claims,
@@ -190,7 +365,7 @@ async fn get_user(
     Ok(Json(user))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct UpdateUserRequest {
     username: String,
     #[serde(default)]
@@ -198,6 +373,18 @@ struct UpdateUserRequest {
 }
 
 /// Update a user's information (admin only).
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = "users",
+    params(("id" = Uuid, Path, description = "User ID")),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, body = User),
+        (status = 404, description = "No such user, or the requester is not an admin"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub, target_user_id = %id))]
 async fn update_user(
     State(state): State<AppState>,
     claims: Claims,
@@ -218,13 +405,26 @@ async fn update_user(
     Ok(Json(user))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct UpdatePasswordRequest {
     current_password: String,
     password: String,
 }
 
 /// Update a user's password (user can update their own, admin can update any).
+#[utoipa::path(
+    post,
+    path = "/{id}/password",
+    tag = "users",
+    params(("id" = Uuid, Path, description = "User ID")),
+    request_body = UpdatePasswordRequest,
+    responses(
+        (status = 200, description = "Password updated"),
+        (status = 400, description = "Invalid new password or wrong current password"),
+        (status = 404, description = "No such user, or not visible to the requester"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub, target_user_id = %id))]
 async fn update_password(
     State(state): State<AppState>,
     claims: Claims,
@@ -254,6 +454,18 @@ async fn update_password(
 }
 
 /// Delete a user (admin only).
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = "users",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "User deleted"),
+        (status = 404, description = "No such user, or the requester is not an admin"),
+        (status = 409, description = "The user has created games"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub, target_user_id = %id))]
 async fn delete_user(
     State(state): State<AppState>,
     claims: Claims,
@@ -274,13 +486,25 @@ async fn delete_user(
     Ok(())
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct AuthRequest {
     username: String,
     password: String,
 }
 
 /// Authenticate a user and return a JWT token in a cookie.
+#[utoipa::path(
+    post,
+    path = "/auth",
+    tag = "users",
+    request_body = AuthRequest,
+    responses(
+        (status = 200, body = AuthResponse, description = "Signed in; session cookies are set"),
+        (status = 400, description = "Wrong password"),
+        (status = 404, description = "No such user"),
+    )
+)]
+#[instrument(skip_all)]
 async fn authenticate(
     cookies: CookieJar,
     State(state): State<AppState>,
@@ -299,7 +523,7 @@ async fn authenticate(
     issue_session(cookies, &state, &repo, user).await
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct RegisterRequest {
     username: String,
     email: String,
@@ -307,6 +531,17 @@ struct RegisterRequest {
 }
 
 /// Register a new user (public endpoint).
+#[utoipa::path(
+    post,
+    path = "/register",
+    tag = "users",
+    request_body = RegisterRequest,
+    responses(
+        (status = 200, body = AuthResponse, description = "Registered and signed in"),
+        (status = 400, description = "Invalid or taken username or email, or weak password"),
+    )
+)]
+#[instrument(skip_all)]
 async fn register(
     cookies: CookieJar,
     State(state): State<AppState>,
@@ -326,6 +561,16 @@ async fn register(
 }
 
 /// Refresh access token from a long-lived refresh token cookie.
+#[utoipa::path(
+    post,
+    path = "/refresh",
+    tag = "users",
+    responses(
+        (status = 200, body = AuthResponse, description = "New session cookies are set"),
+        (status = 401, description = "Missing or invalid refresh token"),
+    )
+)]
+#[instrument(skip_all)]
 async fn refresh(
     cookies: CookieJar,
     State(state): State<AppState>,
@@ -348,6 +593,16 @@ async fn refresh(
 }
 
 /// Resend a verification email for the authenticated user.
+#[utoipa::path(
+    post,
+    path = "/resend-verification",
+    tag = "users",
+    responses(
+        (status = 200, description = "Verification email sent"),
+        (status = 400, description = "Email already verified"),
+    )
+)]
+#[instrument(skip_all, fields(user_id = %claims.sub))]
 async fn resend_verification(State(state): State<AppState>, claims: Claims) -> Result<()> {
     info!(user_id = %claims.sub, "Resend verification requested");
     let repo = UserRepository::new(&state.db);
@@ -364,12 +619,23 @@ async fn resend_verification(State(state): State<AppState>, claims: Claims) -> R
     Ok(())
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct VerifyEmailRequest {
     token: String,
 }
 
 /// Verifies a user's email address from a one-time token.
+#[utoipa::path(
+    post,
+    path = "/verify-email",
+    tag = "users",
+    request_body = VerifyEmailRequest,
+    responses(
+        (status = 200, description = "Email verified"),
+        (status = 400, description = "Invalid or expired token"),
+    )
+)]
+#[instrument(skip_all)]
 async fn verify_email(
     State(state): State<AppState>,
     Json(payload): Json<VerifyEmailRequest>,
@@ -379,19 +645,26 @@ async fn verify_email(
         "Verify email requested"
     );
     let repo = UserRepository::new(&state.db);
-    let verified = repo.verify_email_token(payload.token.trim()).await?;
-
-    if !verified {
+    let Some(user_id) = repo.verify_email_token(payload.token.trim()).await? else {
         warn!("Email verification failed due to invalid or expired token");
         return Err(Error::User(UserError::InvalidVerificationToken));
+    };
+
+    // A second outstanding token may verify an already verified address
+    if repo.mark_email_verified(user_id).await? {
+        let user = repo.find_by_id(user_id).await?.ok_or(Error::NotFound)?;
+        // The address is verified either way, so a failed mail is not an error
+        if let Err(err) = send_welcome_email(&state, &user).await {
+            warn!(%user_id, "Failed to send welcome email: {err}");
+        }
     }
 
-    info!("Email verification succeeded");
+    info!(%user_id, "Email verification succeeded");
 
     Ok(())
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct RequestPasswordResetRequest {
     identifier: String,
 }
@@ -399,6 +672,14 @@ struct RequestPasswordResetRequest {
 /// Sends a password reset email for a known user account.
 ///
 /// Always returns success to avoid disclosing whether an account exists.
+#[utoipa::path(
+    post,
+    path = "/request-password-reset",
+    tag = "users",
+    request_body = RequestPasswordResetRequest,
+    responses((status = 200, description = "A reset email is sent if the account exists"))
+)]
+#[instrument(skip_all)]
 async fn request_password_reset(
     State(state): State<AppState>,
     Json(payload): Json<RequestPasswordResetRequest>,
@@ -419,13 +700,24 @@ async fn request_password_reset(
     Ok(())
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct ResetPasswordRequest {
     token: String,
     password: String,
 }
 
 /// Resets a user's password using a one-time reset token.
+#[utoipa::path(
+    post,
+    path = "/reset-password",
+    tag = "users",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password reset"),
+        (status = 400, description = "Invalid or expired token, or invalid password"),
+    )
+)]
+#[instrument(skip_all)]
 async fn reset_password(
     State(state): State<AppState>,
     Json(payload): Json<ResetPasswordRequest>,
@@ -448,18 +740,35 @@ async fn reset_password(
     Ok(())
 }
 
-/// Logout the current user by clearing the token cookie.
+/// Logout the current user by revoking their refresh token and clearing
+/// both session cookies.
+#[utoipa::path(
+    post,
+    path = "/logout",
+    tag = "users",
+    responses((status = 200, description = "Session cookies cleared"))
+)]
+#[instrument(skip_all)]
 async fn logout(cookies: CookieJar, State(state): State<AppState>) -> CookieJar {
     debug!("Logout requested");
 
     if let Some(refresh_token_cookie) = cookies.get(REFRESH_COOKIE) {
         let repo = UserRepository::new(&state.db);
-        let _ = repo
+        if let Err(err) = repo
             .revoke_refresh_token(refresh_token_cookie.value())
-            .await;
+            .await
+        {
+            warn!("Failed to revoke refresh token on logout: {err}");
+        }
     }
 
+    clear_session_cookies(cookies)
+}
+
+/// Remove both session cookies. Removal cookies must match the path the
+/// cookies were set with.
+fn clear_session_cookies(cookies: CookieJar) -> CookieJar {
     cookies
-        .remove(Cookie::from(ACCESS_COOKIE))
-        .remove(Cookie::from(REFRESH_COOKIE))
+        .remove(Cookie::build(ACCESS_COOKIE).path("/"))
+        .remove(Cookie::build(REFRESH_COOKIE).path("/"))
 }
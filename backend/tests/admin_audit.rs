@@ -0,0 +1,109 @@
+mod common;
+
+use backend::repositories::UserRepository;
+use common::TestApp;
+
+#[tokio::test]
+async fn create_game_records_creation() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+
+    let game: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id: uuid::Uuid = game["id"].as_str().unwrap().parse().unwrap();
+    let alice_id: uuid::Uuid = alice["id"].as_str().unwrap().parse().unwrap();
+
+    let (creator_id, world_name): (uuid::Uuid, String) =
+        sqlx::query_as("SELECT creator_id, world_name FROM game_creations WHERE game_id = $1")
+            .bind(game_id)
+            .fetch_one(&app.db)
+            .await
+            .unwrap();
+
+    assert_eq!(creator_id, alice_id);
+    assert_eq!(world_name, "default");
+}
+
+#[tokio::test]
+async fn creation_record_outlives_game_and_creator() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let game: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id: uuid::Uuid = game["id"].as_str().unwrap().parse().unwrap();
+    let alice_id: uuid::Uuid = alice["id"].as_str().unwrap().parse().unwrap();
+
+    sqlx::query("DELETE FROM games WHERE id = $1")
+        .bind(game_id)
+        .execute(&app.db)
+        .await
+        .unwrap();
+    assert!(UserRepository::new(&app.db).delete(alice_id).await.unwrap());
+
+    let (creator_id,): (uuid::Uuid,) =
+        sqlx::query_as("SELECT creator_id FROM game_creations WHERE game_id = $1")
+            .bind(game_id)
+            .fetch_one(&app.db)
+            .await
+            .unwrap();
+    assert_eq!(creator_id, alice_id);
+}
+
+#[tokio::test]
+async fn audit_log_returns_creator() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let game: serde_json::Value = app.server.put("/api/games").await.json();
+
+    app.register_admin("admin", "admin@example.com").await;
+    let response = app.server.get("/api/admin/audit/games").await;
+    response.assert_status_ok();
+
+    let page: serde_json::Value = response.json();
+    let records = page["items"].as_array().unwrap();
+    assert_eq!(page["total"], 1);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["game_id"], game["id"]);
+    assert_eq!(records[0]["creator_id"], alice["id"]);
+    assert_eq!(records[0]["world_name"], "default");
+}
+
+#[tokio::test]
+async fn audit_log_paginates() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    app.server.put("/api/games").await;
+    app.server.put("/api/games").await;
+
+    app.register_admin("admin", "admin@example.com").await;
+    let first: serde_json::Value = app
+        .server
+        .get("/api/admin/audit/games")
+        .add_query_param("limit", 1)
+        .await
+        .json();
+    let second: serde_json::Value = app
+        .server
+        .get("/api/admin/audit/games")
+        .add_query_param("limit", 1)
+        .add_query_param("offset", 1)
+        .await
+        .json();
+
+    assert_eq!(first["total"], 2);
+    assert_eq!(first["items"].as_array().unwrap().len(), 1);
+    assert_eq!(second["items"].as_array().unwrap().len(), 1);
+    assert_ne!(first["items"][0]["game_id"], second["items"][0]["game_id"]);
+}
+
+#[tokio::test]
+async fn audit_log_requires_admin() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .get("/api/admin/audit/games")
+        .expect_failure()
+        .await;
+
+    response.assert_status_not_found();
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::TestApp;
+use serde_json::Value;
+
+// ==== OpenAPI ====
+
+#[tokio::test]
+async fn openapi_document_lists_versioned_paths() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/docs").await;
+
+    response.assert_status_ok();
+    let doc: Value = response.json();
+    let paths: Vec<&str> = doc["paths"]
+        .as_object()
+        .expect("paths should be an object")
+        .keys()
+        .map(|path| path.trim_end_matches('/'))
+        .collect();
+    assert!(paths.contains(&"/api/v1/games"));
+    assert!(paths.contains(&"/api/v1/users/register"));
+    assert!(paths.iter().all(|path| path.starts_with("/api/v1/")));
+}
+
+#[tokio::test]
+async fn swagger_ui_is_served() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/docs/ui/").await;
+
+    response.assert_status_ok();
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn v1_health_returns_ok() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/v1/health").await;
+
+    response.assert_status_ok();
+    response.assert_text("OK");
+    assert!(response.maybe_header("deprecation").is_none());
+}
+
+#[tokio::test]
+async fn unversioned_api_is_marked_deprecated() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/health").await;
+
+    response.assert_status_ok();
+    assert_eq!(response.header("deprecation"), "true");
+}
+
+#[tokio::test]
+async fn unversioned_api_errors_are_marked_deprecated() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/users/me").expect_failure().await;
+
+    response.assert_status_unauthorized();
+    assert_eq!(response.header("deprecation"), "true");
+}
+
+#[tokio::test]
+async fn v1_register_and_me_share_session_with_unversioned_api() {
+    let app = TestApp::spawn().await;
+
+    app.server
+        .post("/api/v1/users/register")
+        .json(&json!({
+            "username": "alice",
+            "email": "alice@example.com",
+            "password": "Str0ng!Pass"
+        }))
+        .await
+        .assert_status_ok();
+
+    let v1: serde_json::Value = app.server.get("/api/v1/users/me").await.json();
+    let legacy: serde_json::Value = app.server.get("/api/users/me").await.json();
+    assert_eq!(v1["username"], "alice");
+    assert_eq!(v1, legacy);
+}
@@ -1,7 +1,10 @@
 mod common;
 
+use axum::http::{HeaderName, HeaderValue, header};
 use common::TestApp;
+use jsonwebtoken::{EncodingKey, Header};
 use serde_json::json;
+use uuid::Uuid;
 
 // ==== Registration ====
 
@@ -121,6 +124,28 @@ async fn register_short_username_fails() {
     response.assert_status_bad_request();
 }
 
+#[tokio::test]
+async fn register_username_with_invalid_characters_fails() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .post("/api/users/register")
+        .json(&json!({
+            "username": "al<ice>",
+            "email": "alice@example.com",
+            "password": "Str0ng!Pass"
+        }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_bad_request();
+    assert_eq!(
+        response.text(),
+        "Username may only contain letters, digits, underscores and hyphens."
+    );
+}
+
 // ==== Authentication ====
 
 #[tokio::test]
@@ -195,6 +220,62 @@ async fn authenticate_unknown_user_fails() {
     response.assert_status_not_found();
 }
 
+#[tokio::test]
+async fn authenticate_ignores_username_case() {
+    let app = TestApp::spawn().await;
+
+    app.server
+        .post("/api/users/register")
+        .json(&json!({
+            "username": "Alice",
+            "email": "alice@example.com",
+            "password": "Str0ng!Pass"
+        }))
+        .await;
+
+    for username in ["alice", "ALICE"] {
+        let response = app
+            .server
+            .post("/api/users/auth")
+            .json(&json!({
+                "username": username,
+                "password": "Str0ng!Pass"
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["username"], "Alice");
+    }
+}
+
+#[tokio::test]
+async fn register_username_differing_only_in_case_fails() {
+    let app = TestApp::spawn().await;
+
+    app.server
+        .post("/api/users/register")
+        .json(&json!({
+            "username": "Alice",
+            "email": "alice@example.com",
+            "password": "Str0ng!Pass"
+        }))
+        .await;
+
+    let response = app
+        .server
+        .post("/api/users/register")
+        .json(&json!({
+            "username": "alice",
+            "email": "other@example.com",
+            "password": "Str0ng!Pass"
+        }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_bad_request();
+}
+
 // ==== Me ====
 
 #[tokio::test]
@@ -226,6 +307,29 @@ async fn me_without_auth_returns_unauthorized() {
     response.assert_status_unauthorized();
 }
 
+#[tokio::test]
+async fn me_with_expired_token_returns_unauthorized() {
+    let app = TestApp::spawn_with_config(|config| config.jwt_expiry_seconds = 2).await;
+
+    app.register("alice", "alice@example.com").await;
+    app.server.get("/api/users/me").await.assert_status_ok();
+
+    tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+
+    let response = app.server.get("/api/users/me").expect_failure().await;
+    response.assert_status_unauthorized();
+}
+
+#[tokio::test]
+async fn me_with_one_hour_token_succeeds_immediately() {
+    let app = TestApp::spawn_with_config(|config| config.jwt_expiry_seconds = 60 * 60).await;
+
+    app.register("alice", "alice@example.com").await;
+
+    let response = app.server.get("/api/users/me").await;
+    response.assert_status_ok();
+}
+
 // ==== Refresh ====
 
 #[tokio::test]
@@ -308,3 +412,116 @@ async fn logout_clears_session() {
     let refresh = app.server.post("/api/users/refresh").expect_failure().await;
     refresh.assert_status_unauthorized();
 }
+
+#[tokio::test]
+async fn logout_expires_both_cookies_on_root_path() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app.server.post("/api/users/logout").await;
+    response.assert_status_ok();
+
+    let cookies: Vec<String> = response
+        .headers()
+        .get_all("set-cookie")
+        .iter()
+        .map(|value| value.to_str().unwrap().to_string())
+        .collect();
+    for name in ["token=", "refresh_token="] {
+        let cookie = cookies
+            .iter()
+            .find(|cookie| cookie.starts_with(name))
+            .unwrap_or_else(|| panic!("missing removal cookie for {name}"));
+        assert!(cookie.contains("Path=/"), "{cookie}");
+        assert!(cookie.contains("Max-Age=0"), "{cookie}");
+    }
+}
+
+#[tokio::test]
+async fn logout_without_session_succeeds() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.post("/api/users/logout").await;
+    response.assert_status_ok();
+}
+
+// ==== Forged tokens ====
+
+/// Signs a token with the same claims layout as the backend, without going
+/// through any of its helpers.
+fn forge_token(secret: &str, admin: bool, exp: i64) -> String {
+    let now = chrono::Utc::now().timestamp();
+    let claims = json!({
+        "exp": exp,
+        "iat": now,
+        "sub": Uuid::new_v4(),
+        "admin": admin,
+        "username": "mallory",
+    });
+    jsonwebtoken::encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .unwrap()
+}
+
+fn token_cookie(token: &str) -> (HeaderName, HeaderValue) {
+    (
+        header::COOKIE,
+        HeaderValue::from_str(&format!("token={token}")).unwrap(),
+    )
+}
+
+fn in_one_hour() -> i64 {
+    chrono::Utc::now().timestamp() + 60 * 60
+}
+
+#[tokio::test]
+async fn me_with_token_signed_by_wrong_secret_returns_unauthorized() {
+    let app = TestApp::spawn().await;
+    let token = forge_token("not-the-server-secret-at-all", false, in_one_hour());
+    let (name, value) = token_cookie(&token);
+
+    let response = app
+        .server
+        .get("/api/users/me")
+        .add_header(name, value)
+        .expect_failure()
+        .await;
+
+    response.assert_status_unauthorized();
+}
+
+#[tokio::test]
+async fn me_with_token_expired_in_the_past_returns_unauthorized() {
+    let app = TestApp::spawn().await;
+    let expired = chrono::Utc::now().timestamp() - 60;
+    let token = forge_token(&app.state.config.jwt_secret, false, expired);
+    let (name, value) = token_cookie(&token);
+
+    let response = app
+        .server
+        .get("/api/users/me")
+        .add_header(name, value)
+        .expect_failure()
+        .await;
+
+    response.assert_status_unauthorized();
+}
+
+#[tokio::test]
+async fn forged_admin_token_cannot_list_users() {
+    let app = TestApp::spawn().await;
+    let token = forge_token("not-the-server-secret-at-all", true, in_one_hour());
+    let (name, value) = token_cookie(&token);
+
+    let response = app
+        .server
+        .get("/api/users")
+        .add_header(name, value)
+        .expect_failure()
+        .await;
+
+    response.assert_status_unauthorized();
+}
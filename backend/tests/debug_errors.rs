@@ -0,0 +1,54 @@
+mod common;
+
+use common::TestApp;
+
+/// Register a user, then close the database pool so the next query fails.
+async fn app_with_broken_database(debug_mode: bool) -> TestApp {
+    let app = TestApp::spawn_with_config(|config| config.debug_mode = debug_mode).await;
+    app.register("alice", "alice@example.com").await;
+    app.db.close().await;
+    app
+}
+
+// ==== Debug mode ====
+
+#[tokio::test]
+async fn debug_mode_adds_internal_message() {
+    let app = app_with_broken_database(true).await;
+
+    let response = app
+        .server
+        .get("/api/users/me?debug=1")
+        .expect_failure()
+        .await;
+
+    response.assert_status_internal_server_error();
+    let body: serde_json::Value = response.json();
+    let message = body["internal_message"].as_str().unwrap();
+    assert!(message.starts_with("Database error"), "{message}");
+}
+
+#[tokio::test]
+async fn debug_mode_requires_query_param() {
+    let app = app_with_broken_database(true).await;
+
+    let response = app.server.get("/api/users/me").expect_failure().await;
+
+    response.assert_status_internal_server_error();
+    response.assert_text("Internal server error");
+}
+
+#[tokio::test]
+async fn internal_message_omitted_without_debug_mode() {
+    let app = app_with_broken_database(false).await;
+
+    let response = app
+        .server
+        .get("/api/users/me?debug=1")
+        .expect_failure()
+        .await;
+
+    response.assert_status_internal_server_error();
+    response.assert_text("Internal server error");
+    assert!(!response.text().contains("Database error"));
+}
@@ -1,9 +1,26 @@
 mod common;
 
 use backend::email::MailType;
+use backend::repositories::UserRepository;
 use common::TestApp;
 use serde_json::json;
 
+async fn register_alice(app: &TestApp) -> String {
+    app.server
+        .post("/api/users/register")
+        .json(&json!({
+            "username": "alice",
+            "email": "alice@example.com",
+            "password": "Str0ng!Pass"
+        }))
+        .await;
+
+    match app.mock_email.latest().unwrap().mail_type {
+        MailType::EmailVerification { token } => token,
+        _ => panic!("expected EmailVerification"),
+    }
+}
+
 // ==== Email Verification ====
 
 #[tokio::test]
@@ -177,3 +194,94 @@ async fn resend_verification_without_auth_fails() {
 
     response.assert_status_unauthorized();
 }
+
+// ==== Welcome Email ====
+
+#[tokio::test]
+async fn verify_email_sends_welcome_email() {
+    let app = TestApp::spawn().await;
+    let token = register_alice(&app).await;
+    assert_eq!(app.mock_email.sent().len(), 1);
+
+    app.server
+        .post("/api/users/verify-email")
+        .json(&json!({ "token": token }))
+        .await;
+
+    let sent = app.mock_email.sent();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[1].recipient.email, "alice@example.com");
+    assert!(matches!(sent[1].mail_type, MailType::Welcome));
+}
+
+#[tokio::test]
+async fn verify_email_succeeds_when_welcome_email_fails() {
+    let app = TestApp::spawn().await;
+    let token = register_alice(&app).await;
+    app.mock_email.set_failing(true);
+
+    app.server
+        .post("/api/users/verify-email")
+        .json(&json!({ "token": token }))
+        .await
+        .assert_status_ok();
+
+    let verified: bool =
+        sqlx::query_scalar("SELECT email_verified FROM users WHERE username = 'alice'")
+            .fetch_one(&app.db)
+            .await
+            .unwrap();
+    assert!(verified);
+    assert_eq!(app.mock_email.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn verifying_twice_sends_one_welcome_email() {
+    let app = TestApp::spawn().await;
+    let token = register_alice(&app).await;
+
+    app.server
+        .post("/api/users/verify-email")
+        .json(&json!({ "token": token }))
+        .await;
+    app.server
+        .post("/api/users/verify-email")
+        .json(&json!({ "token": token }))
+        .expect_failure()
+        .await
+        .assert_status_bad_request();
+
+    assert_eq!(app.mock_email.sent().len(), 2);
+}
+
+#[tokio::test]
+async fn second_verification_token_does_not_send_another_welcome_email() {
+    let app = TestApp::spawn().await;
+    let token = register_alice(&app).await;
+    app.server
+        .post("/api/users/verify-email")
+        .json(&json!({ "token": token }))
+        .await;
+
+    // Resending is refused once verified, so issue another token directly
+    let user_id = sqlx::query_scalar("SELECT id FROM users WHERE username = 'alice'")
+        .fetch_one(&app.db)
+        .await
+        .unwrap();
+    let token = UserRepository::new(&app.db)
+        .create_email_verification_token(user_id)
+        .await
+        .unwrap();
+    app.server
+        .post("/api/users/verify-email")
+        .json(&json!({ "token": token }))
+        .await;
+
+    let welcomes = app
+        .mock_email
+        .sent()
+        .into_iter()
+        .filter(|mail| matches!(mail.mail_type, MailType::Welcome))
+        .count();
+    assert_eq!(welcomes, 1);
+}
@@ -0,0 +1,269 @@
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+async fn create_game(app: &TestApp) -> String {
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    created["id"].as_str().unwrap().to_string()
+}
+
+async fn create_invite(app: &TestApp, game_id: &str) -> String {
+    let invite: serde_json::Value = app
+        .server
+        .post(&format!("/api/games/{game_id}/invite"))
+        .await
+        .json();
+    invite["code"].as_str().unwrap().to_string()
+}
+
+// ==== Create Invite ====
+
+#[tokio::test]
+async fn create_invite_returns_code() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let response = app
+        .server
+        .post(&format!("/api/games/{game_id}/invite"))
+        .await;
+
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["game_id"], game_id);
+    assert!(!body["code"].as_str().unwrap().is_empty());
+    assert!(body["used_by"].is_null());
+}
+
+#[tokio::test]
+async fn create_invite_for_missing_game_returns_not_found() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .post(&format!("/api/games/{}/invite", uuid::Uuid::new_v4()))
+        .expect_failure()
+        .await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn create_invite_requires_authentication() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .post(&format!("/api/games/{}/invite", uuid::Uuid::new_v4()))
+        .expect_failure()
+        .await;
+
+    response.assert_status_unauthorized();
+}
+
+#[tokio::test]
+async fn create_invite_by_stranger_is_forbidden() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    app.register("mallory", "mallory@example.com").await;
+    let response = app
+        .server
+        .post(&format!("/api/games/{game_id}/invite"))
+        .expect_failure()
+        .await;
+
+    response.assert_status_forbidden();
+}
+
+#[tokio::test]
+async fn create_invite_by_player_returns_code() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    let code = create_invite(&app, &game_id).await;
+
+    app.register("bob", "bob@example.com").await;
+    app.server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": code }))
+        .await
+        .assert_status_ok();
+
+    app.server
+        .post(&format!("/api/games/{game_id}/invite"))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn create_invite_for_started_game_returns_conflict() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let game = app
+        .state
+        .games
+        .get_game(&game_id.parse().unwrap())
+        .await
+        .unwrap();
+    for name in ["first", "second"] {
+        game.join_player(uuid::Uuid::new_v4(), name.to_string())
+            .await
+            .unwrap();
+    }
+    game.start_game().await.unwrap();
+
+    let response = app
+        .server
+        .post(&format!("/api/games/{game_id}/invite"))
+        .expect_failure()
+        .await;
+
+    response.assert_status(axum::http::StatusCode::CONFLICT);
+}
+
+// ==== Join By Code ====
+
+#[tokio::test]
+async fn join_by_code_adds_player_to_game() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    let code = create_invite(&app, &game_id).await;
+
+    app.register("bob", "bob@example.com").await;
+    let response = app
+        .server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": code }))
+        .await;
+
+    let game: serde_json::Value = response.json();
+    assert_eq!(game["id"], game_id);
+    let players = game["players"].as_array().unwrap();
+    assert_eq!(players.len(), 1);
+    assert_eq!(players[0]["name"], "bob");
+}
+
+#[tokio::test]
+async fn join_by_code_twice_returns_gone() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    let code = create_invite(&app, &game_id).await;
+
+    app.register("bob", "bob@example.com").await;
+    app.server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": code }))
+        .await;
+
+    app.register("carol", "carol@example.com").await;
+    let response = app
+        .server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": code }))
+        .expect_failure()
+        .await;
+
+    response.assert_status(axum::http::StatusCode::GONE);
+}
+
+#[tokio::test]
+async fn failed_join_by_code_keeps_code_usable() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    let first_code = create_invite(&app, &game_id).await;
+    let second_code = create_invite(&app, &game_id).await;
+
+    app.register("bob", "bob@example.com").await;
+    app.server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": first_code }))
+        .await
+        .assert_status_ok();
+
+    // Bob is already in the game, so the second code is not used up
+    app.server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": second_code }))
+        .expect_failure()
+        .await
+        .assert_status_bad_request();
+
+    app.register("carol", "carol@example.com").await;
+    let game: serde_json::Value = app
+        .server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": second_code }))
+        .await
+        .json();
+    assert_eq!(game["players"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn join_by_unknown_code_returns_not_found() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": "NOPE1234" }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn join_by_code_for_started_game_returns_conflict() {
+    let app = TestApp::spawn_http().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    let first_code = create_invite(&app, &game_id).await;
+    let second_code = create_invite(&app, &game_id).await;
+
+    let mut alice_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut alice_ws).await;
+    let _ = alice_ws.receive_json::<serde_json::Value>().await;
+
+    app.register("bob", "bob@example.com").await;
+    app.server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": first_code }))
+        .await;
+
+    alice_ws.send_json(&json!({ "type": "start" })).await;
+    let mut saw_game_started = false;
+    for _ in 0..8 {
+        let event = alice_ws.receive_json::<serde_json::Value>().await;
+        if event["type"] == "game_started" {
+            saw_game_started = true;
+            break;
+        }
+    }
+    assert!(saw_game_started, "expected game_started event on websocket");
+
+    app.register("carol", "carol@example.com").await;
+    let response = app
+        .server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": second_code }))
+        .expect_failure()
+        .await;
+
+    response.assert_status(axum::http::StatusCode::CONFLICT);
+}
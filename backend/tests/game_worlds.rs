@@ -0,0 +1,183 @@
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn list_worlds_returns_all_presets() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/games/worlds").await;
+    response.assert_status_ok();
+
+    let worlds: Vec<serde_json::Value> = response.json();
+    let mut names: Vec<&str> = worlds
+        .iter()
+        .map(|world| world["name"].as_str().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["default", "large", "small"]);
+
+    for world in &worlds {
+        assert!(world["min_players"].as_u64().unwrap() <= world["max_players"].as_u64().unwrap());
+    }
+}
+
+#[tokio::test]
+async fn create_game_with_small_world_uses_small_map() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let worlds: Vec<serde_json::Value> = app.server.get("/api/games/worlds").await.json();
+    let small = worlds
+        .iter()
+        .find(|world| world["name"] == "small")
+        .unwrap();
+
+    let response = app
+        .server
+        .put("/api/games")
+        .json(&json!({ "world_name": "small" }))
+        .await;
+    response.assert_status_ok();
+
+    let game: serde_json::Value = response.json();
+    let areas = game["world"]["areas"].as_object().unwrap();
+    assert_eq!(areas.len() as u64, small["area_count"].as_u64().unwrap());
+    assert!(areas.values().any(|area| area["name"] == "Forest of Doom"));
+}
+
+#[tokio::test]
+async fn create_game_without_body_uses_default_world() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let worlds: Vec<serde_json::Value> = app.server.get("/api/games/worlds").await.json();
+    let default = worlds
+        .iter()
+        .find(|world| world["name"] == "default")
+        .unwrap();
+
+    let game: serde_json::Value = app.server.put("/api/games").await.json();
+    let areas = game["world"]["areas"].as_object().unwrap();
+    assert_eq!(areas.len() as u64, default["area_count"].as_u64().unwrap());
+}
+
+#[tokio::test]
+async fn create_game_with_unknown_world_fails() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .put("/api/games")
+        .json(&json!({ "world_name": "atlantis" }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn create_game_without_body_uses_default_config() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let game: serde_json::Value = app.server.put("/api/games").await.json();
+
+    let config: ::common::GameConfig = serde_json::from_value(game["config"].clone()).unwrap();
+    assert_eq!(config, ::common::GameConfig::default());
+}
+
+#[tokio::test]
+async fn create_game_with_rules_overrides_defaults() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .put("/api/games")
+        .json(&json!({
+            "config": { "max_dice_per_area": 4, "min_players": 3, "idle_timeout_seconds": 60 }
+        }))
+        .await;
+    response.assert_status_ok();
+
+    let created: serde_json::Value = response.json();
+    assert_eq!(created["config"]["max_dice_per_area"], 4);
+    assert_eq!(created["config"]["min_players"], 3);
+    assert_eq!(created["config"]["idle_timeout_seconds"], 60);
+}
+
+#[tokio::test]
+async fn create_game_with_max_players_limits_the_game() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .put("/api/games")
+        .json(&json!({ "config": { "max_players": 3 } }))
+        .await;
+    response.assert_status_ok();
+
+    let created: serde_json::Value = response.json();
+    assert_eq!(created["config"]["max_players"], 3);
+    // Settings left out of the body keep their defaults
+    assert_eq!(created["config"]["allow_spectators"], true);
+
+    let game_id = created["id"].as_str().unwrap().parse().unwrap();
+    let game = app.state.games.get_game(&game_id).await.unwrap();
+    for i in 0..3 {
+        game.join_player(Uuid::new_v4(), format!("player{i}"))
+            .await
+            .unwrap();
+    }
+    assert!(game.is_full().await);
+    assert!(
+        game.join_player(Uuid::new_v4(), "player3".to_string())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn create_game_with_invalid_config_fails() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    for config in [
+        json!({ "turn_time_limit": { "secs": 0, "nanos": 0 } }),
+        json!({ "min_players": 4, "max_players": 3 }),
+        json!({ "max_players": 100 }),
+        json!({ "max_dice_per_area": 0 }),
+        json!({ "idle_timeout_seconds": 0 }),
+        json!({ "reconnect_grace_period_seconds": u64::MAX }),
+    ] {
+        let response = app
+            .server
+            .put("/api/games")
+            .json(&json!({ "config": config }))
+            .expect_failure()
+            .await;
+
+        response.assert_status_bad_request();
+    }
+}
+
+#[tokio::test]
+async fn create_game_with_turn_time_limit_succeeds() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .put("/api/games")
+        .json(&json!({ "config": { "turn_time_limit": { "secs": 30, "nanos": 0 } } }))
+        .await;
+    response.assert_status_ok();
+
+    let created: serde_json::Value = response.json();
+    assert_eq!(created["config"]["turn_time_limit"]["secs"], 30);
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use ::common::{GameConfig, GameState, World};
+use backend::games::{Creator, Game, Games};
+use backend::prelude::DEFAULT_GAME_BROADCAST_CHANNEL_SIZE;
+use common::TestApp;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+async fn new_game(app: &TestApp) -> Game {
+    let alice = app.register("alice", "alice@example.com").await;
+    let creator = Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    };
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let world = World::from_str_checked(include_str!("../worlds/default.world")).unwrap();
+    games
+        .create_game(world, GameConfig::default(), creator)
+        .await
+        .unwrap()
+}
+
+/// Let the clock move on so a refreshed activity timestamp is observable.
+async fn tick() -> Instant {
+    let before = Instant::now();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    before
+}
+
+#[tokio::test]
+async fn joining_refreshes_activity() {
+    let app = TestApp::spawn().await;
+    let game = new_game(&app).await;
+
+    let before = tick().await;
+    game.join_player(Uuid::new_v4(), "first".to_string())
+        .await
+        .unwrap();
+    assert!(game.last_activity() > before);
+
+    let before = tick().await;
+    game.join_spectator(Uuid::new_v4(), "watcher".to_string())
+        .await
+        .unwrap();
+    assert!(game.last_activity() > before);
+}
+
+#[tokio::test]
+async fn starting_and_ending_turns_refresh_activity() {
+    let app = TestApp::spawn().await;
+    let game = new_game(&app).await;
+    game.join_player(Uuid::new_v4(), "first".to_string())
+        .await
+        .unwrap();
+    game.join_player(Uuid::new_v4(), "second".to_string())
+        .await
+        .unwrap();
+
+    let before = tick().await;
+    game.start_game().await.unwrap();
+    assert!(game.last_activity() > before);
+
+    let snapshot = game.snapshot().await;
+    let GameState::InProgress { turn } = snapshot.state else {
+        panic!("expected game in progress, got {:?}", snapshot.state);
+    };
+    let before = tick().await;
+    game.end_turn(snapshot.players[turn].id).await.unwrap();
+    assert!(game.last_activity() > before);
+}
+
+#[tokio::test]
+async fn rejected_command_does_not_refresh_activity() {
+    let app = TestApp::spawn().await;
+    let game = new_game(&app).await;
+
+    tick().await;
+    let last_activity = game.last_activity();
+    assert!(game.start_game().await.is_err());
+
+    assert_eq!(game.last_activity(), last_activity);
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use ::common::{GameConfig, World};
+use backend::games::{Creator, Game, GameEvent, Games};
+use common::TestApp;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+async fn game_with_channel_size(app: &TestApp, channel_size: usize) -> Game {
+    let alice = app.register("alice", "alice@example.com").await;
+    let creator = Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    };
+
+    let games = Games::new(app.db.clone(), channel_size);
+    let world = World::from_str_checked(include_str!("../worlds/default.world")).unwrap();
+    games
+        .create_game(world, GameConfig::default(), creator)
+        .await
+        .unwrap()
+}
+
+async fn join_three_players(game: &Game) {
+    for name in ["first", "second", "third"] {
+        game.join_player(Uuid::new_v4(), name.to_string())
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn small_channel_lags_slow_subscriber() {
+    let app = TestApp::spawn().await;
+    let game = game_with_channel_size(&app, 2).await;
+    let mut events = game.subscribe_events();
+
+    join_three_players(&game).await;
+
+    // Each join sends a join event and an update, six in all, so the four
+    // oldest no longer fit in the channel
+    assert!(matches!(events.recv().await, Err(RecvError::Lagged(4))));
+}
+
+#[tokio::test]
+async fn large_channel_does_not_lag() {
+    let app = TestApp::spawn().await;
+    let game = game_with_channel_size(&app, 128).await;
+    let mut events = game.subscribe_events();
+
+    join_three_players(&game).await;
+
+    for _ in 0..3 {
+        assert!(events.recv().await.is_ok());
+    }
+}
+
+#[tokio::test]
+async fn player_join_is_broadcast_as_diff() {
+    let app = TestApp::spawn().await;
+    let game = game_with_channel_size(&app, 16).await;
+    let before = game.snapshot().await;
+    let mut events = game.subscribe_events();
+
+    game.join_player(Uuid::new_v4(), "first".to_string())
+        .await
+        .unwrap();
+
+    let mut client = before;
+    loop {
+        match events.recv().await.unwrap() {
+            GameEvent::Diff { diff } => {
+                assert!(diff.areas.is_empty());
+                client.apply_diff(diff);
+                break;
+            }
+            GameEvent::Snapshot { .. } => panic!("expected a diff, got a snapshot"),
+            _ => {}
+        }
+    }
+
+    assert_eq!(client.players, game.snapshot().await.players);
+}
+
+#[tokio::test]
+async fn change_between_subscribe_and_snapshot_is_not_lost() {
+    let app = TestApp::spawn().await;
+    let game = game_with_channel_size(&app, 16).await;
+    let mut events = game.subscribe_events();
+
+    // Published after subscribing but before the snapshot is taken, so the
+    // snapshot already holds the change its diff carries
+    game.join_player(Uuid::new_v4(), "first".to_string())
+        .await
+        .unwrap();
+    let mut client = game.snapshot().await;
+    game.join_player(Uuid::new_v4(), "second".to_string())
+        .await
+        .unwrap();
+
+    while let Ok(event) = events.try_recv() {
+        if let GameEvent::Diff { diff } = event {
+            client.apply_diff(diff);
+        }
+    }
+
+    assert_eq!(client.players, game.snapshot().await.players);
+    assert_eq!(client.players.len(), 2);
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use ::common::GameState;
+use backend::prelude::Error;
+use backend::repositories::GameRepository;
+use common::TestApp;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::timeout;
+use uuid::Uuid;
+
+async fn create_game(app: &TestApp) -> Uuid {
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    created["id"].as_str().unwrap().parse().unwrap()
+}
+
+async fn start_game(app: &TestApp, game_id: Uuid) {
+    let game = app.state.games.get_game(&game_id).await.unwrap();
+    for name in ["first", "second"] {
+        game.join_player(Uuid::new_v4(), name.to_string())
+            .await
+            .unwrap();
+    }
+    game.start_game().await.unwrap();
+}
+
+#[tokio::test]
+async fn creator_can_delete_waiting_game() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    app.server
+        .delete(&format!("/api/games/{game_id}"))
+        .await
+        .assert_status_ok();
+
+    assert!(app.state.games.get_game(&game_id).await.is_none());
+    app.server
+        .get(&format!("/api/games/{game_id}"))
+        .expect_failure()
+        .await
+        .assert_status_not_found();
+
+    // The stored game is finished, so it is not restored on restart
+    let snapshot = GameRepository::new(&app.db)
+        .load_snapshot(game_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(snapshot.state, GameState::Finished);
+}
+
+#[tokio::test]
+async fn non_creator_cannot_delete_game() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    app.register("bob", "bob@example.com").await;
+    app.server
+        .delete(&format!("/api/games/{game_id}"))
+        .expect_failure()
+        .await
+        .assert_status_forbidden();
+
+    assert!(app.state.games.get_game(&game_id).await.is_some());
+}
+
+#[tokio::test]
+async fn creator_cannot_delete_game_in_progress() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    start_game(&app, game_id).await;
+
+    app.server
+        .delete(&format!("/api/games/{game_id}"))
+        .expect_failure()
+        .await
+        .assert_status_forbidden();
+
+    assert!(app.state.games.get_game(&game_id).await.is_some());
+}
+
+#[tokio::test]
+async fn cancel_unless_in_progress_leaves_started_game_running() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    start_game(&app, game_id).await;
+    let game = app.state.games.get_game(&game_id).await.unwrap();
+
+    let result = game.cancel_unless_in_progress("cancelled").await;
+
+    assert!(matches!(result, Err(Error::Forbidden(_))));
+    assert!(matches!(
+        game.snapshot().await.state,
+        GameState::InProgress { .. }
+    ));
+}
+
+#[tokio::test]
+async fn admin_can_delete_game_in_progress() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    start_game(&app, game_id).await;
+
+    app.register_admin("admin", "admin@example.com").await;
+    app.server
+        .delete(&format!("/api/games/{game_id}"))
+        .await
+        .assert_status_ok();
+
+    assert!(app.state.games.get_game(&game_id).await.is_none());
+}
+
+#[tokio::test]
+async fn delete_game_requires_authentication() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    app.server.post("/api/users/logout").await;
+
+    app.server
+        .delete(&format!("/api/games/{game_id}"))
+        .expect_failure()
+        .await
+        .assert_status_unauthorized();
+}
+
+#[tokio::test]
+async fn delete_missing_game_returns_not_found() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    app.server
+        .delete(&format!("/api/games/{}", Uuid::new_v4()))
+        .expect_failure()
+        .await
+        .assert_status_not_found();
+}
+
+#[tokio::test]
+async fn deleting_game_notifies_connected_sockets() {
+    let app = TestApp::spawn_http().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let mut ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut ws).await;
+    let _ = ws.receive_json::<serde_json::Value>().await;
+
+    app.server
+        .delete(&format!("/api/games/{game_id}"))
+        .await
+        .assert_status_ok();
+
+    let finished = timeout(Duration::from_secs(1), async {
+        loop {
+            let event = ws.receive_json::<serde_json::Value>().await;
+            if event["type"] == "finished" {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("expected finished event after deleting the game");
+    assert_eq!(finished["reason"], "Game was cancelled by the creator");
+    assert_eq!(finished["winner_id"], json!(null));
+}
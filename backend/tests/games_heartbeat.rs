@@ -0,0 +1,80 @@
+mod common;
+
+use axum_test::{TestWebSocket, WsMessage};
+use common::TestApp;
+use std::time::Duration;
+use tokio::time::{Instant, timeout};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+async fn connect(app: &TestApp) -> TestWebSocket {
+    app.register("alice", "alice@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    let mut ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut ws).await;
+    let _ = ws.receive_json::<serde_json::Value>().await;
+    ws
+}
+
+async fn spawn_app() -> TestApp {
+    TestApp::spawn_http_with_config(|config| {
+        config.ws_heartbeat_interval_seconds = HEARTBEAT_INTERVAL.as_secs();
+    })
+    .await
+}
+
+#[tokio::test]
+async fn unresponsive_client_is_disconnected() {
+    let app = spawn_app().await;
+    let mut ws = connect(&app).await;
+
+    // Read nothing, so no pong is ever sent back
+    tokio::time::sleep(2 * HEARTBEAT_INTERVAL).await;
+
+    let closed = timeout(Duration::from_millis(500), async {
+        loop {
+            if let WsMessage::Close(frame) = ws.receive_message().await {
+                return frame;
+            }
+        }
+    })
+    .await
+    .expect("expected close frame after missed heartbeat");
+
+    assert_eq!(&*closed.unwrap().reason, "heartbeat timeout");
+}
+
+#[tokio::test]
+async fn responsive_client_stays_connected() {
+    let app = spawn_app().await;
+    let mut ws = connect(&app).await;
+
+    let mut pings = 0;
+    let deadline = Instant::now() + 3 * HEARTBEAT_INTERVAL;
+    while let Ok(message) = timeout(
+        deadline.saturating_duration_since(Instant::now()),
+        ws.receive_message(),
+    )
+    .await
+    {
+        match message {
+            WsMessage::Ping(payload) => {
+                assert_eq!(&payload[..], b"heartbeat");
+                pings += 1;
+                ws.send_message(WsMessage::Pong(payload)).await;
+            }
+            WsMessage::Close(_) => panic!("responsive client was disconnected"),
+            _ => {}
+        }
+    }
+
+    assert!(pings >= 2, "expected regular heartbeat pings, got {pings}");
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+/// Create a game as the signed-in user and return its ID and an invite code.
+async fn create_game_with_invite(app: &TestApp) -> (String, String) {
+    let game: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = game["id"].as_str().unwrap().to_string();
+    let invite: serde_json::Value = app
+        .server
+        .post(&format!("/api/games/{game_id}/invite"))
+        .await
+        .json();
+    (game_id, invite["code"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn player_me_lists_joined_games() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let (first_id, first_code) = create_game_with_invite(&app).await;
+    let (second_id, second_code) = create_game_with_invite(&app).await;
+    create_game_with_invite(&app).await;
+
+    app.register("bob", "bob@example.com").await;
+    for code in [first_code, second_code] {
+        app.server
+            .post("/api/games/join-by-code")
+            .json(&json!({ "code": code }))
+            .await;
+    }
+
+    let page: serde_json::Value = app
+        .server
+        .get("/api/games")
+        .add_query_param("player_me", true)
+        .await
+        .json();
+
+    let mut ids: Vec<&str> = page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|game| game["id"].as_str().unwrap())
+        .collect();
+    ids.sort();
+    let mut expected = vec![first_id.as_str(), second_id.as_str()];
+    expected.sort();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn player_me_without_games_is_empty() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    app.server.put("/api/games").await;
+
+    app.register("bob", "bob@example.com").await;
+    let page: serde_json::Value = app
+        .server
+        .get("/api/games")
+        .add_query_param("player_me", true)
+        .await
+        .json();
+
+    assert_eq!(page["total"], 0);
+    assert!(page["items"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn player_me_requires_authentication() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .get("/api/games")
+        .add_query_param("player_me", true)
+        .expect_failure()
+        .await;
+
+    response.assert_status_unauthorized();
+}
+
+#[tokio::test]
+async fn list_games_without_filter_does_not_require_authentication() {
+    let app = TestApp::spawn().await;
+
+    app.server.get("/api/games").await.assert_status_ok();
+}
+
+#[tokio::test]
+async fn game_list_previews_players_with_hex_colors() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let (game_id, first_code) = create_game_with_invite(&app).await;
+    let second: serde_json::Value = app
+        .server
+        .post(&format!("/api/games/{game_id}/invite"))
+        .await
+        .json();
+    let second_code = second["code"].as_str().unwrap().to_string();
+
+    for (name, code) in [("bob", first_code), ("carol", second_code)] {
+        app.register(name, &format!("{name}@example.com")).await;
+        app.server
+            .post("/api/games/join-by-code")
+            .json(&json!({ "code": code }))
+            .await
+            .assert_status_ok();
+    }
+
+    let page: serde_json::Value = app.server.get("/api/games").await.json();
+    let game = &page["items"][0];
+    assert_eq!(game["player_count"], 2);
+    assert_eq!(
+        game["players"],
+        json!([
+            { "name": "bob", "color": "#FF0000" },
+            { "name": "carol", "color": "#00CC44" },
+        ])
+    );
+}
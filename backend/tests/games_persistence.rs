@@ -0,0 +1,499 @@
+mod common;
+
+use ::common::{AttackResult, GameConfig, GameState, MoveRecord, World};
+use backend::games::{Creator, Games};
+use backend::prelude::DEFAULT_GAME_BROADCAST_CHANNEL_SIZE;
+use backend::repositories::{GameRepository, UserRepository};
+use common::TestApp;
+use std::time::Duration;
+use uuid::Uuid;
+
+async fn create_game(app: &TestApp) -> Uuid {
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    created["id"].as_str().unwrap().parse().unwrap()
+}
+
+/// The moves on the signed-in user's first page of a game's history.
+async fn history(app: &TestApp, game_id: Uuid) -> Vec<MoveRecord> {
+    let page: serde_json::Value = app
+        .server
+        .get(&format!("/api/games/{game_id}/history"))
+        .await
+        .json();
+    serde_json::from_value(page["items"].clone()).unwrap()
+}
+
+// ==== Snapshots ====
+
+#[tokio::test]
+async fn created_game_snapshot_is_persisted() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let repo = GameRepository::new(&app.db);
+    let snapshot = repo.load_snapshot(game_id).await.unwrap().unwrap();
+
+    assert_eq!(snapshot.id, game_id);
+    assert_eq!(snapshot.state, GameState::WaitingForPlayers);
+    assert!(!snapshot.world.areas.is_empty());
+}
+
+#[tokio::test]
+async fn load_snapshot_for_unknown_game_returns_none() {
+    let app = TestApp::spawn().await;
+
+    let repo = GameRepository::new(&app.db);
+    assert!(repo.load_snapshot(Uuid::new_v4()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn game_creator_must_be_a_user() {
+    let app = TestApp::spawn().await;
+    let creator = Creator {
+        id: Uuid::new_v4(),
+        name: "ghost".to_string(),
+    };
+    let world = World::from_str_checked("0,0\n0,1").unwrap();
+    let snapshot = ::common::Game::new(world);
+
+    let repo = GameRepository::new(&app.db);
+    assert!(repo.create(&creator, &snapshot).await.is_err());
+    assert!(repo.load_snapshot(snapshot.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn creator_of_stored_game_cannot_be_deleted() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let alice_id = alice["id"].as_str().unwrap().parse().unwrap();
+    let game_id = create_game(&app).await;
+
+    assert!(UserRepository::new(&app.db).delete(alice_id).await.is_err());
+    let repo = GameRepository::new(&app.db);
+    assert!(repo.load_snapshot(game_id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn dice_seed_is_stored_outside_the_snapshot() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let creator = Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    };
+    let config = GameConfig {
+        seed: Some(7),
+        ..GameConfig::default()
+    };
+    let world = World::from_str_checked("0,0\n0,1").unwrap();
+    let game = app
+        .state
+        .games
+        .create_game(world, config, creator)
+        .await
+        .unwrap();
+
+    let has_seed: bool = sqlx::query_scalar("SELECT snapshot ? 'seed' FROM games WHERE id = $1")
+        .bind(game.id)
+        .fetch_one(&app.db)
+        .await
+        .unwrap();
+    assert!(!has_seed);
+
+    let repo = GameRepository::new(&app.db);
+    assert_eq!(repo.load_snapshot(game.id).await.unwrap().unwrap().seed, 7);
+}
+
+#[tokio::test]
+async fn create_game_ignores_seed_from_request() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let created: serde_json::Value = app
+        .server
+        .put("/api/games")
+        .json(&serde_json::json!({ "config": { "seed": 1 } }))
+        .await
+        .json();
+    assert!(created.get("seed").is_none());
+    assert!(created["config"].get("seed").is_none());
+
+    let game_id = created["id"].as_str().unwrap().parse().unwrap();
+    let repo = GameRepository::new(&app.db);
+    assert_ne!(repo.load_snapshot(game_id).await.unwrap().unwrap().seed, 1);
+}
+
+#[tokio::test]
+async fn restored_snapshot_continues_dice_where_they_left_off() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    let game = app.state.games.get_game(&game_id).await.unwrap();
+    for name in ["first", "second"] {
+        game.join_player(Uuid::new_v4(), name.to_string())
+            .await
+            .unwrap();
+    }
+    game.start_game().await.unwrap();
+
+    let snapshot = game.snapshot().await;
+    assert!(snapshot.dice_draws() > 0);
+    let repo = GameRepository::new(&app.db);
+    repo.save_snapshot(game_id, &snapshot).await.unwrap();
+
+    let restored = repo.load_snapshot(game_id).await.unwrap().unwrap();
+    assert_eq!(restored.seed, snapshot.seed);
+    assert_eq!(restored.dice_draws(), snapshot.dice_draws());
+}
+
+#[tokio::test]
+async fn joined_player_is_persisted_in_snapshot() {
+    let app = TestApp::spawn_http().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let mut ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut ws).await;
+    let _ = ws.receive_json::<serde_json::Value>().await;
+
+    let repo = GameRepository::new(&app.db);
+    let mut players = 0;
+    for _ in 0..20 {
+        players = repo
+            .load_snapshot(game_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .players
+            .len();
+        if players == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    assert_eq!(players, 1, "expected joined player in stored snapshot");
+}
+
+// ==== Moves ====
+
+#[tokio::test]
+async fn moves_are_returned_in_order() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let player_id = Uuid::new_v4();
+    let attack = MoveRecord::Attack {
+        turn: 0,
+        player_id,
+        from_id: Uuid::new_v4(),
+        to_id: Uuid::new_v4(),
+        result: AttackResult {
+            attack_roll: 14,
+            defence_roll: 3,
+            captured: true,
+            attacker_remaining_dice: 1,
+            defender_remaining_dice: 5,
+        },
+    };
+    let end_turn = MoveRecord::EndTurn {
+        turn: 0,
+        player_id,
+        bonus_dice: 2,
+    };
+
+    let repo = GameRepository::new(&app.db);
+    repo.save_move(game_id, 0, &attack).await.unwrap();
+    repo.save_move(game_id, 0, &end_turn).await.unwrap();
+
+    let moves = repo.moves_for_game(game_id).await.unwrap();
+    assert_eq!(moves, vec![attack, end_turn]);
+}
+
+#[tokio::test]
+async fn moves_are_ordered_by_turn_number() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let first = MoveRecord::EndTurn {
+        turn: 0,
+        player_id: Uuid::new_v4(),
+        bonus_dice: 0,
+    };
+    let second = MoveRecord::EndTurn {
+        turn: 1,
+        player_id: Uuid::new_v4(),
+        bonus_dice: 0,
+    };
+
+    let repo = GameRepository::new(&app.db);
+    repo.save_move(game_id, 1, &second).await.unwrap();
+    repo.save_move(game_id, 0, &first).await.unwrap();
+
+    let moves = repo.moves_for_game(game_id).await.unwrap();
+    assert_eq!(moves, vec![first, second]);
+}
+
+#[tokio::test]
+async fn moves_for_game_without_moves_is_empty() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let repo = GameRepository::new(&app.db);
+    assert!(repo.moves_for_game(game_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn history_endpoint_returns_played_moves() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let game = app.state.games.get_game(&game_id).await.unwrap();
+    for name in ["first", "second"] {
+        game.join_player(Uuid::new_v4(), name.to_string())
+            .await
+            .unwrap();
+    }
+    game.start_game().await.unwrap();
+    let GameState::InProgress { turn } = game.snapshot().await.state else {
+        panic!("expected game in progress");
+    };
+    let player_id = game.snapshot().await.players[turn].id;
+    game.end_turn(player_id).await.unwrap();
+
+    let history = history(&app, game_id).await;
+    assert_eq!(history.len(), 1);
+    assert!(matches!(
+        history[0],
+        MoveRecord::EndTurn { turn: 0, player_id: id, .. } if id == player_id
+    ));
+}
+
+#[tokio::test]
+async fn history_of_new_game_is_empty() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    assert!(history(&app, game_id).await.is_empty());
+}
+
+#[tokio::test]
+async fn history_of_new_game_is_empty_and_public() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    app.server.post("/api/users/logout").await;
+
+    let response = app
+        .server
+        .get(&format!("/api/games/{game_id}/history"))
+        .await;
+    response.assert_status_ok();
+
+    let page: serde_json::Value = response.json();
+    assert_eq!(page["total"], 0);
+    assert!(page["items"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn history_is_paginated() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let game = app.state.games.get_game(&game_id).await.unwrap();
+    for name in ["first", "second"] {
+        game.join_player(Uuid::new_v4(), name.to_string())
+            .await
+            .unwrap();
+    }
+    game.start_game().await.unwrap();
+    for _ in 0..2 {
+        let GameState::InProgress { turn } = game.snapshot().await.state else {
+            panic!("expected game in progress");
+        };
+        let player_id = game.snapshot().await.players[turn].id;
+        game.end_turn(player_id).await.unwrap();
+    }
+
+    let page: serde_json::Value = app
+        .server
+        .get(&format!("/api/games/{game_id}/history"))
+        .add_query_param("limit", 1)
+        .add_query_param("offset", 1)
+        .await
+        .json();
+    assert_eq!(page["total"], 2);
+    assert_eq!(
+        (page["offset"].as_u64(), page["limit"].as_u64()),
+        (Some(1), Some(1))
+    );
+    let items: Vec<MoveRecord> = serde_json::from_value(page["items"].clone()).unwrap();
+    assert!(matches!(items[..], [MoveRecord::EndTurn { turn: 1, .. }]));
+}
+
+#[tokio::test]
+async fn history_of_fog_of_war_game_is_withheld_until_finished() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let creator = Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    };
+    let config = GameConfig {
+        fog_of_war: true,
+        ..GameConfig::default()
+    };
+    let world = World::from_str_checked("0,0\n0,1").unwrap();
+    let game = app
+        .state
+        .games
+        .create_game(world, config, creator)
+        .await
+        .unwrap();
+    let players = [Uuid::new_v4(), Uuid::new_v4()];
+    for (i, id) in players.iter().enumerate() {
+        game.join_player(*id, format!("player {i}")).await.unwrap();
+    }
+    game.start_game().await.unwrap();
+
+    app.server
+        .get(&format!("/api/games/{}/history", game.id))
+        .expect_failure()
+        .await
+        .assert_status_forbidden();
+
+    game.resign(players[0]).await.unwrap();
+    assert_eq!(game.snapshot().await.state, GameState::Finished);
+    app.server
+        .get(&format!("/api/games/{}/history", game.id))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn history_endpoint_returns_attacks() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let creator = Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    };
+
+    // Starting dice are random, so find a seed where the first player can
+    // attack
+    for seed in 0..50 {
+        let config = GameConfig {
+            seed: Some(seed),
+            ..GameConfig::default()
+        };
+        let world = World::from_str_checked("0,0\n0,1").unwrap();
+        let game = app
+            .state
+            .games
+            .create_game(world, config, creator.clone())
+            .await
+            .unwrap();
+        for name in ["first", "second"] {
+            game.join_player(Uuid::new_v4(), name.to_string())
+                .await
+                .unwrap();
+        }
+        game.start_game().await.unwrap();
+
+        let snapshot = game.snapshot().await;
+        let GameState::InProgress { turn } = snapshot.state else {
+            panic!("expected game in progress");
+        };
+        let player_id = snapshot.players[turn].id;
+        let (from, to): (Vec<_>, Vec<_>) = snapshot
+            .world
+            .areas
+            .values()
+            .partition(|area| area.is_owned_by(player_id));
+        if from[0].stack.is_single() {
+            continue;
+        }
+        let (from_id, to_id) = (from[0].id, to[0].id);
+        game.attack(from_id, to_id, player_id).await.unwrap();
+
+        let history = history(&app, game.id).await;
+        assert_eq!(history.len(), 1);
+        assert!(matches!(
+            history[0],
+            MoveRecord::Attack { turn: 0, player_id: id, from_id: from, to_id: to, .. }
+                if id == player_id && from == from_id && to == to_id
+        ));
+        return;
+    }
+    panic!("no seed let the first player attack");
+}
+
+#[tokio::test]
+async fn history_of_removed_game_is_served_from_database() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+    assert!(app.state.games.remove_game(game_id).await);
+
+    let history = history(&app, game_id).await;
+    assert!(history.is_empty());
+}
+
+#[tokio::test]
+async fn history_of_unknown_game_is_not_found() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    app.server
+        .get(&format!("/api/games/{}/history", Uuid::new_v4()))
+        .expect_failure()
+        .await
+        .assert_status_not_found();
+}
+
+// ==== Restore ====
+
+#[tokio::test]
+async fn load_from_db_restores_unfinished_games() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let games = Games::load_from_db(&app.db, DEFAULT_GAME_BROADCAST_CHANNEL_SIZE)
+        .await
+        .unwrap();
+
+    let game = games.get_game(&game_id).await.unwrap();
+    assert_eq!(game.creator.name, "alice");
+    assert_eq!(game.snapshot().await.id, game_id);
+}
+
+#[tokio::test]
+async fn load_from_db_skips_finished_games() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game_id = create_game(&app).await;
+
+    let repo = GameRepository::new(&app.db);
+    let mut snapshot = repo.load_snapshot(game_id).await.unwrap().unwrap();
+    snapshot.state = GameState::Finished;
+    repo.save_snapshot(game_id, &snapshot).await.unwrap();
+
+    let games = Games::load_from_db(&app.db, DEFAULT_GAME_BROADCAST_CHANNEL_SIZE)
+        .await
+        .unwrap();
+    assert!(games.get_game(&game_id).await.is_none());
+}
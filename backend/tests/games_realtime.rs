@@ -1,5 +1,8 @@
 mod common;
 
+use ::common::{GameConfig, Stack, Tile, World};
+use axum_test::WsMessage;
+use backend::games::{Creator, DEFAULT_WORLD, GameCommand, GameEvent};
 use common::TestApp;
 use serde_json::json;
 use std::time::Duration;
@@ -88,6 +91,54 @@ async fn games_stream_emits_empty_list_when_no_games_exist() {
     assert!(body.contains("data: []"), "unexpected SSE body: {body}");
 }
 
+#[tokio::test]
+async fn games_stream_throttles_bursts_and_sends_latest_list() {
+    let app = TestApp::spawn_http().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let creator = Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    };
+
+    let url = app.server.server_url("/api/games/stream").unwrap();
+    let mut response = reqwest::Client::new().get(url).send().await.unwrap();
+
+    for _ in 0..20 {
+        let world = app.state.worlds.load(DEFAULT_WORLD).unwrap();
+        app.state
+            .games
+            .create_game(world, GameConfig::default(), creator.clone())
+            .await
+            .unwrap();
+    }
+
+    let mut body = String::new();
+    let _ = timeout(Duration::from_millis(500), async {
+        while let Some(chunk) = response.chunk().await.unwrap() {
+            body.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    })
+    .await;
+    let burst_events = body.matches("event: games").count();
+    assert!(burst_events <= 5, "received {burst_events} events: {body}");
+
+    let last_game_count = |body: &str| {
+        body.split("\n\n")
+            .filter_map(|event| event.lines().find_map(|line| line.strip_prefix("data: ")))
+            .last()
+            .and_then(|data| serde_json::from_str::<Vec<serde_json::Value>>(data).ok())
+            .map_or(0, |games| games.len())
+    };
+    timeout(Duration::from_secs(3), async {
+        while last_game_count(&body) < 20 {
+            let chunk = response.chunk().await.unwrap().unwrap();
+            body.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    })
+    .await
+    .expect("timed out waiting for the full game list");
+}
+
 // ==== Active game websocket ====
 
 #[tokio::test]
@@ -132,6 +183,7 @@ async fn game_websocket_allows_commands_and_emits_events() {
         .await
         .into_websocket()
         .await;
+    common::expect_protocol_version(&mut alice_ws).await;
 
     let alice_initial = alice_ws.receive_json::<serde_json::Value>().await;
     assert_eq!(alice_initial["type"], "snapshot");
@@ -145,6 +197,7 @@ async fn game_websocket_allows_commands_and_emits_events() {
         .await
         .into_websocket()
         .await;
+    common::expect_protocol_version(&mut bob_ws).await;
 
     let bob_initial = bob_ws.receive_json::<serde_json::Value>().await;
     assert_eq!(bob_initial["type"], "snapshot");
@@ -183,6 +236,112 @@ async fn game_websocket_allows_commands_and_emits_events() {
     );
 }
 
+/// Read events from `ws` until one of type `event_type` arrives.
+async fn receive_event_of_type(
+    ws: &mut axum_test::TestWebSocket,
+    event_type: &str,
+) -> serde_json::Value {
+    timeout(Duration::from_secs(3), async {
+        loop {
+            let event = ws.receive_json::<serde_json::Value>().await;
+            if event["type"] == event_type {
+                return event;
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out waiting for {event_type}"))
+}
+
+#[tokio::test]
+async fn fogged_events_hide_dice_from_distant_player() {
+    let app = TestApp::spawn_http().await;
+    let mut ids = Vec::new();
+    for name in ["alice", "bob", "carol"] {
+        let user = app.register(name, &format!("{name}@example.com")).await;
+        ids.push(user["id"].as_str().unwrap().parse::<Uuid>().unwrap());
+    }
+    let (alice, bob, carol) = (ids[0], ids[1], ids[2]);
+
+    // A row of areas: alice and bob fight at one end, far from carol
+    let mut world = World::from_str_checked("0,0\n0,1\n0,2\n0,3\n0,4").unwrap();
+    let owners = [alice, bob, bob, bob, carol];
+    for area in world.areas.values_mut() {
+        let y = area.tiles.iter().next().unwrap().y();
+        area.owner = Some(owners[y]);
+        area.stack = Stack::new(4).unwrap();
+    }
+    let area_at = |world: &World, y: usize| {
+        world
+            .areas
+            .values()
+            .find(|area| area.tiles.contains(&Tile::new(0, y)))
+            .unwrap()
+            .id
+    };
+    let (alice_area, bob_area) = (area_at(&world, 0), area_at(&world, 1));
+
+    let config = GameConfig {
+        fog_of_war: true,
+        ..GameConfig::default()
+    };
+    let creator = Creator {
+        id: alice,
+        name: "alice".to_string(),
+    };
+    let game = app
+        .state
+        .games
+        .create_game(world, config, creator)
+        .await
+        .unwrap();
+    for (id, name) in [(alice, "alice"), (bob, "bob"), (carol, "carol")] {
+        game.join_player(id, name.to_string()).await.unwrap();
+    }
+    game.start_game().await.unwrap();
+    if game.snapshot().await.current_player_id() == Some(carol) {
+        game.end_turn(carol).await.unwrap();
+    }
+    let visible = game.snapshot().await.visible_areas(carol).unwrap();
+    assert!(!visible.contains(&alice_area) && !visible.contains(&bob_area));
+
+    // The last registered user, carol, is signed in
+    let mut carol_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{}/ws", game.id))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut carol_ws).await;
+    let _snapshot = carol_ws.receive_json::<serde_json::Value>().await;
+
+    let attacker = game.snapshot().await.current_player_id().unwrap();
+    let (from_id, to_id) = if attacker == alice {
+        (alice_area, bob_area)
+    } else {
+        (bob_area, alice_area)
+    };
+    game.attack(from_id, to_id, attacker).await.unwrap();
+
+    let attack = receive_event_of_type(&mut carol_ws, "attack_resolved").await;
+    assert_eq!(attack["result"]["attack_roll"], 0);
+    assert_eq!(attack["result"]["defence_roll"], 0);
+    assert_eq!(attack["result"]["attacker_remaining_dice"], 0);
+    assert_eq!(attack["result"]["defender_remaining_dice"], 0);
+
+    game.end_turn(attacker).await.unwrap();
+    let distributed = receive_event_of_type(&mut carol_ws, "dice_distributed").await;
+    let visible = game.snapshot().await.visible_areas(carol).unwrap();
+    for area_id in distributed["areas_updated"].as_array().unwrap() {
+        let area_id: Uuid = area_id.as_str().unwrap().parse().unwrap();
+        assert!(
+            visible.contains(&area_id),
+            "carol saw dice land on {area_id}"
+        );
+    }
+}
+
 #[tokio::test]
 async fn game_websocket_reconnect_same_user_receives_snapshot() {
     let app = TestApp::spawn_http().await;
@@ -198,6 +357,7 @@ async fn game_websocket_reconnect_same_user_receives_snapshot() {
         .await
         .into_websocket()
         .await;
+    common::expect_protocol_version(&mut first_ws).await;
 
     let first_snapshot = first_ws.receive_json::<serde_json::Value>().await;
     assert_eq!(first_snapshot["type"], "snapshot");
@@ -210,8 +370,546 @@ async fn game_websocket_reconnect_same_user_receives_snapshot() {
         .await
         .into_websocket()
         .await;
+    common::expect_protocol_version(&mut second_ws).await;
 
     let second_snapshot = second_ws.receive_json::<serde_json::Value>().await;
     assert_eq!(second_snapshot["type"], "snapshot");
     assert_eq!(second_snapshot["game"]["id"], game_id);
 }
+
+async fn receive_msgpack_event(ws: &mut axum_test::TestWebSocket) -> GameEvent {
+    match ws.receive_message().await {
+        WsMessage::Binary(data) => rmp_serde::from_slice(&data).unwrap(),
+        message => panic!("expected a binary message, got {message:?}"),
+    }
+}
+
+#[tokio::test]
+async fn game_websocket_msgpack_client_receives_same_events_as_json_client() {
+    let app = TestApp::spawn_http().await;
+
+    app.register("alice", "alice@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    let mut alice_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut alice_ws).await;
+    let _ = alice_ws.receive_json::<serde_json::Value>().await;
+
+    app.register("bob", "bob@example.com").await;
+    let mut bob_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut bob_ws).await;
+    let _ = bob_ws.receive_json::<serde_json::Value>().await;
+
+    bob_ws
+        .send_message(WsMessage::Binary(b"msgpack".to_vec().into()))
+        .await;
+    let snapshot = receive_msgpack_event(&mut bob_ws).await;
+    assert!(matches!(snapshot, GameEvent::Snapshot { .. }));
+
+    // Alice first sees bob joining
+    let joined = alice_ws.receive_json::<serde_json::Value>().await;
+    assert_eq!(joined["type"], "player_joined");
+    let _ = alice_ws.receive_json::<serde_json::Value>().await;
+
+    let start = rmp_serde::to_vec_named(&GameCommand::Start).unwrap();
+    bob_ws.send_message(WsMessage::Binary(start.into())).await;
+
+    // The game starting, then the state update it causes
+    let mut alice_events = Vec::new();
+    let mut bob_events = Vec::new();
+    for _ in 0..2 {
+        let event = alice_ws.receive_json::<GameEvent>().await;
+        alice_events.push(serde_json::to_value(event).unwrap());
+        let event = receive_msgpack_event(&mut bob_ws).await;
+        bob_events.push(serde_json::to_value(event).unwrap());
+    }
+
+    assert_eq!(alice_events[0]["type"], "game_started");
+    assert_eq!(alice_events, bob_events);
+}
+
+// ==== Spectators ====
+
+#[tokio::test]
+async fn game_websocket_spectator_receives_snapshots() {
+    let app = TestApp::spawn_http().await;
+
+    app.register("alice", "alice@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    app.register("eve", "eve@example.com").await;
+    let mut eve_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws?spectate=true"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut eve_ws).await;
+
+    let snapshot = eve_ws.receive_json::<serde_json::Value>().await;
+    assert_eq!(snapshot["type"], "snapshot");
+    assert_eq!(snapshot["game"]["players"].as_array().unwrap().len(), 0);
+    assert_eq!(snapshot["game"]["spectators"][0]["name"], "eve");
+
+    let games: serde_json::Value = app.server.get("/api/games").await.json();
+    assert_eq!(games["items"][0]["player_count"], 0);
+    assert_eq!(games["items"][0]["spectator_count"], 1);
+}
+
+#[tokio::test]
+async fn game_websocket_spectator_cannot_start_game() {
+    let app = TestApp::spawn_http().await;
+
+    app.register("alice", "alice@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    app.register("eve", "eve@example.com").await;
+    let mut eve_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws?spectate=true"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut eve_ws).await;
+    let _ = eve_ws.receive_json::<serde_json::Value>().await;
+
+    eve_ws.send_json(&json!({ "type": "start" })).await;
+
+    let mut saw_spectator_error = false;
+    for _ in 0..4 {
+        let event = eve_ws.receive_json::<serde_json::Value>().await;
+        if event["type"] == "error"
+            && event["message"]
+                .as_str()
+                .unwrap_or_default()
+                .contains("spectators cannot")
+        {
+            saw_spectator_error = true;
+            break;
+        }
+    }
+
+    assert!(
+        saw_spectator_error,
+        "expected spectator action error on websocket"
+    );
+}
+
+#[tokio::test]
+async fn game_websocket_closes_on_server_shutdown() {
+    let app = TestApp::spawn_http().await;
+
+    app.register("alice", "alice@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    let mut sockets = Vec::new();
+    for spectate in [false, true] {
+        let mut ws = app
+            .server
+            .get_websocket(&format!("/api/games/{game_id}/ws?spectate={spectate}"))
+            .expect_failure()
+            .await
+            .into_websocket()
+            .await;
+        common::expect_protocol_version(&mut ws).await;
+        let _ = ws.receive_json::<serde_json::Value>().await;
+        sockets.push(ws);
+    }
+
+    app.state.shutdown.send(()).unwrap();
+
+    for mut ws in sockets {
+        let closed = timeout(Duration::from_secs(2), async {
+            loop {
+                if let WsMessage::Close(frame) = ws.receive_message().await {
+                    return frame;
+                }
+            }
+        })
+        .await
+        .expect("expected close frame after shutdown");
+
+        assert_eq!(u16::from(closed.unwrap().code), 1001);
+    }
+}
+
+#[tokio::test]
+async fn game_websocket_rejects_players_when_game_is_full() {
+    let app = TestApp::spawn_http().await;
+
+    app.register("host", "host@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    // Fill every seat; the last one is taken while the game is one short
+    let mut sockets = Vec::new();
+    for i in 0..::common::MAX_PLAYERS {
+        app.register(&format!("player{i}"), &format!("player{i}@example.com"))
+            .await;
+        let mut ws = app
+            .server
+            .get_websocket(&format!("/api/games/{game_id}/ws"))
+            .expect_failure()
+            .await
+            .into_websocket()
+            .await;
+        common::expect_protocol_version(&mut ws).await;
+        let _ = ws.receive_json::<serde_json::Value>().await;
+        sockets.push(ws);
+    }
+
+    app.register("latecomer", "latecomer@example.com").await;
+    let response = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await;
+
+    response.assert_status(axum::http::StatusCode::CONFLICT);
+}
+
+// ==== Leaving ====
+
+#[tokio::test]
+async fn game_websocket_clean_close_emits_player_left() {
+    let app = TestApp::spawn_http().await;
+
+    let alice = app.register("alice", "alice@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    let mut alice_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut alice_ws).await;
+    let _ = alice_ws.receive_json::<serde_json::Value>().await;
+
+    app.register("bob", "bob@example.com").await;
+    let mut bob_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut bob_ws).await;
+    let _ = bob_ws.receive_json::<serde_json::Value>().await;
+
+    alice_ws.close().await;
+
+    let left = timeout(Duration::from_secs(1), async {
+        loop {
+            let event = bob_ws.receive_json::<serde_json::Value>().await;
+            if event["type"] == "player_left" {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("expected player_left event after alice closed their socket");
+
+    assert_eq!(left["player_id"], alice["id"]);
+    assert_eq!(left["player_name"], "alice");
+}
+
+// ==== Protocol version ====
+
+async fn connect_alice(app: &TestApp) -> axum_test::TestWebSocket {
+    app.register("alice", "alice@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    let mut ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut ws).await;
+    let _ = ws.receive_json::<serde_json::Value>().await;
+    ws
+}
+
+#[tokio::test]
+async fn game_websocket_matching_protocol_version_proceeds() {
+    let app = TestApp::spawn_http().await;
+    let mut ws = connect_alice(&app).await;
+
+    ws.send_json(&json!({
+        "type": "protocol_version",
+        "version": ::common::PROTOCOL_VERSION,
+    }))
+    .await;
+    ws.send_json(&json!({ "type": "start" })).await;
+
+    // Starting alone fails, the error proves the socket still runs commands
+    let event = timeout(
+        Duration::from_secs(1),
+        ws.receive_json::<serde_json::Value>(),
+    )
+    .await
+    .expect("expected a reply after the version handshake");
+    assert_eq!(event["type"], "error");
+}
+
+#[tokio::test]
+async fn game_websocket_mismatched_protocol_version_closes() {
+    let app = TestApp::spawn_http().await;
+    let mut ws = connect_alice(&app).await;
+
+    ws.send_json(&json!({
+        "type": "protocol_version",
+        "version": ::common::PROTOCOL_VERSION + 1,
+    }))
+    .await;
+
+    let closed = timeout(Duration::from_secs(1), async {
+        loop {
+            if let WsMessage::Close(frame) = ws.receive_message().await {
+                return frame;
+            }
+        }
+    })
+    .await
+    .expect("expected close frame after a mismatched version");
+
+    let frame = closed.unwrap();
+    assert_eq!(u16::from(frame.code), 1002);
+    assert!(
+        frame.reason.contains("protocol version"),
+        "{}",
+        frame.reason
+    );
+}
+
+// ==== Turn events ====
+
+#[tokio::test]
+async fn game_websocket_end_turn_emits_dice_distributed() {
+    let app = TestApp::spawn_http().await;
+
+    let alice = app.register("alice", "alice@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    let mut alice_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut alice_ws).await;
+    let _ = alice_ws.receive_json::<serde_json::Value>().await;
+
+    app.register("bob", "bob@example.com").await;
+    let mut bob_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut bob_ws).await;
+    let _ = bob_ws.receive_json::<serde_json::Value>().await;
+
+    bob_ws.send_json(&json!({ "type": "start" })).await;
+    let current = timeout(Duration::from_secs(1), async {
+        loop {
+            let event = bob_ws.receive_json::<serde_json::Value>().await;
+            if event["type"] == "turn_started" {
+                return event["player_id"].clone();
+            }
+        }
+    })
+    .await
+    .expect("expected turn_started event after starting the game");
+
+    let ws = if current == alice["id"] {
+        &mut alice_ws
+    } else {
+        &mut bob_ws
+    };
+    ws.send_json(&json!({ "type": "end_turn" })).await;
+
+    let distributed = timeout(Duration::from_secs(1), async {
+        loop {
+            let event = ws.receive_json::<serde_json::Value>().await;
+            if event["type"] == "dice_distributed" {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("expected dice_distributed event after ending the turn");
+
+    assert_eq!(distributed["player_id"], current);
+    let amount = distributed["amount"].as_u64().unwrap();
+    let areas = distributed["areas_updated"].as_array().unwrap();
+    assert!(amount >= 1, "every player owns at least one area");
+    assert!(!areas.is_empty() && areas.len() as u64 <= amount);
+}
+
+#[tokio::test]
+async fn game_websocket_resign_finishes_game_and_closes_socket() {
+    let app = TestApp::spawn_http().await;
+
+    let alice = app.register("alice", "alice@example.com").await;
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    let mut alice_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut alice_ws).await;
+    let _ = alice_ws.receive_json::<serde_json::Value>().await;
+
+    app.register("bob", "bob@example.com").await;
+    let mut bob_ws = app
+        .server
+        .get_websocket(&format!("/api/games/{game_id}/ws"))
+        .expect_failure()
+        .await
+        .into_websocket()
+        .await;
+    common::expect_protocol_version(&mut bob_ws).await;
+    let _ = bob_ws.receive_json::<serde_json::Value>().await;
+
+    bob_ws.send_json(&json!({ "type": "start" })).await;
+    bob_ws.send_json(&json!({ "type": "resign" })).await;
+
+    let finished = timeout(Duration::from_secs(1), async {
+        loop {
+            if let WsMessage::Text(text) = bob_ws.receive_message().await {
+                let event: serde_json::Value = serde_json::from_str(text.as_str()).unwrap();
+                if event["type"] == "finished" {
+                    return event;
+                }
+            }
+        }
+    })
+    .await
+    .expect("expected finished event after resigning");
+    assert_eq!(finished["winner_id"], alice["id"]);
+
+    let closed = timeout(Duration::from_secs(1), async {
+        loop {
+            if let WsMessage::Close(frame) = bob_ws.receive_message().await {
+                return frame;
+            }
+        }
+    })
+    .await
+    .expect("expected close frame after resigning");
+    assert_eq!(u16::from(closed.unwrap().code), 1000);
+
+    let eliminated = timeout(Duration::from_secs(1), async {
+        loop {
+            let event = alice_ws.receive_json::<serde_json::Value>().await;
+            if event["type"] == "player_eliminated" {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("expected player_eliminated event after bob resigned");
+    assert!(eliminated["eliminated_by"].is_null());
+}
+
+// ==== Chat ====
+
+/// The next event of type `event_type` on `ws`, skipping all others.
+async fn next_event_of_type(
+    ws: &mut axum_test::TestWebSocket,
+    event_type: &str,
+) -> serde_json::Value {
+    timeout(Duration::from_secs(1), async {
+        loop {
+            let event = ws.receive_json::<serde_json::Value>().await;
+            if event["type"] == event_type {
+                return event;
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("expected {event_type} event"))
+}
+
+#[tokio::test]
+async fn game_websocket_chat_is_broadcast_sanitized() {
+    let app = TestApp::spawn_http().await;
+    let mut ws = connect_alice(&app).await;
+
+    ws.send_json(&json!({ "type": "chat", "message": "  good\u{7} luck\n" }))
+        .await;
+
+    let chat = next_event_of_type(&mut ws, "chat_message").await;
+    assert_eq!(chat["player_name"], "alice");
+    assert_eq!(chat["message"], "good luck");
+}
+
+#[tokio::test]
+async fn game_websocket_malformed_chat_returns_error() {
+    let app = TestApp::spawn_http().await;
+    let mut ws = connect_alice(&app).await;
+
+    for message in [" \n ".to_string(), "a".repeat(201)] {
+        ws.send_json(&json!({ "type": "chat", "message": message }))
+            .await;
+        let error = next_event_of_type(&mut ws, "error").await;
+        assert!(
+            error["message"].as_str().unwrap().contains("chat message"),
+            "{error}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn game_websocket_chat_is_rate_limited() {
+    let app = TestApp::spawn_http().await;
+    let mut ws = connect_alice(&app).await;
+
+    for i in 0..backend::games::CHAT_RATE_LIMIT_MESSAGES {
+        ws.send_json(&json!({ "type": "chat", "message": format!("message {i}") }))
+            .await;
+        next_event_of_type(&mut ws, "chat_message").await;
+    }
+
+    ws.send_json(&json!({ "type": "chat", "message": "one too many" }))
+        .await;
+    let error = next_event_of_type(&mut ws, "error").await;
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .contains("too many chat messages"),
+        "{error}"
+    );
+}
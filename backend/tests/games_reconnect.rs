@@ -0,0 +1,78 @@
+mod common;
+
+use ::common::GameConfig;
+use backend::games::{Game, GameEvent};
+use common::TestApp;
+use std::time::Duration;
+
+/// A started two-player game in which both players are connected.
+async fn connected_game(app: &TestApp, grace_period_seconds: u64) -> Game {
+    let config = GameConfig {
+        reconnect_grace_period_seconds: grace_period_seconds,
+        ..GameConfig::default()
+    };
+    let game = common::two_player_game(app, config).await;
+    for player in game.snapshot().await.players {
+        game.player_connected(player.id);
+    }
+    game.start_game().await.unwrap();
+
+    game
+}
+
+#[tokio::test]
+async fn reconnecting_within_grace_period_keeps_turn() {
+    let app = TestApp::spawn().await;
+    let game = connected_game(&app, 1).await;
+    let mut events = game.subscribe_events();
+    let player = game.snapshot().await.current_player_id().unwrap();
+
+    game.player_disconnected(player).await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    game.player_connected(player);
+
+    // Wait past the end of the grace period
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    assert_eq!(game.snapshot().await.current_player_id().unwrap(), player);
+    while let Ok(event) = events.try_recv() {
+        assert!(!matches!(event, GameEvent::TurnSkipped { .. }));
+    }
+}
+
+#[tokio::test]
+async fn turn_is_skipped_after_grace_period() {
+    let app = TestApp::spawn().await;
+    let game = connected_game(&app, 1).await;
+    let mut events = game.subscribe_events();
+    let player = game.snapshot().await.current_player_id().unwrap();
+
+    game.player_disconnected(player).await;
+
+    let skipped = tokio::time::timeout(Duration::from_secs(3), async {
+        loop {
+            if let GameEvent::TurnSkipped { player_id } = events.recv().await.unwrap() {
+                return player_id;
+            }
+        }
+    })
+    .await
+    .expect("turn was not skipped");
+
+    assert_eq!(skipped, player);
+    assert_ne!(game.snapshot().await.current_player_id().unwrap(), player);
+}
+
+#[tokio::test]
+async fn absent_players_turn_is_skipped_when_it_comes_around() {
+    let app = TestApp::spawn().await;
+    let game = connected_game(&app, 0).await;
+    let snapshot = game.snapshot().await;
+    let first = game.snapshot().await.current_player_id().unwrap();
+    let second = snapshot.players.iter().find(|p| p.id != first).unwrap().id;
+
+    game.player_disconnected(second).await;
+    game.end_turn(first).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(game.snapshot().await.current_player_id().unwrap(), first);
+}
@@ -0,0 +1,292 @@
+mod common;
+
+use ::common::{GameConfig, World};
+use backend::games::{Creator, FINISHED_GAME_EXPIRY, Game, Games};
+use backend::prelude::{DEFAULT_GAME_BROADCAST_CHANNEL_SIZE, Error};
+use common::TestApp;
+use std::time::Duration;
+use uuid::Uuid;
+
+async fn creator(app: &TestApp) -> Creator {
+    let alice = app.register("alice", "alice@example.com").await;
+    Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    }
+}
+
+async fn game_with_players(games: &Games, creator: &Creator, players: usize) -> Game {
+    let world = World::from_str_checked(include_str!("../worlds/default.world")).unwrap();
+    let game = games
+        .create_game(world, GameConfig::default(), creator.clone())
+        .await
+        .unwrap();
+    for i in 0..players {
+        game.join_player(Uuid::new_v4(), format!("player{i}"))
+            .await
+            .unwrap();
+    }
+    game
+}
+
+#[tokio::test]
+async fn total_active_player_count_of_empty_store_is_zero() {
+    let app = TestApp::spawn().await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+
+    assert_eq!(games.total_active_player_count().await, 0);
+}
+
+#[tokio::test]
+async fn total_active_player_count_sums_started_games() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+
+    let first = game_with_players(&games, &creator, 2).await;
+    let second = game_with_players(&games, &creator, 3).await;
+    first.start_game().await.unwrap();
+    second.start_game().await.unwrap();
+
+    assert_eq!(games.total_active_player_count().await, 5);
+}
+
+#[tokio::test]
+async fn total_active_player_count_drops_when_a_game_finishes() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+
+    let first = game_with_players(&games, &creator, 2).await;
+    let second = game_with_players(&games, &creator, 3).await;
+    first.start_game().await.unwrap();
+    second.start_game().await.unwrap();
+    assert_eq!(games.total_active_player_count().await, 5);
+
+    second.cancel("Game was cancelled").await.unwrap();
+
+    assert_eq!(games.total_active_player_count().await, 2);
+}
+
+#[tokio::test]
+async fn total_active_player_count_ignores_games_not_in_progress() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+
+    let started = game_with_players(&games, &creator, 2).await;
+    game_with_players(&games, &creator, 3).await;
+    started.start_game().await.unwrap();
+
+    assert_eq!(games.total_active_player_count().await, 2);
+}
+
+#[tokio::test]
+async fn for_player_lists_games_in_any_state() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+
+    let player = Uuid::new_v4();
+    let waiting = game_with_players(&games, &creator, 1).await;
+    let started = game_with_players(&games, &creator, 1).await;
+    game_with_players(&games, &creator, 2).await;
+    for game in [&waiting, &started] {
+        game.join_player(player, "player".to_string())
+            .await
+            .unwrap();
+    }
+    started.start_game().await.unwrap();
+
+    let mut ids: Vec<Uuid> = games
+        .for_player(player)
+        .await
+        .iter()
+        .map(|g| g.id)
+        .collect();
+    ids.sort();
+    let mut expected = vec![waiting.id, started.id];
+    expected.sort();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn for_player_of_unknown_player_is_empty() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    game_with_players(&games, &creator, 2).await;
+
+    assert!(games.for_player(Uuid::new_v4()).await.is_empty());
+}
+
+#[tokio::test]
+async fn player_count_follows_joins() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let game = game_with_players(&games, &creator, 0).await;
+    assert_eq!(game.player_count(), 0);
+
+    let player = Uuid::new_v4();
+    game.join_player(player, "first".to_string()).await.unwrap();
+    assert_eq!(game.player_count(), 1);
+
+    // Joining twice is rejected and leaves the count alone
+    assert!(game.join_player(player, "first".to_string()).await.is_err());
+    assert_eq!(game.player_count(), 1);
+    assert_eq!(game.snapshot().await.players.len(), 1);
+}
+
+#[tokio::test]
+async fn player_count_of_restored_game_includes_existing_players() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let game = game_with_players(&games, &creator, 2).await;
+
+    // Snapshots are stored in the background, so wait for the second join
+    let restored = tokio::time::timeout(std::time::Duration::from_secs(2), async {
+        loop {
+            let restored = Games::load_from_db(&app.db, DEFAULT_GAME_BROADCAST_CHANNEL_SIZE)
+                .await
+                .unwrap();
+            let restored = restored.get_game(&game.id).await.unwrap();
+            if restored.snapshot().await.players.len() == 2 {
+                return restored;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("expected both players in the stored snapshot");
+
+    assert_eq!(restored.player_count(), 2);
+}
+
+#[tokio::test]
+async fn subscribe_game_of_existing_game() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let game = game_with_players(&games, &creator, 1).await;
+
+    let snapshot = games.subscribe_game(&game.id).await.unwrap();
+
+    assert_eq!(snapshot.borrow().id, game.id);
+    assert_eq!(snapshot.borrow().players.len(), 1);
+}
+
+#[tokio::test]
+async fn subscribe_game_of_unknown_game_is_none() {
+    let app = TestApp::spawn().await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+
+    assert!(games.subscribe_game(&Uuid::new_v4()).await.is_none());
+}
+
+#[tokio::test]
+async fn subscribe_game_receives_updates() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let game = game_with_players(&games, &creator, 0).await;
+    let mut snapshot = games.subscribe_game(&game.id).await.unwrap();
+
+    game.join_player(Uuid::new_v4(), "first".to_string())
+        .await
+        .unwrap();
+
+    tokio::time::timeout(std::time::Duration::from_secs(2), snapshot.changed())
+        .await
+        .expect("expected a snapshot after the join")
+        .unwrap();
+    assert_eq!(snapshot.borrow_and_update().players.len(), 1);
+}
+
+#[tokio::test]
+async fn create_game_rejects_invalid_world() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+
+    let world = World::from_string("0,0 1,0\n6,0 7,0");
+    let result = games
+        .create_game(world, GameConfig::default(), creator)
+        .await;
+
+    assert!(matches!(result, Err(Error::BadRequest(_))));
+    assert!(games.list_games().await.is_empty());
+}
+
+#[tokio::test]
+async fn remove_game_removes_existing_game() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let game = game_with_players(&games, &creator, 1).await;
+
+    assert!(games.remove_game(game.id).await);
+    assert!(games.get_game(&game.id).await.is_none());
+    assert!(games.subscribe_list().borrow().is_empty());
+    assert!(!games.remove_game(game.id).await);
+}
+
+/// Finish a started two player game by having its first player resign.
+async fn finished_game(games: &Games, creator: &Creator) -> Game {
+    let game = game_with_players(games, creator, 2).await;
+    game.start_game().await.unwrap();
+    let resigning = game.snapshot().await.players[0].id;
+    game.resign(resigning).await.unwrap();
+    assert!(game.finished_at().is_some());
+    game
+}
+
+#[tokio::test]
+async fn cleanup_finished_removes_games_after_expiry() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let finished = finished_game(&games, &creator).await;
+    let running = game_with_players(&games, &creator, 2).await;
+    running.start_game().await.unwrap();
+
+    tokio::time::pause();
+    tokio::time::advance(FINISHED_GAME_EXPIRY - Duration::from_secs(1)).await;
+    assert_eq!(games.cleanup_finished().await, 0);
+    assert!(games.get_game(&finished.id).await.is_some());
+
+    // The background cleanup task may get to the expired game first
+    tokio::time::advance(Duration::from_secs(1)).await;
+    games.cleanup_finished().await;
+    assert!(games.get_game(&finished.id).await.is_none());
+    assert!(games.get_game(&running.id).await.is_some());
+
+    let listed: Vec<Uuid> = games
+        .subscribe_list()
+        .borrow()
+        .iter()
+        .map(|g| g.id)
+        .collect();
+    assert_eq!(listed, vec![running.id]);
+}
+
+#[tokio::test]
+async fn cleanup_finished_runs_in_background() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let finished = finished_game(&games, &creator).await;
+
+    tokio::time::pause();
+    // The cleanup task runs every five minutes, so it has run at least once
+    // since the game expired
+    tokio::time::advance(FINISHED_GAME_EXPIRY + Duration::from_secs(5 * 60)).await;
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while games.get_game(&finished.id).await.is_some() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("expected the finished game to be cleaned up");
+}
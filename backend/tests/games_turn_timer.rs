@@ -0,0 +1,91 @@
+mod common;
+
+use ::common::GameConfig;
+use backend::games::GameEvent;
+use common::TestApp;
+use std::time::Duration;
+
+fn two_second_turns() -> GameConfig {
+    GameConfig {
+        turn_time_limit: Some(Duration::from_secs(2)),
+        ..GameConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn turn_auto_advances_when_time_limit_expires() {
+    let app = TestApp::spawn().await;
+    let game = common::started_game(&app, two_second_turns()).await;
+    let mut events = game.subscribe_events();
+    let starting_player = game.snapshot().await.current_player_id().unwrap();
+
+    let expired = tokio::time::timeout(Duration::from_secs(4), async {
+        loop {
+            if let GameEvent::TurnTimerExpired { player_id } = events.recv().await.unwrap() {
+                return player_id;
+            }
+        }
+    })
+    .await
+    .expect("turn timer did not expire");
+
+    assert_eq!(expired, starting_player);
+    assert_ne!(
+        game.snapshot().await.current_player_id().unwrap(),
+        starting_player
+    );
+}
+
+#[tokio::test]
+async fn ending_turn_restarts_timer() {
+    let app = TestApp::spawn().await;
+    let game = common::started_game(&app, two_second_turns()).await;
+    let mut events = game.subscribe_events();
+
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    let first_player = game.snapshot().await.current_player_id().unwrap();
+    game.end_turn(first_player).await.unwrap();
+    let second_player = game.snapshot().await.current_player_id().unwrap();
+
+    // The original deadline passes without the new player's turn ending
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    assert_eq!(
+        game.snapshot().await.current_player_id().unwrap(),
+        second_player
+    );
+
+    let mut restarted = false;
+    while let Ok(event) = events.try_recv() {
+        match event {
+            GameEvent::TurnTimerStarted { seconds_remaining } => {
+                assert_eq!(seconds_remaining, 2);
+                restarted = true;
+            }
+            GameEvent::TurnTimerExpired { .. } => panic!("timer expired after the turn ended"),
+            _ => {}
+        }
+    }
+    assert!(restarted, "expected a new turn timer after ending the turn");
+}
+
+#[tokio::test]
+async fn game_without_time_limit_does_not_advance() {
+    let app = TestApp::spawn().await;
+    let game = common::two_player_game(&app, GameConfig::default()).await;
+    let mut events = game.subscribe_events();
+    game.start_game().await.unwrap();
+
+    let starting_player = game.snapshot().await.current_player_id().unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    assert_eq!(
+        game.snapshot().await.current_player_id().unwrap(),
+        starting_player
+    );
+    while let Ok(event) = events.try_recv() {
+        assert!(!matches!(
+            event,
+            GameEvent::TurnTimerStarted { .. } | GameEvent::TurnTimerExpired { .. }
+        ));
+    }
+}
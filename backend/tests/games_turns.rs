@@ -0,0 +1,233 @@
+mod common;
+
+use ::common::{GameConfig, GameState, World};
+use backend::games::{Creator, Game, GameEvent, Games};
+use backend::prelude::DEFAULT_GAME_BROADCAST_CHANNEL_SIZE;
+use common::TestApp;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// The next `TurnStarted` event as `(player_id, turn_number)`.
+fn next_turn_started(events: &mut broadcast::Receiver<GameEvent>) -> (Uuid, u32) {
+    loop {
+        match events.try_recv().expect("expected a turn_started event") {
+            GameEvent::TurnStarted {
+                player_id,
+                turn_number,
+            } => return (player_id, turn_number),
+            _ => continue,
+        }
+    }
+}
+
+#[tokio::test]
+async fn turn_started_carries_turn_number() {
+    let app = TestApp::spawn().await;
+    let game = common::two_player_game(&app, GameConfig::default()).await;
+    let mut events = game.subscribe_events();
+
+    game.start_game().await.unwrap();
+    let first = game.snapshot().await.current_player_id().unwrap();
+    assert_eq!(next_turn_started(&mut events), (first, 0));
+
+    game.end_turn(first).await.unwrap();
+    let second = game.snapshot().await.current_player_id().unwrap();
+    assert_eq!(next_turn_started(&mut events), (second, 1));
+    assert_eq!(game.snapshot().await.turn_number, 1);
+}
+
+#[tokio::test]
+async fn snapshot_json_includes_turn_number() {
+    let app = TestApp::spawn().await;
+    let game = common::two_player_game(&app, GameConfig::default()).await;
+    game.start_game().await.unwrap();
+    game.end_turn(game.snapshot().await.current_player_id().unwrap())
+        .await
+        .unwrap();
+
+    let json = serde_json::to_value(game.snapshot().await).unwrap();
+    assert_eq!(json["turn_number"], 1);
+}
+
+#[tokio::test]
+async fn end_turn_publishes_dice_distributed_before_turn_ended() {
+    let app = TestApp::spawn().await;
+    let game = common::two_player_game(&app, GameConfig::default()).await;
+    game.start_game().await.unwrap();
+    let first = game.snapshot().await.current_player_id().unwrap();
+    let dice_of = |game: &::common::Game| -> usize {
+        game.world
+            .areas
+            .values()
+            .filter(|area| area.is_owned_by(first))
+            .map(|area| area.stack.count())
+            .sum()
+    };
+    let before = dice_of(&game.snapshot().await);
+
+    let mut events = game.subscribe_events();
+    game.end_turn(first).await.unwrap();
+
+    let amount = loop {
+        match events
+            .try_recv()
+            .expect("expected a dice_distributed event")
+        {
+            GameEvent::DiceDistributed {
+                player_id, amount, ..
+            } => {
+                assert_eq!(player_id, first);
+                break amount;
+            }
+            GameEvent::TurnEnded { .. } => panic!("turn ended before dice were distributed"),
+            _ => continue,
+        }
+    };
+    assert_eq!(dice_of(&game.snapshot().await), before + amount);
+}
+
+/// A started game on a world of two adjacent single-tile areas, so each of
+/// the two players is dealt one of them.
+async fn duel(app: &TestApp, creator: Creator, seed: u64) -> Game {
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let config = GameConfig {
+        seed: Some(seed),
+        ..GameConfig::default()
+    };
+    let world = World::from_str_checked("0,0\n0,1").unwrap();
+    let game = games.create_game(world, config, creator).await.unwrap();
+    for name in ["first", "second"] {
+        game.join_player(Uuid::new_v4(), name.to_string())
+            .await
+            .unwrap();
+    }
+    game.start_game().await.unwrap();
+    game
+}
+
+#[tokio::test]
+async fn capturing_last_area_publishes_player_eliminated_and_game_finished() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let creator = Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    };
+
+    for seed in 0..50 {
+        let game = duel(&app, creator.clone(), seed).await;
+        let attacker = game.snapshot().await.current_player_id().unwrap();
+        let snapshot = game.snapshot().await;
+        let from = snapshot
+            .world
+            .areas
+            .values()
+            .find(|area| area.is_owned_by(attacker))
+            .unwrap();
+        let to = snapshot
+            .world
+            .areas
+            .values()
+            .find(|area| !area.is_owned_by(attacker))
+            .unwrap();
+        if from.stack.is_single() {
+            continue;
+        }
+        let defender = to.owner.unwrap();
+
+        let mut events = game.subscribe_events();
+        game.attack(from.id, to.id, attacker).await.unwrap();
+        if !game.snapshot().await.world.is_player_eliminated(defender) {
+            continue;
+        }
+
+        let mut eliminated = None;
+        let mut finished = None;
+        while let Ok(event) = events.try_recv() {
+            match event {
+                GameEvent::PlayerEliminated {
+                    player_id,
+                    eliminated_by,
+                } => eliminated = Some((player_id, eliminated_by)),
+                GameEvent::GameFinished { winner_id, .. } => {
+                    assert!(eliminated.is_some(), "finished before elimination");
+                    finished = Some(winner_id);
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(eliminated, Some((defender, Some(attacker))));
+        assert_eq!(finished, Some(Some(attacker)));
+        return;
+    }
+    panic!("no attack captured the last area in 50 seeded games");
+}
+
+#[tokio::test]
+async fn resign_with_two_players_left_continues_game() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let creator = Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    };
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+    let world = World::from_str_checked("0,0\n0,1\n0,2").unwrap();
+    let game = games
+        .create_game(world, GameConfig::default(), creator)
+        .await
+        .unwrap();
+    let players: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+    for (i, id) in players.iter().enumerate() {
+        game.join_player(*id, format!("player {i}")).await.unwrap();
+    }
+    game.start_game().await.unwrap();
+
+    let resigning = game.snapshot().await.current_player_id().unwrap();
+    let mut events = game.subscribe_events();
+    game.resign(resigning).await.unwrap();
+
+    let snapshot = game.snapshot().await;
+    assert!(matches!(snapshot.state, GameState::InProgress { .. }));
+    assert!(snapshot.world.is_player_eliminated(resigning));
+    assert_ne!(
+        game.snapshot().await.current_player_id().unwrap(),
+        resigning
+    );
+
+    let mut eliminated = None;
+    while let Ok(event) = events.try_recv() {
+        match event {
+            GameEvent::PlayerEliminated {
+                player_id,
+                eliminated_by,
+            } => eliminated = Some((player_id, eliminated_by)),
+            GameEvent::GameFinished { .. } => panic!("game finished with two players left"),
+            _ => {}
+        }
+    }
+    assert_eq!(eliminated, Some((resigning, None)));
+}
+
+#[tokio::test]
+async fn resign_in_duel_finishes_game() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let creator = Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    };
+    let game = duel(&app, creator, 0).await;
+    let snapshot = game.snapshot().await;
+    let (first, second) = (snapshot.players[0].id, snapshot.players[1].id);
+
+    let mut events = game.subscribe_events();
+    game.resign(first).await.unwrap();
+
+    assert_eq!(game.snapshot().await.state, GameState::Finished);
+    let finished = std::iter::from_fn(|| events.try_recv().ok()).find_map(|event| match event {
+        GameEvent::GameFinished { winner_id, .. } => Some(winner_id),
+        _ => None,
+    });
+    assert_eq!(finished, Some(Some(second)));
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use ::common::{GameConfig, World};
+use backend::games::{Creator, Games};
+use backend::prelude::DEFAULT_GAME_BROADCAST_CHANNEL_SIZE;
+use common::TestApp;
+
+async fn creator(app: &TestApp) -> Creator {
+    let alice = app.register("alice", "alice@example.com").await;
+    Creator {
+        id: alice["id"].as_str().unwrap().parse().unwrap(),
+        name: "alice".to_string(),
+    }
+}
+
+// ==== games_created_total ====
+
+#[tokio::test]
+async fn games_created_total_counts_create_game_calls() {
+    let app = TestApp::spawn().await;
+    let creator = creator(&app).await;
+    let games = Games::new(app.db.clone(), DEFAULT_GAME_BROADCAST_CHANNEL_SIZE);
+
+    for _ in 0..3 {
+        let world = World::from_str_checked(include_str!("../worlds/default.world")).unwrap();
+        games
+            .create_game(world, GameConfig::default(), creator.clone())
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(games.metrics().games_created_total.get(), 3);
+    assert_eq!(games.metrics().attacks_total.get(), 0);
+}
+
+// ==== /metrics ====
+
+#[tokio::test]
+async fn metrics_endpoint_exposes_counters() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    app.server.put("/api/v1/games").await.assert_status_ok();
+
+    let response = app.server.get("/metrics").await;
+
+    response.assert_status_ok();
+    let text = response.text();
+    assert!(text.contains("games_created_total 1"));
+    assert!(text.contains("attacks_total 0"));
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use backend::repositories::UserRepository;
+use common::TestApp;
+
+/// Register a user who creates `n` games.
+async fn create_games(app: &TestApp, n: usize) {
+    app.register("alice", "alice@example.com").await;
+    for _ in 0..n {
+        app.server.put("/api/games").await;
+    }
+}
+
+#[tokio::test]
+async fn list_games_first_page_holds_all_games() {
+    let app = TestApp::spawn().await;
+    create_games(&app, 3).await;
+
+    let page: serde_json::Value = app
+        .server
+        .get("/api/games")
+        .add_query_param("offset", 0)
+        .add_query_param("limit", 5)
+        .await
+        .json();
+
+    assert_eq!(page["total"], 3);
+    assert_eq!(page["offset"], 0);
+    assert_eq!(page["limit"], 5);
+    assert_eq!(page["items"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn list_games_offset_past_end_is_empty() {
+    let app = TestApp::spawn().await;
+    create_games(&app, 3).await;
+
+    let page: serde_json::Value = app
+        .server
+        .get("/api/games")
+        .add_query_param("offset", 5)
+        .add_query_param("limit", 5)
+        .await
+        .json();
+
+    assert_eq!(page["total"], 3);
+    assert!(page["items"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn list_games_pages_do_not_overlap() {
+    let app = TestApp::spawn().await;
+    create_games(&app, 3).await;
+
+    let mut ids = Vec::new();
+    for offset in 0..3 {
+        let page: serde_json::Value = app
+            .server
+            .get("/api/games")
+            .add_query_param("offset", offset)
+            .add_query_param("limit", 1)
+            .await
+            .json();
+        ids.push(page["items"][0]["id"].as_str().unwrap().to_string());
+    }
+
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 3);
+}
+
+#[tokio::test]
+async fn list_users_is_paginated() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    app.register("bob", "bob@example.com").await;
+    app.register_admin("admin", "admin@example.com").await;
+
+    let page: serde_json::Value = app
+        .server
+        .get("/api/users")
+        .add_query_param("limit", 2)
+        .await
+        .json();
+
+    assert_eq!(page["total"], 3);
+    assert_eq!(page["items"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn list_users_pages_skip_deleted_users() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    app.register("bob", "bob@example.com").await;
+    app.register("carol", "carol@example.com").await;
+    app.register_admin("admin", "admin@example.com").await;
+    let alice_id = alice["id"].as_str().unwrap().parse().unwrap();
+    UserRepository::new(&app.db)
+        .soft_delete(alice_id)
+        .await
+        .unwrap();
+
+    let mut ids = Vec::new();
+    for offset in 0..4 {
+        let page: serde_json::Value = app
+            .server
+            .get("/api/users")
+            .add_query_param("limit", 1)
+            .add_query_param("offset", offset)
+            .await
+            .json();
+        assert_eq!(page["total"], 3);
+        ids.extend(page["items"].as_array().unwrap().iter().cloned());
+    }
+
+    assert_eq!(ids.len(), 3);
+    assert!(ids.iter().all(|user| user["id"] != alice["id"]));
+}
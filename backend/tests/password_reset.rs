@@ -123,6 +123,35 @@ async fn reset_password_with_valid_token_updates_password_and_revokes_sessions()
         .await;
 }
 
+#[tokio::test]
+async fn reset_password_to_current_password_fails() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    app.server
+        .post("/api/users/request-password-reset")
+        .json(&json!({ "identifier": "alice" }))
+        .await;
+
+    let token = match app.mock_email.latest().unwrap().mail_type {
+        MailType::PasswordReset { token } => token,
+        _ => panic!("expected PasswordReset"),
+    };
+
+    let response = app
+        .server
+        .post("/api/users/reset-password")
+        .json(&json!({
+            "token": token,
+            "password": "Str0ng!Pass"
+        }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_bad_request();
+    assert!(response.text().contains("used recently"));
+}
+
 #[tokio::test]
 async fn reset_password_token_cannot_be_reused() {
     let app = TestApp::spawn().await;
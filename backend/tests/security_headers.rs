@@ -0,0 +1,54 @@
+mod common;
+
+use common::TestApp;
+
+// ==== Security headers ====
+
+#[tokio::test]
+async fn responses_carry_security_headers() {
+    let app = TestApp::spawn().await;
+
+    for path in ["/api/health", "/api/v1/games", "/api/docs"] {
+        let response = app.server.get(path).await;
+
+        assert_eq!(response.header("x-frame-options"), "DENY", "{path}");
+        assert_eq!(
+            response.header("x-content-type-options"),
+            "nosniff",
+            "{path}"
+        );
+        assert_eq!(
+            response.header("referrer-policy"),
+            "strict-origin-when-cross-origin",
+            "{path}"
+        );
+        assert!(
+            response.maybe_header("content-security-policy").is_some(),
+            "{path}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn error_responses_carry_security_headers() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/users/me").expect_failure().await;
+
+    assert_eq!(response.header("x-frame-options"), "DENY");
+}
+
+#[tokio::test]
+async fn csp_header_uses_configured_policy() {
+    let app = TestApp::spawn_with_config(|config| {
+        config.csp_policy = "default-src 'none'".to_string();
+    })
+    .await;
+
+    let response = app.server.get("/api/health").await;
+
+    assert_eq!(
+        response.header("content-security-policy"),
+        "default-src 'none'"
+    );
+}
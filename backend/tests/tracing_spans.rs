@@ -0,0 +1,34 @@
+mod common;
+
+use common::TestApp;
+use tracing_test::traced_test;
+
+// ==== Handler spans ====
+
+#[tokio::test]
+#[traced_test]
+async fn get_game_span_carries_game_id() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+    let game: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = game["id"].as_str().unwrap();
+
+    app.server
+        .get(&format!("/api/games/{game_id}"))
+        .await
+        .assert_status_ok();
+
+    assert!(logs_contain(&format!("get_game{{game_id={game_id}}}")));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn authenticated_handler_span_carries_user_id() {
+    let app = TestApp::spawn().await;
+    let alice = app.register("alice", "alice@example.com").await;
+    let user_id = alice["id"].as_str().unwrap();
+
+    app.server.get("/api/users/me").await.assert_status_ok();
+
+    assert!(logs_contain(&format!("me{{user_id={user_id}}}")));
+}
@@ -1,5 +1,6 @@
 mod common;
 
+use backend::repositories::UserRepository;
 use common::TestApp;
 use serde_json::json;
 
@@ -13,9 +14,9 @@ async fn list_users_as_admin_succeeds() {
     let response = app.server.get("/api/users").await;
     response.assert_status_ok();
 
-    let body: Vec<serde_json::Value> = response.json();
-    assert_eq!(body.len(), 1);
-    assert_eq!(body[0]["username"], "admin");
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["items"][0]["username"], "admin");
 }
 
 #[tokio::test]
@@ -198,6 +199,79 @@ async fn update_user_as_non_admin_fails() {
     response.assert_status_not_found();
 }
 
+// ==== Update Own Username ====
+
+#[tokio::test]
+async fn update_me_renames_current_user() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .patch("/api/users/me")
+        .json(&json!({ "username": "alicia", "password": "Str0ng!Pass" }))
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["username"], "alicia");
+
+    app.server
+        .post("/api/users/auth")
+        .json(&json!({ "username": "alicia", "password": "Str0ng!Pass" }))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn update_me_with_short_username_fails() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .patch("/api/users/me")
+        .json(&json!({ "username": "al", "password": "Str0ng!Pass" }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn update_me_with_taken_username_fails() {
+    let app = TestApp::spawn().await;
+    app.register("bob", "bob@example.com").await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .patch("/api/users/me")
+        .json(&json!({ "username": "bob", "password": "Str0ng!Pass" }))
+        .expect_failure()
+        .await;
+
+    response.assert_status(axum::http::StatusCode::CONFLICT);
+    assert!(response.text().contains("Username already exists"));
+}
+
+#[tokio::test]
+async fn update_me_with_wrong_password_fails() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .patch("/api/users/me")
+        .json(&json!({ "username": "alicia", "password": "WrongPassword1!" }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_bad_request();
+    let me: serde_json::Value = app.server.get("/api/users/me").await.json();
+    assert_eq!(me["username"], "alice");
+}
+
 // ==== Update Password ====
 
 #[tokio::test]
@@ -243,6 +317,94 @@ async fn update_own_password_with_wrong_current_password_fails() {
     response.assert_status_bad_request();
 }
 
+#[tokio::test]
+async fn update_own_password_to_previous_password_fails() {
+    let app = TestApp::spawn().await;
+    let user: serde_json::Value = app.register("alice", "alice@example.com").await;
+    let id = user["id"].as_str().unwrap();
+
+    app.server
+        .post(&format!("/api/users/{id}/password"))
+        .json(&json!({
+            "current_password": "Str0ng!Pass",
+            "password": "NewStr0ng!Pass"
+        }))
+        .await;
+
+    let response = app
+        .server
+        .post(&format!("/api/users/{id}/password"))
+        .json(&json!({
+            "current_password": "NewStr0ng!Pass",
+            "password": "Str0ng!Pass"
+        }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_bad_request();
+    assert!(response.text().contains("used recently"));
+}
+
+/// Change the user's password from `current` through every password in `next`,
+/// returning the last one.
+async fn change_passwords(app: &TestApp, id: &str, current: &str, next: &[String]) -> String {
+    let mut current = current.to_string();
+    for password in next {
+        app.server
+            .post(&format!("/api/users/{id}/password"))
+            .json(&json!({
+                "current_password": current,
+                "password": password
+            }))
+            .await
+            .assert_status_ok();
+        current = password.clone();
+    }
+    current
+}
+
+#[tokio::test]
+async fn update_own_password_accepts_password_older_than_history() {
+    let app = TestApp::spawn().await;
+    let user: serde_json::Value = app.register("alice", "alice@example.com").await;
+    let id = user["id"].as_str().unwrap();
+
+    let next: Vec<String> = (1..=5).map(|i| format!("Str0ng!Pass{i}")).collect();
+    let current = change_passwords(&app, id, "Str0ng!Pass", &next).await;
+
+    // The original password is the 6th most recent and may be used again
+    app.server
+        .post(&format!("/api/users/{id}/password"))
+        .json(&json!({
+            "current_password": current,
+            "password": "Str0ng!Pass"
+        }))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn update_own_password_rejects_fifth_most_recent_password() {
+    let app = TestApp::spawn().await;
+    let user: serde_json::Value = app.register("alice", "alice@example.com").await;
+    let id = user["id"].as_str().unwrap();
+
+    let next: Vec<String> = (1..=4).map(|i| format!("Str0ng!Pass{i}")).collect();
+    let current = change_passwords(&app, id, "Str0ng!Pass", &next).await;
+
+    let response = app
+        .server
+        .post(&format!("/api/users/{id}/password"))
+        .json(&json!({
+            "current_password": current,
+            "password": "Str0ng!Pass"
+        }))
+        .expect_failure()
+        .await;
+    response.assert_status_bad_request();
+    assert!(response.text().contains("used recently"));
+}
+
 #[tokio::test]
 async fn update_other_users_password_as_non_admin_fails() {
     let app = TestApp::spawn().await;
@@ -301,6 +463,23 @@ async fn delete_user_as_admin_succeeds() {
     response.assert_status_not_found();
 }
 
+#[tokio::test]
+async fn delete_user_who_created_games_returns_conflict() {
+    let app = TestApp::spawn().await;
+    let bob = app.register("bob", "bob@example.com").await;
+    let bob_id = bob["id"].as_str().unwrap();
+    app.server.put("/api/games").await.assert_status_ok();
+
+    app.register_admin("admin", "admin@example.com").await;
+    let response = app
+        .server
+        .delete(&format!("/api/users/{bob_id}"))
+        .expect_failure()
+        .await;
+
+    response.assert_status(axum::http::StatusCode::CONFLICT);
+}
+
 #[tokio::test]
 async fn delete_user_as_non_admin_fails() {
     let app = TestApp::spawn().await;
@@ -330,3 +509,246 @@ async fn delete_nonexistent_user_returns_not_found() {
 
     response.assert_status_not_found();
 }
+
+// ==== Soft Delete ====
+
+#[tokio::test]
+async fn soft_deleted_user_cannot_be_found_by_username() {
+    let app = TestApp::spawn().await;
+    let user: serde_json::Value = app.register("alice", "alice@example.com").await;
+    let id: uuid::Uuid = user["id"].as_str().unwrap().parse().unwrap();
+
+    let repo = UserRepository::new(&app.db);
+    assert!(repo.soft_delete(id).await.unwrap());
+
+    assert!(repo.find_by_username("alice").await.unwrap().is_none());
+    assert!(repo.find_by_id(id).await.unwrap().is_none());
+    assert!(repo.find_all().await.unwrap().is_empty());
+
+    let response = app
+        .server
+        .post("/api/users/auth")
+        .json(&json!({
+            "username": "alice",
+            "password": "Str0ng!Pass"
+        }))
+        .expect_failure()
+        .await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn soft_deleted_user_row_is_retained() {
+    let app = TestApp::spawn().await;
+    let user: serde_json::Value = app.register("alice", "alice@example.com").await;
+    let id: uuid::Uuid = user["id"].as_str().unwrap().parse().unwrap();
+
+    let repo = UserRepository::new(&app.db);
+    repo.soft_delete(id).await.unwrap();
+
+    let deleted = repo
+        .find_by_id_including_deleted(id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(deleted.id, id);
+    assert_eq!(repo.find_all_including_deleted().await.unwrap().len(), 1);
+
+    // Deleting twice reports that nothing changed
+    assert!(!repo.soft_delete(id).await.unwrap());
+}
+
+#[tokio::test]
+async fn soft_deleted_user_is_anonymised() {
+    let app = TestApp::spawn().await;
+    let user: serde_json::Value = app.register("alice", "alice@example.com").await;
+    let id: uuid::Uuid = user["id"].as_str().unwrap().parse().unwrap();
+    app.server
+        .patch("/api/users/me/profile")
+        .json(&json!({ "display_name": "Alice the Bold" }))
+        .await
+        .assert_status_ok();
+
+    let repo = UserRepository::new(&app.db);
+    repo.soft_delete(id).await.unwrap();
+
+    let deleted = repo
+        .find_by_id_including_deleted(id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(deleted.username, format!("deleted-{id}"));
+    assert_eq!(deleted.email, format!("deleted-{id}"));
+    assert_eq!(deleted.display_name, None);
+    assert!(deleted.verify_password("Str0ng!Pass").is_err());
+}
+
+#[tokio::test]
+async fn soft_deleted_user_frees_username_and_email() {
+    let app = TestApp::spawn().await;
+    let user: serde_json::Value = app.register("alice", "alice@example.com").await;
+    let id: uuid::Uuid = user["id"].as_str().unwrap().parse().unwrap();
+
+    UserRepository::new(&app.db).soft_delete(id).await.unwrap();
+
+    let again: serde_json::Value = app.register("Alice", "alice@example.com").await;
+    assert_ne!(again["id"], user["id"]);
+}
+
+// ==== Delete Own Account ====
+
+#[tokio::test]
+async fn delete_me_removes_account_and_session() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .delete("/api/users/me")
+        .json(&json!({ "password": "Str0ng!Pass" }))
+        .await;
+    response.assert_status(axum::http::StatusCode::NO_CONTENT);
+
+    let cleared = response
+        .headers()
+        .get_all("set-cookie")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .filter(|cookie| cookie.contains("Max-Age=0"))
+        .count();
+    assert_eq!(cleared, 2, "expected both session cookies to be cleared");
+
+    let login = app
+        .server
+        .post("/api/users/auth")
+        .json(&json!({ "username": "alice", "password": "Str0ng!Pass" }))
+        .expect_failure()
+        .await;
+    login.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn delete_me_with_wrong_password_fails() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .delete("/api/users/me")
+        .json(&json!({ "password": "WrongPassword1!" }))
+        .expect_failure()
+        .await;
+    response.assert_status_bad_request();
+
+    app.server.get("/api/users/me").await.assert_status_ok();
+}
+
+#[tokio::test]
+async fn delete_me_unauthenticated_fails() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .delete("/api/users/me")
+        .json(&json!({ "password": "Str0ng!Pass" }))
+        .expect_failure()
+        .await;
+    response.assert_status_unauthorized();
+}
+
+// ==== Profile ====
+
+#[tokio::test]
+async fn update_profile_sets_display_name_used_in_games() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .patch("/api/users/me/profile")
+        .json(&json!({ "display_name": "Alice the Bold" }))
+        .await;
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["username"], "alice");
+    assert_eq!(body["display_name"], "Alice the Bold");
+
+    let created: serde_json::Value = app.server.put("/api/games").await.json();
+    let game_id = created["id"].as_str().unwrap();
+
+    let games: serde_json::Value = app.server.get("/api/games").await.json();
+    assert_eq!(games["items"][0]["creator"]["name"], "Alice the Bold");
+
+    let invite: serde_json::Value = app
+        .server
+        .post(&format!("/api/games/{game_id}/invite"))
+        .await
+        .json();
+    let game: serde_json::Value = app
+        .server
+        .post("/api/games/join-by-code")
+        .json(&json!({ "code": invite["code"] }))
+        .await
+        .json();
+    assert_eq!(game["players"][0]["name"], "Alice the Bold");
+}
+
+#[tokio::test]
+async fn update_profile_clearing_display_name_falls_back_to_username() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    app.server
+        .patch("/api/users/me/profile")
+        .json(&json!({ "display_name": "Alice the Bold" }))
+        .await;
+
+    let response = app
+        .server
+        .patch("/api/users/me/profile")
+        .json(&json!({ "display_name": null }))
+        .await;
+    let body: serde_json::Value = response.json();
+    assert!(body["display_name"].is_null());
+
+    app.server.put("/api/games").await;
+    let games: serde_json::Value = app.server.get("/api/games").await.json();
+    assert_eq!(games["items"][0]["creator"]["name"], "alice");
+}
+
+#[tokio::test]
+async fn update_profile_with_too_long_display_name_fails() {
+    let app = TestApp::spawn().await;
+    app.register("alice", "alice@example.com").await;
+
+    let response = app
+        .server
+        .patch("/api/users/me/profile")
+        .json(&json!({ "display_name": "a".repeat(51) }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_bad_request();
+}
+
+// ==== Rating ====
+
+#[tokio::test]
+async fn new_user_has_default_rating() {
+    let app = TestApp::spawn().await;
+    let user: serde_json::Value = app.register("alice", "alice@example.com").await;
+
+    assert_eq!(user["rating"], backend::rating::DEFAULT_RATING);
+}
+
+#[tokio::test]
+async fn update_rating_is_reflected_in_profile() {
+    let app = TestApp::spawn().await;
+    let user: serde_json::Value = app.register("alice", "alice@example.com").await;
+    let id: uuid::Uuid = user["id"].as_str().unwrap().parse().unwrap();
+
+    let repo = UserRepository::new(&app.db);
+    assert!(repo.update_rating(id, 1016.0).await.unwrap());
+
+    let me: serde_json::Value = app.server.get("/api/users/me").await.json();
+    assert_eq!(me["rating"], 1016.0);
+}
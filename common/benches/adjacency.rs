@@ -0,0 +1,59 @@
+//! Adjacency lookups on a 50-area world: the pairwise `is_adjacent` scan
+//! that `largest_connected_group` used to do, against the tile lookup in
+//! `adjacency_list` and the graph cached on the world.
+
+use common::World;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::collections::HashMap;
+use std::hint::black_box;
+use uuid::Uuid;
+
+const AREAS: usize = 50;
+
+/// A generated world with every area owned by one player, so connected
+/// group searches visit the whole map.
+fn world() -> (World, Uuid) {
+    let player_id = Uuid::from_u128(1);
+    let mut world = World::generate(42, 30, 30, AREAS);
+    for area in world.areas.values_mut() {
+        area.owner = Some(player_id);
+    }
+    (world, player_id)
+}
+
+/// Adjacency built by comparing every pair of areas.
+fn pairwise_adjacency(world: &World) -> HashMap<Uuid, Vec<Uuid>> {
+    world
+        .areas
+        .values()
+        .map(|area| {
+            let neighbours = world
+                .areas
+                .values()
+                .filter(|other| other.id != area.id && area.is_adjacent(other))
+                .map(|other| other.id)
+                .collect();
+            (area.id, neighbours)
+        })
+        .collect()
+}
+
+fn adjacency(c: &mut Criterion) {
+    let (world, player_id) = world();
+    assert_eq!(world.areas.len(), AREAS);
+
+    let mut group = c.benchmark_group("adjacency_50_areas");
+    group.bench_function("pairwise_is_adjacent", |b| {
+        b.iter(|| pairwise_adjacency(black_box(&world)))
+    });
+    group.bench_function("adjacency_list", |b| {
+        b.iter(|| black_box(&world).adjacency_list())
+    });
+    group.bench_function("largest_connected_group_cached", |b| {
+        b.iter(|| black_box(&world).largest_connected_group(player_id))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, adjacency);
+criterion_main!(benches);
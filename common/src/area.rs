@@ -1,39 +1,79 @@
-use crate::{Stack, Tile};
-use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use crate::{Stack, Terrain, Tile};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{HashSet, VecDeque};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Area {
     pub id: Uuid,
+    #[serde(default)]
+    pub name: Option<String>,
     pub owner: Option<Uuid>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub tiles: HashSet<Tile>,
     pub stack: Stack,
+    #[serde(default)]
+    pub terrain: Terrain,
+}
+
+/// Write tiles in sorted order so the same area always serializes to the
+/// same JSON.
+fn serialize_sorted<S: Serializer>(
+    tiles: &HashSet<Tile>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut sorted: Vec<&Tile> = tiles.iter().collect();
+    sorted.sort();
+    serializer.collect_seq(sorted)
 }
 
 impl Area {
     pub fn new(tiles: HashSet<Tile>) -> Self {
         Self {
             id: Uuid::new_v4(),
+            name: None,
             owner: None,
             tiles,
             stack: Stack::default(),
+            terrain: Terrain::default(),
         }
     }
 
+    pub fn with_stack(mut self, stack: Stack) -> Self {
+        self.stack = stack;
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Centroid of the area's tiles in world coordinates, or the origin for
+    /// an area without tiles.
+    ///
+    /// Tiles are summed as integer axial coordinates and divided once at
+    /// the end, so large areas far from the origin stay exact.
     pub fn center(&self) -> (f32, f32) {
-        let (sum_x, sum_y): (f32, f32) = self
-            .tiles
-            .iter()
-            .map(|tile| tile.to_world_coordinates())
-            .fold((0.0, 0.0), |(acc_x, acc_y), (x, y)| (acc_x + x, acc_y + y));
-
-        let count = self.tiles.len() as f32;
-        if count > 0.0 {
-            (sum_x / count, sum_y / count)
-        } else {
-            (0.0, 0.0)
+        if self.tiles.is_empty() {
+            return (0.0, 0.0);
         }
+
+        let (mut sum_q, mut sum_r, mut odd_columns) = (0i64, 0i64, 0i64);
+        for tile in &self.tiles {
+            let (q, r) = tile.to_axial();
+            sum_q += i64::from(q);
+            sum_r += i64::from(r);
+            odd_columns += i64::from(q & 1);
+        }
+
+        let count = self.tiles.len() as f64;
+        Tile::centroid_to_world_coordinates(
+            sum_q as f64 / count,
+            sum_r as f64 / count,
+            odd_columns as f64 / count,
+        )
     }
 
     pub fn is_owned_by(&self, player_id: Uuid) -> bool {
@@ -44,6 +84,26 @@ impl Area {
         self.owner.is_none()
     }
 
+    /// An area is valid when it has at least one tile and all of its tiles
+    /// form a single connected region.
+    pub fn is_valid(&self) -> bool {
+        let Some(start) = self.tiles.iter().next() else {
+            return false;
+        };
+
+        let mut visited = HashSet::from([*start]);
+        let mut queue = VecDeque::from([*start]);
+        while let Some(tile) = queue.pop_front() {
+            for neighbor in tile.neighbors().into_iter().flatten() {
+                if self.tiles.contains(&neighbor) && visited.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        visited.len() == self.tiles.len()
+    }
+
     pub fn is_adjacent(&self, other: &Area) -> bool {
         self.tiles.iter().any(|tile| {
             other
@@ -68,6 +128,73 @@ mod tests {
         let area = area_with_tiles(&[(0, 0)]);
         assert!(area.owner.is_none());
         assert_eq!(area.stack.count(), Stack::MIN);
+        assert_eq!(area.terrain, Terrain::Plains);
+    }
+
+    #[test]
+    fn is_valid_single_tile() {
+        assert!(area_with_tiles(&[(0, 0)]).is_valid());
+    }
+
+    #[test]
+    fn is_valid_adjacent_tiles() {
+        assert!(area_with_tiles(&[(0, 0), (0, 1), (1, 1)]).is_valid());
+    }
+
+    #[test]
+    fn is_valid_rejects_disconnected_tiles() {
+        assert!(!area_with_tiles(&[(0, 0), (0, 2)]).is_valid());
+    }
+
+    #[test]
+    fn is_valid_rejects_empty_area() {
+        assert!(!Area::new(HashSet::new()).is_valid());
+    }
+
+    #[test]
+    fn with_stack_sets_stack() {
+        let area = area_with_tiles(&[(0, 0)]).with_stack(Stack::new(5).unwrap());
+        assert_eq!(area.stack.count(), 5);
+    }
+
+    #[test]
+    fn with_name_sets_name() {
+        let area = area_with_tiles(&[(0, 0)]).with_name("Northern Plains");
+        assert_eq!(area.name.as_deref(), Some("Northern Plains"));
+    }
+
+    #[test]
+    fn name_survives_serde_roundtrip() {
+        let area = area_with_tiles(&[(0, 0)]).with_name("Forest of Doom");
+        let json = serde_json::to_string(&area).unwrap();
+        let deser: Area = serde_json::from_str(&json).unwrap();
+        assert_eq!(deser.name.as_deref(), Some("Forest of Doom"));
+    }
+
+    #[test]
+    fn serialization_is_deterministic() {
+        let coords: Vec<(usize, usize)> =
+            (0..4).flat_map(|x| (0..4).map(move |y| (x, y))).collect();
+        let area = area_with_tiles(&coords);
+
+        let first = serde_json::to_string(&area).unwrap();
+        let second = serde_json::to_string(&area).unwrap();
+        assert_eq!(first, second);
+
+        // The same tiles inserted in another order give the same JSON
+        let mut reordered = area_with_tiles(&coords.iter().rev().copied().collect::<Vec<_>>());
+        reordered.id = area.id;
+        assert_eq!(serde_json::to_string(&reordered).unwrap(), first);
+    }
+
+    #[test]
+    fn tiles_serialize_sorted_by_column_then_row() {
+        let area = area_with_tiles(&[(1, 0), (0, 1), (0, 0)]);
+        let json = serde_json::to_value(&area).unwrap();
+        assert_eq!(
+            json["tiles"],
+            serde_json::json!([{ "x": 0, "y": 0 }, { "x": 0, "y": 1 }, { "x": 1, "y": 0 }])
+        );
     }
 
     #[test]
@@ -76,6 +203,90 @@ mod tests {
         assert_eq!(area.center(), (0.0, 0.0));
     }
 
+    /// The centroid as computed before, averaging world coordinates in f32.
+    fn world_average_center(area: &Area) -> (f32, f32) {
+        // Sum in a fixed order, f32 rounding depends on it
+        let mut tiles: Vec<&Tile> = area.tiles.iter().collect();
+        tiles.sort();
+        let (sum_x, sum_y) = tiles
+            .into_iter()
+            .map(Tile::to_world_coordinates)
+            .fold((0.0f32, 0.0f32), |(x, y), (wx, wy)| (x + wx, y + wy));
+        let count = area.tiles.len() as f32;
+        (sum_x / count, sum_y / count)
+    }
+
+    /// The centroid in exact f64 arithmetic, for measuring errors.
+    fn exact_center(area: &Area) -> (f64, f64) {
+        let (sum_x, sum_y) = area
+            .tiles
+            .iter()
+            .map(Tile::to_world_coordinates)
+            .fold((0.0f64, 0.0f64), |(x, y), (wx, wy)| {
+                (x + f64::from(wx), y + f64::from(wy))
+            });
+        let count = area.tiles.len() as f64;
+        (sum_x / count, sum_y / count)
+    }
+
+    fn assert_center_close(actual: (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-5 && (actual.1 - expected.1).abs() < 1e-5,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn center_of_single_tile_is_its_world_coordinates() {
+        for (x, y) in [(0, 0), (1, 0), (2, 3), (7, 4)] {
+            let area = area_with_tiles(&[(x, y)]);
+            assert_center_close(area.center(), Tile::new(x, y).to_world_coordinates());
+        }
+    }
+
+    #[test]
+    fn center_matches_world_average_for_two_tiles() {
+        let area = area_with_tiles(&[(0, 0), (1, 0)]);
+        assert_center_close(area.center(), world_average_center(&area));
+        assert_center_close(area.center(), (1.0, 0.75));
+    }
+
+    #[test]
+    fn center_matches_world_average_for_ten_tiles() {
+        let area = area_with_tiles(&[
+            (2, 2),
+            (2, 3),
+            (3, 2),
+            (3, 3),
+            (4, 1),
+            (4, 2),
+            (5, 2),
+            (5, 3),
+            (6, 2),
+            (6, 3),
+        ]);
+        assert_center_close(area.center(), world_average_center(&area));
+    }
+
+    #[test]
+    fn center_of_large_distant_area_has_less_error() {
+        // Far from the origin the f32 sums of world coordinates lose the
+        // half tile offsets, while integer axial sums stay exact
+        let coords: Vec<(usize, usize)> = (200_000..200_010)
+            .flat_map(|x| (300_000..300_010).map(move |y| (x, y)))
+            .collect();
+        let area = area_with_tiles(&coords);
+        let (exact_x, exact_y) = exact_center(&area);
+        let error =
+            |(x, y): (f32, f32)| (f64::from(x) - exact_x).abs() + (f64::from(y) - exact_y).abs();
+
+        let new_error = error(area.center());
+        let old_error = error(world_average_center(&area));
+
+        assert!(new_error < old_error, "{new_error} >= {old_error}");
+        assert_eq!(new_error, 0.0);
+    }
+
     #[test]
     fn is_owned_by_and_is_not_owned_work() {
         let player_id = Uuid::new_v4();
@@ -6,6 +6,7 @@ pub const MAX_PLAYERS: usize = 6;
 /// Player colors. Each variant has a fixed numeric index (`#[repr(usize)]`)
 /// and a hex color value accessible via [`Color::to_hex`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[repr(usize)]
 pub enum Color {
     Red = 0,
@@ -28,7 +29,7 @@ impl Color {
     ];
 
     /// Returns the hex color string (e.g. `"#FF0000"` for `Red`).
-    pub fn to_hex(self) -> &'static str {
+    pub const fn to_hex(self) -> &'static str {
         match self {
             Color::Red => "#FF0000",
             Color::Green => "#00CC44",
@@ -38,6 +39,36 @@ impl Color {
             Color::Orange => "#FF8800",
         }
     }
+
+    /// Parse a hex color string such as `"#FF0000"` back into the matching
+    /// color. The `#` is optional and letters may be in either case.
+    pub fn from_hex(s: &str) -> Result<Color, ColorError> {
+        let digits = s.strip_prefix('#').unwrap_or(s);
+        Color::ALL
+            .into_iter()
+            .find(|color| color.to_hex()[1..].eq_ignore_ascii_case(digits))
+            .ok_or_else(|| ColorError::UnknownHex(s.to_string()))
+    }
+
+    /// The red, green and blue components of [`Color::to_hex`].
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        let hex = &self.to_hex()[1..];
+        let component =
+            |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).expect("color hex values are valid");
+        (component(0), component(2), component(4))
+    }
+
+    /// Human-readable name of the color, e.g. `"Red"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Color::Red => "Red",
+            Color::Green => "Green",
+            Color::Blue => "Blue",
+            Color::Yellow => "Yellow",
+            Color::Purple => "Purple",
+            Color::Orange => "Orange",
+        }
+    }
 }
 
 impl From<Color> for usize {
@@ -67,6 +98,8 @@ impl TryFrom<usize> for Color {
 pub enum ColorError {
     #[error("invalid color index {0}, expected 0–5")]
     InvalidIndex(usize),
+    #[error("unknown color {0}")]
+    UnknownHex(String),
 }
 
 #[cfg(test)]
@@ -85,6 +118,53 @@ mod tests {
         assert_eq!(Color::Orange.to_hex(), "#FF8800");
     }
 
+    // ==== Color::from_hex ====
+
+    #[test]
+    fn from_hex_accepts_every_known_color() {
+        for color in Color::ALL {
+            assert_eq!(Color::from_hex(color.to_hex()).unwrap(), color);
+        }
+    }
+
+    #[test]
+    fn from_hex_ignores_case_and_missing_hash() {
+        assert_eq!(Color::from_hex("00cc44").unwrap(), Color::Green);
+        assert_eq!(Color::from_hex("#ff8800").unwrap(), Color::Orange);
+        assert_eq!(Color::from_hex("3366Ff").unwrap(), Color::Blue);
+    }
+
+    #[test]
+    fn from_hex_rejects_unknown_colors() {
+        for hex in ["#123456", "", "#", "#FF00", "FF0000FF"] {
+            let err = Color::from_hex(hex).unwrap_err();
+            assert!(matches!(&err, ColorError::UnknownHex(s) if s == hex));
+        }
+    }
+
+    // ==== Color::to_rgb ====
+
+    #[test]
+    fn to_rgb_returns_correct_values() {
+        assert_eq!(Color::Red.to_rgb(), (0xFF, 0x00, 0x00));
+        assert_eq!(Color::Green.to_rgb(), (0x00, 0xCC, 0x44));
+        assert_eq!(Color::Blue.to_rgb(), (0x33, 0x66, 0xFF));
+        assert_eq!(Color::Yellow.to_rgb(), (0xFF, 0xDD, 0x00));
+        assert_eq!(Color::Purple.to_rgb(), (0x99, 0x33, 0xFF));
+        assert_eq!(Color::Orange.to_rgb(), (0xFF, 0x88, 0x00));
+    }
+
+    // ==== Color::name ====
+
+    #[test]
+    fn name_matches_variant() {
+        let names: Vec<&str> = Color::ALL.iter().map(|color| color.name()).collect();
+        assert_eq!(
+            names,
+            ["Red", "Green", "Blue", "Yellow", "Purple", "Orange"]
+        );
+    }
+
     // ==== Numeric conversions ====
 
     #[test]
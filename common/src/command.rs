@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Version of the game WebSocket protocol. Bump it on every change that
+/// breaks existing clients, such as renamed fields or removed events.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// A command sent by a client over the game's WebSocket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameCommand {
+    Start,
+    Attack {
+        from_id: Uuid,
+        to_id: Uuid,
+    },
+    EndTurn,
+    /// Give up the game. The player's areas are left unowned.
+    Resign,
+    JoinAsSpectator,
+    /// Send a chat message to everyone in the game.
+    Chat {
+        message: String,
+    },
+    Ping,
+    /// The protocol version the client speaks. The server closes the socket
+    /// if it does not match [`PROTOCOL_VERSION`].
+    ProtocolVersion {
+        version: u32,
+    },
+}
+
+impl GameCommand {
+    /// Whether a spectator may send this command.
+    pub fn is_allowed_for_spectator(&self) -> bool {
+        matches!(
+            self,
+            GameCommand::JoinAsSpectator
+                | GameCommand::Chat { .. }
+                | GameCommand::Ping
+                | GameCommand::ProtocolVersion { .. }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_with_snake_case_tag() {
+        let json = serde_json::to_value(GameCommand::EndTurn).unwrap();
+        assert_eq!(json["type"], "end_turn");
+    }
+
+    #[test]
+    fn protocol_version_deserializes() {
+        let command: GameCommand =
+            serde_json::from_str(r#"{"type":"protocol_version","version":1}"#).unwrap();
+        assert_eq!(command, GameCommand::ProtocolVersion { version: 1 });
+    }
+
+    #[test]
+    fn spectators_may_only_join_chat_ping_and_send_version() {
+        assert!(GameCommand::Ping.is_allowed_for_spectator());
+        assert!(
+            GameCommand::ProtocolVersion {
+                version: PROTOCOL_VERSION
+            }
+            .is_allowed_for_spectator()
+        );
+        assert!(GameCommand::JoinAsSpectator.is_allowed_for_spectator());
+        assert!(
+            GameCommand::Chat {
+                message: "hi".to_string()
+            }
+            .is_allowed_for_spectator()
+        );
+        assert!(!GameCommand::EndTurn.is_allowed_for_spectator());
+        assert!(!GameCommand::Resign.is_allowed_for_spectator());
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{MAX_PLAYERS, Stack};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Default for [`GameConfig::reconnect_grace_period_seconds`].
+pub const DEFAULT_RECONNECT_GRACE_PERIOD_SECONDS: u64 = 30;
+
+/// Default for [`GameConfig::bonus_dice_cap`].
+pub const DEFAULT_BONUS_DICE_CAP: usize = 20;
+
+/// Fewest players a game can be started with.
+pub const MIN_PLAYERS: usize = 2;
+
+/// Default for [`GameConfig::idle_timeout_seconds`].
+pub const DEFAULT_IDLE_TIMEOUT_SECONDS: u64 = 300;
+
+/// Longest [`GameConfig::idle_timeout_seconds`] a game may be created with.
+pub const MAX_IDLE_TIMEOUT_SECONDS: u64 = 24 * 60 * 60;
+
+/// Longest [`GameConfig::reconnect_grace_period_seconds`] a game may be
+/// created with.
+pub const MAX_RECONNECT_GRACE_PERIOD_SECONDS: u64 = 60 * 60;
+
+/// Shortest [`GameConfig::turn_time_limit`] a game may be created with.
+pub const MIN_TURN_TIME_LIMIT: Duration = Duration::from_secs(5);
+
+/// Longest [`GameConfig::turn_time_limit`] a game may be created with.
+pub const MAX_TURN_TIME_LIMIT: Duration = Duration::from_secs(60 * 60);
+
+/// Reasons a [`GameConfig`] is rejected by [`GameConfig::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConfigError {
+    #[error("max_players must be between {MIN_PLAYERS} and {MAX_PLAYERS}")]
+    MaxPlayersOutOfRange,
+
+    #[error("min_players must be between {MIN_PLAYERS} and max_players")]
+    MinPlayersOutOfRange,
+
+    #[error("max_dice_per_area must be between 1 and {}", Stack::MAX)]
+    MaxDiceOutOfRange,
+
+    #[error("idle_timeout_seconds must be between 1 and {MAX_IDLE_TIMEOUT_SECONDS}")]
+    IdleTimeoutOutOfRange,
+
+    #[error(
+        "reconnect_grace_period_seconds must be between 1 and {MAX_RECONNECT_GRACE_PERIOD_SECONDS}"
+    )]
+    ReconnectGracePeriodOutOfRange,
+
+    #[error(
+        "turn_time_limit must be between {} and {} seconds",
+        MIN_TURN_TIME_LIMIT.as_secs(),
+        MAX_TURN_TIME_LIMIT.as_secs()
+    )]
+    TurnTimeLimitOutOfRange,
+}
+
+/// Settings chosen when a game is created.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct GameConfig {
+    /// Whether users may watch the game without taking part in it.
+    pub allow_spectators: bool,
+    /// Most players that may join, capped at [`MAX_PLAYERS`].
+    pub max_players: usize,
+    /// Fewest players needed to start the game, at least [`MIN_PLAYERS`].
+    pub min_players: usize,
+    /// Most dice a single area may hold, capped at [`Stack::MAX`].
+    pub max_dice_per_area: usize,
+    /// How long a game in progress may go without any moves before it is
+    /// ended.
+    pub idle_timeout_seconds: u64,
+    /// How long a player may take before their turn is ended for them.
+    /// `None` means turns never time out.
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<Object>))]
+    pub turn_time_limit: Option<Duration>,
+    /// Whether players only see dice counts of areas they own or border.
+    pub fog_of_war: bool,
+    /// How long a player whose connection dropped still counts as present,
+    /// giving them time to reconnect before their turns are skipped.
+    pub reconnect_grace_period_seconds: u64,
+    /// Seed for the game's dice. `None` picks a random seed; set it to make
+    /// a game reproducible. Never serialized, so clients can neither read
+    /// nor choose it.
+    #[serde(skip)]
+    pub seed: Option<u64>,
+    /// Most bonus dice a player keeps in reserve when all their areas are
+    /// full.
+    pub bonus_dice_cap: usize,
+}
+
+impl GameConfig {
+    pub fn reconnect_grace_period(&self) -> Duration {
+        Duration::from_secs(self.reconnect_grace_period_seconds)
+    }
+
+    pub fn idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.idle_timeout_seconds)
+    }
+
+    /// Check that the settings describe a game that can be played: player
+    /// bounds that can be met and durations that are neither zero nor
+    /// absurdly long.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&self.max_players) {
+            return Err(ConfigError::MaxPlayersOutOfRange);
+        }
+        if !(MIN_PLAYERS..=self.max_players).contains(&self.min_players) {
+            return Err(ConfigError::MinPlayersOutOfRange);
+        }
+        if !(1..=Stack::MAX).contains(&self.max_dice_per_area) {
+            return Err(ConfigError::MaxDiceOutOfRange);
+        }
+        if !(1..=MAX_IDLE_TIMEOUT_SECONDS).contains(&self.idle_timeout_seconds) {
+            return Err(ConfigError::IdleTimeoutOutOfRange);
+        }
+        if !(1..=MAX_RECONNECT_GRACE_PERIOD_SECONDS).contains(&self.reconnect_grace_period_seconds)
+        {
+            return Err(ConfigError::ReconnectGracePeriodOutOfRange);
+        }
+        if let Some(limit) = self.turn_time_limit
+            && !(MIN_TURN_TIME_LIMIT..=MAX_TURN_TIME_LIMIT).contains(&limit)
+        {
+            return Err(ConfigError::TurnTimeLimitOutOfRange);
+        }
+        Ok(())
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            allow_spectators: true,
+            max_players: MAX_PLAYERS,
+            min_players: MIN_PLAYERS,
+            max_dice_per_area: Stack::MAX,
+            idle_timeout_seconds: DEFAULT_IDLE_TIMEOUT_SECONDS,
+            turn_time_limit: None,
+            fog_of_war: false,
+            reconnect_grace_period_seconds: DEFAULT_RECONNECT_GRACE_PERIOD_SECONDS,
+            seed: None,
+            bonus_dice_cap: DEFAULT_BONUS_DICE_CAP,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_allows_spectators() {
+        assert!(GameConfig::default().allow_spectators);
+    }
+
+    #[test]
+    fn default_allows_max_players() {
+        assert_eq!(GameConfig::default().max_players, MAX_PLAYERS);
+    }
+
+    #[test]
+    fn default_rules_match_constants() {
+        let config = GameConfig::default();
+        assert_eq!(config.min_players, MIN_PLAYERS);
+        assert_eq!(config.max_dice_per_area, Stack::MAX);
+        assert_eq!(
+            config.idle_timeout(),
+            Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECONDS)
+        );
+    }
+
+    #[test]
+    fn default_has_no_turn_time_limit() {
+        assert_eq!(GameConfig::default().turn_time_limit, None);
+    }
+
+    #[test]
+    fn default_has_no_fog_of_war() {
+        assert!(!GameConfig::default().fog_of_war);
+    }
+
+    #[test]
+    fn default_has_reconnect_grace_period() {
+        let config = GameConfig::default();
+        assert_eq!(
+            config.reconnect_grace_period(),
+            Duration::from_secs(DEFAULT_RECONNECT_GRACE_PERIOD_SECONDS)
+        );
+    }
+
+    #[test]
+    fn default_has_no_seed() {
+        assert_eq!(GameConfig::default().seed, None);
+    }
+
+    #[test]
+    fn seed_is_neither_serialized_nor_deserialized() {
+        let config = GameConfig {
+            seed: Some(42),
+            ..GameConfig::default()
+        };
+        let value = serde_json::to_value(&config).unwrap();
+        assert!(value.get("seed").is_none());
+
+        let config: GameConfig = serde_json::from_str(r#"{"seed": 42}"#).unwrap();
+        assert_eq!(config.seed, None);
+    }
+
+    #[test]
+    fn default_bonus_dice_cap() {
+        assert_eq!(GameConfig::default().bonus_dice_cap, DEFAULT_BONUS_DICE_CAP);
+    }
+
+    #[test]
+    fn missing_fields_deserialize_to_defaults() {
+        let config: GameConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, GameConfig::default());
+    }
+
+    #[test]
+    fn default_is_valid() {
+        assert_eq!(GameConfig::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_inconsistent_player_bounds() {
+        let config = GameConfig {
+            min_players: 4,
+            max_players: 3,
+            ..GameConfig::default()
+        };
+        assert_eq!(config.validate(), Err(ConfigError::MinPlayersOutOfRange));
+
+        let config = GameConfig {
+            max_players: MAX_PLAYERS + 1,
+            ..GameConfig::default()
+        };
+        assert_eq!(config.validate(), Err(ConfigError::MaxPlayersOutOfRange));
+    }
+
+    #[test]
+    fn validate_rejects_zero_durations() {
+        let config = GameConfig {
+            turn_time_limit: Some(Duration::ZERO),
+            ..GameConfig::default()
+        };
+        assert_eq!(config.validate(), Err(ConfigError::TurnTimeLimitOutOfRange));
+
+        let config = GameConfig {
+            idle_timeout_seconds: 0,
+            ..GameConfig::default()
+        };
+        assert_eq!(config.validate(), Err(ConfigError::IdleTimeoutOutOfRange));
+    }
+
+    #[test]
+    fn validate_rejects_absurd_values() {
+        let config = GameConfig {
+            reconnect_grace_period_seconds: u64::MAX,
+            ..GameConfig::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::ReconnectGracePeriodOutOfRange)
+        );
+
+        let config = GameConfig {
+            max_dice_per_area: Stack::MAX + 1,
+            ..GameConfig::default()
+        };
+        assert_eq!(config.validate(), Err(ConfigError::MaxDiceOutOfRange));
+    }
+
+    #[test]
+    fn serialize_deserialize_roundtrip() {
+        let config = GameConfig {
+            allow_spectators: false,
+            max_players: 4,
+            min_players: 3,
+            max_dice_per_area: 4,
+            idle_timeout_seconds: 60,
+            turn_time_limit: Some(Duration::from_secs(30)),
+            fog_of_war: true,
+            reconnect_grace_period_seconds: 5,
+            seed: None,
+            bonus_dice_cap: 5,
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let deser: GameConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config, deser);
+    }
+}
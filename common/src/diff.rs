@@ -0,0 +1,163 @@
+use crate::{Area, Game, GameState, Player, SpectatorInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// The parts of a [`Game`] that changed between two snapshots. Fields that
+/// did not change are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameDiff {
+    /// New state of every area that changed, keyed by area ID.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub areas: HashMap<Uuid, Area>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub players: Option<Vec<Player>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spectators: Option<Vec<SpectatorInfo>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<GameState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_number: Option<u32>,
+}
+
+impl GameDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == GameDiff::default()
+    }
+}
+
+impl Game {
+    /// What changed in this game since `previous`.
+    pub fn diff_from(&self, previous: &Game) -> GameDiff {
+        let areas = self
+            .world
+            .areas
+            .iter()
+            .filter(|(id, area)| previous.world.areas.get(*id) != Some(*area))
+            .map(|(id, area)| (*id, area.clone()))
+            .collect();
+
+        GameDiff {
+            areas,
+            players: (self.players != previous.players).then(|| self.players.clone()),
+            spectators: (self.spectators != previous.spectators).then(|| self.spectators.clone()),
+            state: (self.state != previous.state).then(|| self.state.clone()),
+            turn_number: (self.turn_number != previous.turn_number).then_some(self.turn_number),
+        }
+    }
+
+    /// Bring this game up to date with a diff produced by [`Game::diff_from`].
+    pub fn apply_diff(&mut self, diff: GameDiff) {
+        self.world.areas.extend(diff.areas);
+        if let Some(players) = diff.players {
+            self.players = players;
+        }
+        if let Some(spectators) = diff.spectators {
+            self.spectators = spectators;
+        }
+        if let Some(state) = diff.state {
+            self.state = state;
+        }
+        if let Some(turn_number) = diff.turn_number {
+            self.turn_number = turn_number;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tile, World};
+    use std::collections::HashSet;
+
+    /// A game on a world with `n` single-tile areas in a column.
+    fn game_with_areas(n: usize) -> Game {
+        let areas = (0..n)
+            .map(|y| Area::new(HashSet::from([Tile::new(0, y)])))
+            .map(|area| (area.id, area))
+            .collect();
+        Game::new(World {
+            areas,
+            ..World::default()
+        })
+    }
+
+    #[test]
+    fn identical_games_have_empty_diff() {
+        let game = game_with_areas(3);
+        assert!(game.diff_from(&game.clone()).is_empty());
+    }
+
+    #[test]
+    fn single_area_update_has_one_entry() {
+        let previous = game_with_areas(3);
+        let mut game = previous.clone();
+        let id = *game.world.areas.keys().next().unwrap();
+        game.world
+            .areas
+            .get_mut(&id)
+            .unwrap()
+            .stack
+            .increment()
+            .unwrap();
+
+        let diff = game.diff_from(&previous);
+        assert_eq!(diff.areas.len(), 1);
+        assert_eq!(diff.areas[&id].stack.count(), 2);
+        assert!(diff.players.is_none());
+        assert!(diff.state.is_none());
+    }
+
+    #[test]
+    fn state_change_is_included() {
+        let previous = game_with_areas(2);
+        let mut game = previous.clone();
+        game.state = GameState::InProgress { turn: 0 };
+
+        let diff = game.diff_from(&previous);
+        assert!(diff.areas.is_empty());
+        assert_eq!(diff.state, Some(GameState::InProgress { turn: 0 }));
+    }
+
+    #[test]
+    fn applying_diffs_in_order_reconstructs_game() {
+        let first = game_with_areas(3);
+        let ids: Vec<Uuid> = first.world.areas.keys().copied().collect();
+
+        let mut second = first.clone();
+        second.join_player(Uuid::new_v4(), "Alice".into()).unwrap();
+        let alice = second.players[0].id;
+        second.world.areas.get_mut(&ids[0]).unwrap().owner = Some(alice);
+
+        let mut third = second.clone();
+        third.state = GameState::InProgress { turn: 0 };
+        third.turn_number = 1;
+        third
+            .world
+            .areas
+            .get_mut(&ids[1])
+            .unwrap()
+            .stack
+            .add_n(3)
+            .unwrap();
+
+        let mut client = first.clone();
+        client.apply_diff(second.diff_from(&first));
+        client.apply_diff(third.diff_from(&second));
+
+        assert_eq!(
+            serde_json::to_value(&client).unwrap(),
+            serde_json::to_value(&third).unwrap()
+        );
+    }
+
+    #[test]
+    fn unchanged_fields_are_not_serialized() {
+        let previous = game_with_areas(2);
+        let mut game = previous.clone();
+        game.state = GameState::Finished;
+
+        let json = serde_json::to_value(game.diff_from(&previous)).unwrap();
+        assert_eq!(json, serde_json::json!({ "state": "Finished" }));
+    }
+}
@@ -1,8 +1,17 @@
-use crate::{Color, ColorError, MAX_PLAYERS, StackError};
+use crate::{
+    Color, ColorError, GameConfig, MAX_PLAYERS, MIN_PLAYERS, SpectatorInfo, Stack, StackError,
+};
 
 use super::{Player, World};
-use rand::random_range;
+use rand::{
+    Rng, SeedableRng, TryRng,
+    distr::{Distribution, Uniform},
+    rand_core::utils,
+    rngs::SmallRng,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::convert::Infallible;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -25,6 +34,9 @@ pub enum AttackError {
 
     #[error("it's not the player's turn")]
     NotPlayerTurn,
+
+    #[error("area with ID {0} cannot be owned")]
+    AreaNotOwnable(Uuid),
 }
 
 /// Errors related to [`Game`] operations.
@@ -36,6 +48,9 @@ pub enum GameError {
     #[error("player is already in the game")]
     PlayerAlreadyInGame,
 
+    #[error("player is not in the game")]
+    PlayerNotInGame,
+
     #[error("it's not the player's turn")]
     NotPlayerTurn,
 
@@ -51,6 +66,12 @@ pub enum GameError {
     #[error("not enough players to start the game")]
     NotEnoughPlayers,
 
+    #[error("spectators are not allowed in this game")]
+    SpectatorsNotAllowed,
+
+    #[error("spectators cannot perform this action")]
+    SpectatorActionNotAllowed,
+
     #[error("Invalid turn")]
     InvalidTurn,
 
@@ -66,7 +87,89 @@ pub enum GameError {
 
 type Result<T> = std::result::Result<T, GameError>;
 
+/// The dice totals rolled during a single attack, whether the attacker
+/// captured the target area and the dice left on both areas afterwards.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AttackResult {
+    pub attack_roll: usize,
+    pub defence_roll: usize,
+    pub captured: bool,
+    /// Dice left on the attacking area.
+    #[serde(default)]
+    pub attacker_remaining_dice: usize,
+    /// Dice on the target area, which the attacker owns if it was captured.
+    #[serde(default)]
+    pub defender_remaining_dice: usize,
+}
+
+impl AttackResult {
+    /// The result as seen by a viewer under fog of war, with `0` for every
+    /// dice count they may not see, like [`Stack::hidden`]. Rolls are only
+    /// hidden if neither area is visible, since either side of the fight
+    /// shows how it went.
+    pub fn fogged(self, from_visible: bool, to_visible: bool) -> Self {
+        let hide_rolls = !from_visible && !to_visible;
+        Self {
+            attack_roll: if hide_rolls { 0 } else { self.attack_roll },
+            defence_roll: if hide_rolls { 0 } else { self.defence_roll },
+            captured: self.captured,
+            attacker_remaining_dice: if from_visible {
+                self.attacker_remaining_dice
+            } else {
+                0
+            },
+            defender_remaining_dice: if to_visible {
+                self.defender_remaining_dice
+            } else {
+                0
+            },
+        }
+    }
+}
+
+/// A single move made by a player, in the order it was played. `turn` is
+/// the game's [`Game::turn_number`] when the move was made.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MoveRecord {
+    Attack {
+        #[serde(default)]
+        turn: u32,
+        player_id: Uuid,
+        from_id: Uuid,
+        to_id: Uuid,
+        result: AttackResult,
+    },
+    EndTurn {
+        #[serde(default)]
+        turn: u32,
+        player_id: Uuid,
+        /// Bonus dice placed on the board at the end of the turn.
+        #[serde(default)]
+        bonus_dice: usize,
+    },
+    Resign {
+        #[serde(default)]
+        turn: u32,
+        player_id: Uuid,
+    },
+}
+
+impl MoveRecord {
+    /// The turn the move was made in.
+    pub fn turn(&self) -> u32 {
+        match self {
+            MoveRecord::Attack { turn, .. }
+            | MoveRecord::EndTurn { turn, .. }
+            | MoveRecord::Resign { turn, .. } => *turn,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum GameState {
     WaitingForPlayers,
     InProgress { turn: usize },
@@ -74,31 +177,123 @@ pub enum GameState {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Game {
     pub id: Uuid,
     pub world: World,
     pub players: Vec<Player>,
+    #[serde(default)]
+    pub spectators: Vec<SpectatorInfo>,
     pub state: GameState,
+    /// Number of turns ended so far, starting at 0.
+    #[serde(default)]
+    pub turn_number: u32,
+    #[serde(default)]
+    pub config: GameConfig,
+    /// Seed of the game's dice, from [`GameConfig::seed`] if set. Not
+    /// serialized, since anyone who knows it can predict every roll; the
+    /// backend stores it next to the snapshot.
+    #[serde(skip, default = "rand::random")]
+    pub seed: u64,
+    /// Source of all randomness in the game, created from `seed` on first
+    /// use. Restored games continue it with [`Game::restore_dice`].
+    #[serde(skip)]
+    rng: Option<DiceRng>,
+    /// Every move played so far. Not part of the serialized game, which is
+    /// sent to clients on every change; the backend stores moves separately.
+    #[serde(skip)]
+    move_log: Vec<MoveRecord>,
+}
+
+/// A seeded RNG that counts the words drawn from it, so it can be
+/// recreated at the same position from its seed and the count.
+#[derive(Debug, Clone)]
+struct DiceRng {
+    rng: SmallRng,
+    draws: u64,
+}
+
+impl DiceRng {
+    /// The RNG seeded with `seed`, advanced past its first `draws` words.
+    fn new(seed: u64, draws: u64) -> Self {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        for _ in 0..draws {
+            rng.next_u64();
+        }
+        Self { rng, draws }
+    }
+}
+
+impl TryRng for DiceRng {
+    type Error = Infallible;
+
+    fn try_next_u32(&mut self) -> std::result::Result<u32, Infallible> {
+        // Use the upper half of a whole word, so every draw advances the
+        // generator by exactly one word
+        self.try_next_u64().map(|word| (word >> 32) as u32)
+    }
+
+    fn try_next_u64(&mut self) -> std::result::Result<u64, Infallible> {
+        self.draws += 1;
+        Ok(self.rng.next_u64())
+    }
+
+    fn try_fill_bytes(&mut self, dst: &mut [u8]) -> std::result::Result<(), Infallible> {
+        utils::fill_bytes_via_next_word(dst, || self.try_next_u64())
+    }
+}
+
+/// The dice RNG of a game, seeding it on first use.
+fn dice_rng(rng: &mut Option<DiceRng>, seed: u64) -> &mut DiceRng {
+    rng.get_or_insert_with(|| DiceRng::new(seed, 0))
 }
 
 impl Game {
     pub fn new(world: World) -> Self {
+        Self::with_config(world, GameConfig::default())
+    }
+
+    pub fn with_config(world: World, config: GameConfig) -> Self {
         Self {
             id: Uuid::new_v4(),
             world,
             players: Vec::new(),
+            spectators: Vec::new(),
             state: GameState::WaitingForPlayers,
+            turn_number: 0,
+            seed: config.seed.unwrap_or_else(rand::random),
+            config,
+            rng: None,
+            move_log: Vec::new(),
         }
     }
 
+    /// Every move played in this game, oldest first.
+    pub fn move_log(&self) -> &[MoveRecord] {
+        &self.move_log
+    }
+
+    /// Number of words drawn from the dice RNG so far. Stored with `seed`
+    /// so a restored game can continue the sequence with
+    /// [`Game::restore_dice`].
+    pub fn dice_draws(&self) -> u64 {
+        self.rng.as_ref().map_or(0, |rng| rng.draws)
+    }
+
+    /// Continue the dice of a restored game from `seed`, after the first
+    /// `draws` words, instead of replaying rolls it already made.
+    pub fn restore_dice(&mut self, seed: u64, draws: u64) {
+        self.seed = seed;
+        self.rng = Some(DiceRng::new(seed, draws));
+    }
+
     pub fn join_player(&mut self, id: Uuid, name: String) -> Result<Player> {
         // Check if player is already in the game
         if self.players.iter().any(|p| p.id == id) {
             return Err(GameError::PlayerAlreadyInGame);
         }
 
-        // Check if game is full
-        if self.players.len() >= MAX_PLAYERS {
+        if self.is_full() {
             return Err(GameError::GameFull);
         }
 
@@ -109,112 +304,307 @@ impl Game {
         let color = Color::try_from(self.players.len())?;
         let player = Player::new(id, name, color);
         self.players.push(player.clone());
+        // A spectator who joins as a player stops spectating
+        self.spectators.retain(|s| s.id != id);
         Ok(player)
     }
 
-    pub fn start(&mut self) -> Result<()> {
-        if self.state != GameState::WaitingForPlayers {
-            return Err(GameError::GameStarted);
+    /// Whether no more players can join.
+    pub fn is_full(&self) -> bool {
+        self.players.len() >= self.config.max_players.min(MAX_PLAYERS)
+    }
+
+    pub fn join_spectator(&mut self, id: Uuid, name: String) -> Result<SpectatorInfo> {
+        if !self.config.allow_spectators {
+            return Err(GameError::SpectatorsNotAllowed);
         }
 
-        if self.players.len() < 2 {
-            return Err(GameError::NotEnoughPlayers);
+        if self.players.iter().any(|p| p.id == id) || self.is_spectator(id) {
+            return Err(GameError::PlayerAlreadyInGame);
         }
 
-        let first = random_range(..self.players.len());
-        self.state = GameState::InProgress { turn: first };
-        Ok(())
+        let spectator = SpectatorInfo { id, name };
+        self.spectators.push(spectator.clone());
+        Ok(spectator)
     }
 
-    pub fn attack(&mut self, from_id: Uuid, to_id: Uuid, player_id: Uuid) -> Result<()> {
-        if let GameState::InProgress { turn } = self.state {
-            if self.players[turn].id != player_id {
-                return Err(GameError::NotPlayerTurn);
-            }
-        } else {
-            return Err(GameError::GameNotStarted);
+    pub fn is_spectator(&self, id: Uuid) -> bool {
+        self.spectators.iter().any(|s| s.id == id)
+    }
+
+    /// The player whose turn it is, or `None` unless the game is in
+    /// progress.
+    pub fn current_player(&self) -> Option<&Player> {
+        match self.state {
+            GameState::InProgress { turn } => self.players.get(turn),
+            GameState::WaitingForPlayers | GameState::Finished => None,
         }
+    }
 
-        // Validate attack
-        self.world.validate_attack(from_id, to_id, player_id)?;
+    /// Mutable access to the player whose turn it is.
+    pub fn current_player_mut(&mut self) -> Option<&mut Player> {
+        match self.state {
+            GameState::InProgress { turn } => self.players.get_mut(turn),
+            GameState::WaitingForPlayers | GameState::Finished => None,
+        }
+    }
 
-        // Borrow both areas mutably by temporarily removing the attacker's area
-        let mut from_area = self
-            .world
-            .areas
-            .remove(&from_id)
-            .ok_or(AttackError::AreaNotFound(from_id))?;
-        let to_area = self
-            .world
+    /// ID of the player whose turn it is.
+    pub fn current_player_id(&self) -> Option<Uuid> {
+        self.current_player().map(|player| player.id)
+    }
+
+    /// Whether `player_id` still owns at least one area.
+    pub fn is_alive(&self, player_id: Uuid) -> bool {
+        self.world
             .areas
-            .get_mut(&to_id)
-            .ok_or(AttackError::AreaNotFound(to_id))?;
-
-        let attack_roll = from_area.stack.roll();
-        let defense_roll = to_area.stack.roll();
-
-        if attack_roll > defense_roll {
-            // Attacker wins: transfer ownership and move dice
-            to_area.owner = Some(player_id);
-            let (remaining_stack, moved_stack) = from_area.stack.split()?;
-            to_area.stack = moved_stack;
-            from_area.stack = remaining_stack;
-        } else {
-            // Defender wins: attacker loses all dice except one
-            from_area.stack.defeat();
+            .values()
+            .any(|area| area.is_owned_by(player_id))
+    }
+
+    /// The last player standing once the game has finished. Games that end
+    /// any other way, e.g. by timing out, have no winner.
+    pub fn winner(&self) -> Option<&Player> {
+        if self.state != GameState::Finished {
+            return None;
+        }
+
+        self.last_player_standing()
+    }
+
+    /// The only player still owning areas, if all others have been wiped
+    /// out or never got any.
+    fn last_player_standing(&self) -> Option<&Player> {
+        let mut alive = self.players.iter().filter(|p| self.is_alive(p.id));
+        match (alive.next(), alive.next()) {
+            (Some(winner), None) => Some(winner),
+            _ => None,
         }
+    }
 
-        // Re-insert the attacking area
-        self.world.areas.insert(from_id, from_area);
+    /// Finish the game if a single player is left owning areas, returning
+    /// their ID. Games with several players left carry on.
+    pub fn check_win_condition(&mut self) -> Result<Option<Uuid>> {
+        match self.state {
+            GameState::InProgress { .. } => {}
+            GameState::WaitingForPlayers => return Err(GameError::GameNotStarted),
+            GameState::Finished => return Err(GameError::GameFinished),
+        }
 
-        // Check if game is over
-        if self.world.is_winner(player_id) {
+        let winner = self.last_player_standing().map(|player| player.id);
+        if winner.is_some() {
             self.state = GameState::Finished;
-            // TODO: Send winner event
         }
 
+        Ok(winner)
+    }
+
+    /// The game as seen by `player_id`. With fog of war enabled, the dice
+    /// counts of areas the player neither owns nor borders are hidden.
+    pub fn snapshot_for_player(&self, player_id: Uuid) -> Game {
+        let mut snapshot = self.clone();
+        let Some(visible) = self.visible_areas(player_id) else {
+            return snapshot;
+        };
+
+        for area in snapshot.world.areas.values_mut() {
+            if !visible.contains(&area.id) {
+                area.stack = Stack::hidden();
+            }
+        }
+
+        snapshot
+    }
+
+    /// Areas whose dice counts `player_id` may see under fog of war: the
+    /// ones they own and their neighbours. `None` if the game has no fog of
+    /// war, so every area is visible.
+    pub fn visible_areas(&self, player_id: Uuid) -> Option<HashSet<Uuid>> {
+        if !self.config.fog_of_war {
+            return None;
+        }
+
+        let visible = self
+            .world
+            .areas
+            .values()
+            .filter(|area| area.is_owned_by(player_id))
+            .flat_map(|area| {
+                let mut ids = self.world.area_neighbours(area.id);
+                ids.push(area.id);
+                ids
+            })
+            .collect();
+        Some(visible)
+    }
+
+    pub fn start(&mut self) -> Result<()> {
+        if self.state != GameState::WaitingForPlayers {
+            return Err(GameError::GameStarted);
+        }
+
+        if self.players.len() < self.config.min_players.max(MIN_PLAYERS) {
+            return Err(GameError::NotEnoughPlayers);
+        }
+
+        let mut rng = self
+            .rng
+            .take()
+            .unwrap_or_else(|| DiceRng::new(self.seed, 0));
+        if self.world.areas.values().all(|area| area.is_not_owned()) {
+            self.distribute_starting_ownership(&mut rng);
+        }
+        let first = Uniform::new(0, self.players.len())
+            .expect("game has players")
+            .sample(&mut rng);
+        self.rng = Some(rng);
+        self.state = GameState::InProgress { turn: first };
         Ok(())
     }
 
-    fn distribute_bonus_dice(&mut self, turn: usize) -> Result<()> {
+    /// Deal the world's ownable areas out to the players, as evenly as
+    /// possible, and give every dealt area a random starting stack. Called
+    /// by [`Game::start`] unless the world already has owners.
+    pub fn distribute_starting_ownership(&mut self, rng: &mut impl Rng) {
+        let player_ids: Vec<Uuid> = self.players.iter().map(|player| player.id).collect();
+        self.world
+            .distribute_initial_ownership_balanced(&player_ids, rng);
+        self.world
+            .distribute_starting_dice(self.config.max_dice_per_area, rng);
+    }
+
+    pub fn attack(&mut self, from_id: Uuid, to_id: Uuid, player_id: Uuid) -> Result<AttackResult> {
+        let mut rng = self
+            .rng
+            .take()
+            .unwrap_or_else(|| DiceRng::new(self.seed, 0));
+        let result = self.attack_with_rng(from_id, to_id, player_id, &mut rng);
+        self.rng = Some(rng);
+        result
+    }
+
+    /// Attack like [`Game::attack`], rolling the dice with `rng` instead of
+    /// the game's own RNG. Lets tests decide the rolls.
+    pub fn attack_with_rng(
+        &mut self,
+        from_id: Uuid,
+        to_id: Uuid,
+        player_id: Uuid,
+        rng: &mut impl Rng,
+    ) -> Result<AttackResult> {
+        match self.current_player_id() {
+            Some(current) if current != player_id => return Err(GameError::NotPlayerTurn),
+            Some(_) => {}
+            None => return Err(GameError::GameNotStarted),
+        }
+
+        let result = self.world.apply_attack(from_id, to_id, player_id, rng)?;
+        self.move_log.push(MoveRecord::Attack {
+            turn: self.turn_number,
+            player_id,
+            from_id,
+            to_id,
+            result,
+        });
+        self.check_win_condition()?;
+
+        Ok(result)
+    }
+
+    /// Hand out `turn`'s bonus dice: one per area in their largest connected
+    /// group plus terrain bonuses and dice stored from earlier turns. Dice
+    /// that don't fit on the board are stored up to the configured cap.
+    /// Returns the area each placed die went to, once per die.
+    fn distribute_bonus_dice(&mut self, turn: usize) -> Result<Vec<Uuid>> {
+        let cap = self.config.bonus_dice_cap;
+        let max_dice = self.config.max_dice_per_area;
         let player = self.players.get_mut(turn).ok_or(GameError::InvalidTurn)?;
-        let mut bonus_dice =
-            self.world.largest_connected_group(player.id) + player.take_stored_dice();
+        let mut bonus_dice = self.world.bonus_dice(player.id) + player.take_stored_dice();
+        let rng = dice_rng(&mut self.rng, self.seed);
 
+        let mut placed = Vec::with_capacity(bonus_dice);
         while bonus_dice > 0 {
-            if !self.world.add_bonus_dice(player.id) {
-                player.store_dice(bonus_dice);
+            let Some(area_id) = self.world.add_bonus_dice(player.id, max_dice, rng) else {
+                player.store_dice(bonus_dice, cap);
                 break;
-            }
+            };
             bonus_dice -= 1;
+            placed.push(area_id);
         }
 
-        Ok(())
+        Ok(placed)
     }
 
+    /// Pass the turn to the next player still owning areas. If nobody else
+    /// owns any, e.g. before the world has been handed out, the turn simply
+    /// moves on to the next player.
     fn next_turn(&mut self) {
+        let players = self.players.len();
+        let alive: Vec<bool> = self.players.iter().map(|p| self.is_alive(p.id)).collect();
         if let GameState::InProgress { turn } = &mut self.state {
-            *turn = (*turn + 1) % self.players.len();
+            *turn = (1..players)
+                .map(|step| (*turn + step) % players)
+                .find(|&next| alive[next])
+                .unwrap_or((*turn + 1) % players);
+            self.turn_number += 1;
         }
     }
 
-    pub fn end_turn(&mut self) -> Result<()> {
+    /// End `player_id`'s turn: distribute their bonus dice and pass the turn
+    /// on, skipping eliminated players. Returns the area each bonus die was
+    /// placed on, once per die.
+    pub fn end_turn(&mut self, player_id: Uuid) -> Result<Vec<Uuid>> {
         match self.state {
             GameState::InProgress { turn } => {
-                self.distribute_bonus_dice(turn)?;
+                if self.players[turn].id != player_id {
+                    return Err(GameError::NotPlayerTurn);
+                }
+                let placed = self.distribute_bonus_dice(turn)?;
+                self.move_log.push(MoveRecord::EndTurn {
+                    turn: self.turn_number,
+                    player_id,
+                    bonus_dice: placed.len(),
+                });
                 self.next_turn();
-                Ok(())
+                Ok(placed)
             }
             GameState::WaitingForPlayers => Err(GameError::GameNotStarted),
             GameState::Finished => Err(GameError::GameFinished),
         }
     }
+
+    /// Let `player_id` give up: their areas are left unowned and, if it was
+    /// their turn, the turn passes on. Returns the winner if only one player
+    /// is left.
+    pub fn resign(&mut self, player_id: Uuid) -> Result<Option<Uuid>> {
+        let GameState::InProgress { turn } = self.state else {
+            return Err(match self.state {
+                GameState::Finished => GameError::GameFinished,
+                _ => GameError::GameNotStarted,
+            });
+        };
+        if !self.players.iter().any(|player| player.id == player_id) {
+            return Err(GameError::PlayerNotInGame);
+        }
+
+        self.world.eliminate_player(player_id);
+        self.move_log.push(MoveRecord::Resign {
+            turn: self.turn_number,
+            player_id,
+        });
+        let winner = self.check_win_condition()?;
+        if winner.is_none() && self.players[turn].id == player_id {
+            self.next_turn();
+        }
+
+        Ok(winner)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Area, Stack, Tile};
+    use crate::{Area, DEFAULT_BONUS_DICE_CAP, Tile};
     use std::collections::{HashMap, HashSet};
 
     /// Helper: create a World with no areas.
@@ -238,6 +628,12 @@ mod tests {
             .collect()
     }
 
+    /// Helper: end the turn of whoever is currently on turn.
+    fn end_current_turn(game: &mut Game) -> usize {
+        let player_id = game.current_player_id().expect("game in progress");
+        game.end_turn(player_id).unwrap().len()
+    }
+
     /// Helper: build a World with two adjacent areas, returning (world, from_id, to_id).
     fn world_with_two_adjacent_areas(
         owner_from: Uuid,
@@ -258,17 +654,13 @@ mod tests {
         tiles_a.insert(Tile::new(0, 0));
         let mut from = Area::new(tiles_a);
         from.owner = Some(owner_from);
-        for _ in 1..from_dice {
-            from.stack.increment().unwrap();
-        }
+        from.stack.add_n(from_dice - 1).unwrap();
 
         let mut tiles_b = HashSet::new();
         tiles_b.insert(Tile::new(0, 1));
         let mut to = Area::new(tiles_b);
         to.owner = Some(owner_to);
-        for _ in 1..to_dice {
-            to.stack.increment().unwrap();
-        }
+        to.stack.add_n(to_dice - 1).unwrap();
 
         let from_id = from.id;
         let to_id = to.id;
@@ -277,7 +669,14 @@ mod tests {
         areas.insert(from.id, from);
         areas.insert(to.id, to);
 
-        (World { areas }, from_id, to_id)
+        (
+            World {
+                areas,
+                ..World::default()
+            },
+            from_id,
+            to_id,
+        )
     }
 
     // ================================================================
@@ -346,6 +745,26 @@ mod tests {
         assert!(matches!(err, GameError::GameFull));
     }
 
+    #[test]
+    fn join_player_respects_configured_max_players() {
+        let mut game = Game::with_config(
+            empty_world(),
+            GameConfig {
+                max_players: 3,
+                ..GameConfig::default()
+            },
+        );
+        add_players(&mut game, 2);
+        assert!(!game.is_full());
+
+        add_players(&mut game, 1);
+        assert!(game.is_full());
+        let err = game
+            .join_player(Uuid::new_v4(), "Extra".into())
+            .unwrap_err();
+        assert!(matches!(err, GameError::GameFull));
+    }
+
     #[test]
     fn join_player_after_game_started_returns_error() {
         let mut game = new_game();
@@ -374,6 +793,62 @@ mod tests {
         assert_eq!(p2.color, Color::Green);
     }
 
+    // ================================================================
+    // ==== Game::join_spectator ====
+    // ================================================================
+
+    #[test]
+    fn join_spectator_adds_spectator() {
+        let mut game = new_game();
+        let id = Uuid::new_v4();
+        let spectator = game.join_spectator(id, "Eve".into()).unwrap();
+        assert_eq!(spectator.id, id);
+        assert_eq!(spectator.name, "Eve");
+        assert!(game.is_spectator(id));
+        assert!(game.players.is_empty());
+    }
+
+    #[test]
+    fn join_spectator_allowed_after_start() {
+        let mut game = new_game();
+        add_players(&mut game, 2);
+        game.start().unwrap();
+        assert!(game.join_spectator(Uuid::new_v4(), "Eve".into()).is_ok());
+    }
+
+    #[test]
+    fn join_spectator_when_disallowed_returns_error() {
+        let mut game = Game::with_config(
+            empty_world(),
+            GameConfig {
+                allow_spectators: false,
+                ..GameConfig::default()
+            },
+        );
+        let err = game
+            .join_spectator(Uuid::new_v4(), "Eve".into())
+            .unwrap_err();
+        assert!(matches!(err, GameError::SpectatorsNotAllowed));
+    }
+
+    #[test]
+    fn join_spectator_as_player_returns_error() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 1);
+        let err = game.join_spectator(ids[0], "P0".into()).unwrap_err();
+        assert!(matches!(err, GameError::PlayerAlreadyInGame));
+    }
+
+    #[test]
+    fn join_player_removes_spectator() {
+        let mut game = new_game();
+        let id = Uuid::new_v4();
+        game.join_spectator(id, "Eve".into()).unwrap();
+        game.join_player(id, "Eve".into()).unwrap();
+        assert!(!game.is_spectator(id));
+        assert_eq!(game.players.len(), 1);
+    }
+
     // ================================================================
     // ==== Game::start ====
     // ================================================================
@@ -413,6 +888,85 @@ mod tests {
         assert!(matches!(err, GameError::NotEnoughPlayers));
     }
 
+    #[test]
+    fn start_requires_configured_min_players() {
+        let mut game = Game::with_config(
+            empty_world(),
+            GameConfig {
+                min_players: 3,
+                ..GameConfig::default()
+            },
+        );
+        add_players(&mut game, 2);
+        assert!(matches!(
+            game.start().unwrap_err(),
+            GameError::NotEnoughPlayers
+        ));
+
+        add_players(&mut game, 1);
+        game.start().unwrap();
+    }
+
+    /// Helper: a world of `n` single-tile areas in a column, all unowned.
+    fn unowned_world(n: usize) -> World {
+        let areas = (0..n)
+            .map(|y| Area::new(HashSet::from([Tile::new(0, y)])))
+            .map(|area| (area.id, area))
+            .collect();
+        World {
+            areas,
+            ..World::default()
+        }
+    }
+
+    #[test]
+    fn distribute_starting_ownership_deals_areas_evenly() {
+        for players in 2..=MAX_PLAYERS {
+            let mut game = Game::new(unowned_world(17));
+            let ids = add_players(&mut game, players);
+            game.distribute_starting_ownership(&mut SmallRng::seed_from_u64(9));
+
+            assert!(game.world.areas.values().all(|area| area.owner.is_some()));
+            let counts: Vec<usize> = ids
+                .iter()
+                .map(|id| {
+                    game.world
+                        .areas
+                        .values()
+                        .filter(|area| area.is_owned_by(*id))
+                        .count()
+                })
+                .collect();
+            let (fewest, most) = (counts.iter().min().unwrap(), counts.iter().max().unwrap());
+            assert!(*fewest > 0, "a player got no areas: {counts:?}");
+            assert!(most - fewest <= 1, "uneven deal: {counts:?}");
+        }
+    }
+
+    #[test]
+    fn start_deals_unowned_world() {
+        let mut game = Game::new(unowned_world(6));
+        add_players(&mut game, 2);
+        game.start().unwrap();
+
+        assert!(game.world.areas.values().all(|area| {
+            area.owner.is_some() && (1..=crate::STARTING_DICE_MAX).contains(&area.stack.count())
+        }));
+    }
+
+    #[test]
+    fn start_keeps_existing_ownership() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        let (world, from_id, to_id) = world_with_two_adjacent_areas(ids[0], ids[0], 5);
+        game.world = world;
+        game.start().unwrap();
+
+        assert!(game.world.areas[&from_id].is_owned_by(ids[0]));
+        assert!(game.world.areas[&to_id].is_owned_by(ids[0]));
+        assert_eq!(game.world.areas[&from_id].stack.count(), 5);
+    }
+
     #[test]
     fn start_already_started_returns_error() {
         let mut game = new_game();
@@ -445,7 +999,7 @@ mod tests {
         };
         let expected_next = (turn + 1) % 3;
 
-        game.end_turn().unwrap();
+        end_current_turn(&mut game);
 
         let GameState::InProgress { turn: new_turn } = game.state else {
             panic!("expected InProgress");
@@ -459,10 +1013,10 @@ mod tests {
         add_players(&mut game, 2);
         game.state = GameState::InProgress { turn: 1 };
 
-        game.end_turn().unwrap();
+        end_current_turn(&mut game);
         assert_eq!(game.state, GameState::InProgress { turn: 0 });
 
-        game.end_turn().unwrap();
+        end_current_turn(&mut game);
         assert_eq!(game.state, GameState::InProgress { turn: 1 });
     }
 
@@ -470,7 +1024,7 @@ mod tests {
     fn end_turn_when_waiting_returns_error() {
         let mut game = new_game();
         add_players(&mut game, 2);
-        let err = game.end_turn().unwrap_err();
+        let err = game.end_turn(Uuid::new_v4()).unwrap_err();
         assert!(matches!(err, GameError::GameNotStarted));
     }
 
@@ -479,7 +1033,7 @@ mod tests {
         let mut game = new_game();
         add_players(&mut game, 2);
         game.state = GameState::Finished;
-        let err = game.end_turn().unwrap_err();
+        let err = game.end_turn(Uuid::new_v4()).unwrap_err();
         assert!(matches!(err, GameError::GameFinished));
     }
 
@@ -490,81 +1044,325 @@ mod tests {
         game.state = GameState::InProgress { turn: 0 };
 
         for _ in 0..4 {
-            game.end_turn().unwrap();
+            end_current_turn(&mut game);
         }
         // After 4 end_turn calls with 4 players, we should be back to turn 0
         assert_eq!(game.state, GameState::InProgress { turn: 0 });
     }
 
-    // ================================================================
-    // ==== Game::attack ====
-    // ================================================================
-
     #[test]
-    fn attack_valid_succeeds() {
+    fn end_turn_rejects_player_not_on_turn() {
         let mut game = new_game();
         let ids = add_players(&mut game, 2);
         game.state = GameState::InProgress { turn: 0 };
 
-        let (world, from_id, to_id) = world_with_two_adjacent_areas(ids[0], ids[1], 3);
-        game.world = world;
-
-        assert!(game.attack(from_id, to_id, ids[0]).is_ok());
+        let err = game.end_turn(ids[1]).unwrap_err();
+        assert!(matches!(err, GameError::NotPlayerTurn));
+        assert_eq!(game.state, GameState::InProgress { turn: 0 });
+        assert_eq!(game.turn_number, 0);
     }
 
     #[test]
-    fn attack_from_area_not_found() {
+    fn end_turn_places_bonus_for_largest_connected_group() {
         let mut game = new_game();
         let ids = add_players(&mut game, 2);
+        // Three connected areas and one separate area for player 0
+        for (x, y) in [(0, 0), (0, 1), (0, 2), (4, 0)] {
+            let mut area = Area::new(HashSet::from([Tile::new(x, y)]));
+            area.owner = Some(ids[0]);
+            game.world.areas.insert(area.id, area);
+        }
+        let mut enemy = Area::new(HashSet::from([Tile::new(4, 4)]));
+        enemy.owner = Some(ids[1]);
+        game.world.areas.insert(enemy.id, enemy);
         game.state = GameState::InProgress { turn: 0 };
 
-        let err = game
-            .attack(Uuid::new_v4(), Uuid::new_v4(), ids[0])
-            .unwrap_err();
-        assert!(matches!(err, GameError::AttackError(_)));
+        assert_eq!(game.end_turn(ids[0]).unwrap().len(), 3);
+        let dice: usize = game
+            .world
+            .areas
+            .values()
+            .filter(|area| area.is_owned_by(ids[0]))
+            .map(|area| area.stack.count())
+            .sum();
+        assert_eq!(dice, 4 + 3);
     }
 
     #[test]
-    fn attack_not_enough_dice() {
+    fn end_turn_skips_eliminated_players() {
         let mut game = new_game();
-        let ids = add_players(&mut game, 2);
+        let ids = add_players(&mut game, 4);
+        for (y, owner) in [(0, ids[0]), (1, ids[3])] {
+            let mut area = Area::new(HashSet::from([Tile::new(0, y)]));
+            area.owner = Some(owner);
+            game.world.areas.insert(area.id, area);
+        }
         game.state = GameState::InProgress { turn: 0 };
 
-        let (world, from_id, to_id) = world_with_two_adjacent_areas(ids[0], ids[1], 1); // only 1 die
-        game.world = world;
+        game.end_turn(ids[0]).unwrap();
+        assert_eq!(game.current_player_id(), Some(ids[3]));
+        assert_eq!(game.turn_number, 1);
 
-        let err = game.attack(from_id, to_id, ids[0]).unwrap_err();
-        assert!(matches!(
-            err,
-            GameError::AttackError(AttackError::AreaNotEnoughDice(_))
-        ));
+        game.end_turn(ids[3]).unwrap();
+        assert_eq!(game.current_player_id(), Some(ids[0]));
     }
 
-    #[test]
-    fn attack_self_attack_not_allowed() {
-        let mut game = new_game();
+    /// Helper: a game on turn 0 whose first player owns `areas` full areas
+    /// in a connected column, so all of their bonus dice get stored.
+    fn game_with_full_areas(areas: usize, bonus_dice_cap: usize) -> Game {
+        let mut game = Game::with_config(
+            empty_world(),
+            GameConfig {
+                bonus_dice_cap,
+                ..GameConfig::default()
+            },
+        );
         let ids = add_players(&mut game, 2);
+        for y in 0..areas {
+            let mut tiles = HashSet::new();
+            tiles.insert(Tile::new(0, y));
+            let mut area = Area::new(tiles);
+            area.owner = Some(ids[0]);
+            area.stack.add_n(crate::Stack::MAX - 1).unwrap();
+            game.world.areas.insert(area.id, area);
+        }
         game.state = GameState::InProgress { turn: 0 };
+        game
+    }
 
-        let (world, from_id, to_id) = world_with_two_adjacent_areas(ids[0], ids[0], 3); // same owner
-        game.world = world;
+    #[test]
+    fn end_turn_fills_areas_only_up_to_max_dice_per_area() {
+        let mut game = Game::with_config(
+            empty_world(),
+            GameConfig {
+                max_dice_per_area: 4,
+                ..GameConfig::default()
+            },
+        );
+        let ids = add_players(&mut game, 2);
+        for y in 0..3 {
+            let mut area = Area::new(HashSet::from([Tile::new(0, y)]));
+            area.owner = Some(ids[0]);
+            area.stack.add_n(2).unwrap();
+            game.world.areas.insert(area.id, area);
+        }
+        game.state = GameState::InProgress { turn: 0 };
 
-        let err = game.attack(from_id, to_id, ids[0]).unwrap_err();
-        assert!(matches!(
-            err,
-            GameError::AttackError(AttackError::SelfAttackNotAllowed)
-        ));
+        // Three bonus dice fill every area up to the limit of four, after
+        // which further bonus dice are stored
+        assert_eq!(game.end_turn(ids[0]).unwrap().len(), 3);
+        assert!(game.end_turn(ids[1]).unwrap().is_empty());
+        assert!(game.end_turn(ids[0]).unwrap().is_empty());
+        assert!(
+            game.world
+                .areas
+                .values()
+                .all(|area| area.stack.count() == 4)
+        );
+        assert_eq!(game.players[0].take_stored_dice(), 3);
     }
 
-    // ==== Attack outcome invariants ====
+    #[test]
+    fn end_turn_stores_bonus_dice_up_to_configured_cap() {
+        let mut game = game_with_full_areas(8, 5);
+        end_current_turn(&mut game);
+        assert_eq!(game.players[0].take_stored_dice(), 5);
+    }
 
     #[test]
-    fn attack_from_area_always_has_one_die_after_attack() {
-        // Regardless of win or loss, the attacking area ends up with 1 die.
-        for _ in 0..50 {
-            let mut game = new_game();
-            let ids = add_players(&mut game, 2);
-            game.state = GameState::InProgress { turn: 0 };
+    fn end_turn_with_default_cap_stores_all_bonus_dice() {
+        let mut game = game_with_full_areas(8, DEFAULT_BONUS_DICE_CAP);
+        end_current_turn(&mut game);
+        assert_eq!(game.players[0].take_stored_dice(), 8);
+    }
+
+    #[test]
+    fn end_turn_applies_cap_to_dice_stored_over_several_turns() {
+        let mut game = game_with_full_areas(4, 5);
+        for _ in 0..4 {
+            end_current_turn(&mut game);
+        }
+        assert_eq!(game.players[0].take_stored_dice(), 5);
+    }
+
+    #[test]
+    fn new_game_starts_at_turn_number_zero() {
+        let mut game = new_game();
+        add_players(&mut game, 2);
+        assert_eq!(game.turn_number, 0);
+        game.start().unwrap();
+        assert_eq!(game.turn_number, 0);
+    }
+
+    #[test]
+    fn end_turn_increments_turn_number() {
+        let mut game = new_game();
+        add_players(&mut game, 3);
+        game.start().unwrap();
+
+        for expected in 1..=5 {
+            end_current_turn(&mut game);
+            assert_eq!(game.turn_number, expected);
+        }
+    }
+
+    #[test]
+    fn failed_end_turn_keeps_turn_number() {
+        let mut game = new_game();
+        add_players(&mut game, 2);
+        assert!(game.end_turn(Uuid::new_v4()).is_err());
+        assert_eq!(game.turn_number, 0);
+    }
+
+    // ================================================================
+    // ==== Game::attack ====
+    // ================================================================
+
+    #[test]
+    fn attack_valid_succeeds() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        game.state = GameState::InProgress { turn: 0 };
+
+        let (world, from_id, to_id) = world_with_two_adjacent_areas(ids[0], ids[1], 3);
+        game.world = world;
+
+        assert!(game.attack(from_id, to_id, ids[0]).is_ok());
+    }
+
+    #[test]
+    fn attack_with_rng_rolls_with_given_rng() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        game.state = GameState::InProgress { turn: 0 };
+        let (world, from_id, to_id) =
+            world_with_two_adjacent_areas_full(ids[0], ids[1], Stack::MAX, Stack::MAX);
+        game.world = world;
+
+        // Rolling the same stacks with an identically seeded RNG predicts the attack
+        let mut expected_rng = SmallRng::seed_from_u64(5);
+        let to_area = &game.world.areas[&to_id];
+        let attack_roll = game.world.areas[&from_id]
+            .stack
+            .attack_roll(&mut expected_rng);
+        let defence_roll = to_area
+            .stack
+            .defence_roll(to_area.terrain, &mut expected_rng);
+
+        let result = game
+            .attack_with_rng(from_id, to_id, ids[0], &mut SmallRng::seed_from_u64(5))
+            .unwrap();
+        assert_eq!(result.attack_roll, attack_roll);
+        assert_eq!(result.defence_roll, defence_roll);
+        assert_eq!(result.captured, attack_roll > defence_roll);
+    }
+
+    #[test]
+    fn attack_rolls_are_within_dice_ranges() {
+        for seed in 0..50 {
+            let mut game = new_game();
+            let ids = add_players(&mut game, 2);
+            game.state = GameState::InProgress { turn: 0 };
+            let (world, from_id, to_id) = world_with_two_adjacent_areas_full(ids[0], ids[1], 5, 3);
+            game.world = world;
+
+            let result = game
+                .attack_with_rng(from_id, to_id, ids[0], &mut SmallRng::seed_from_u64(seed))
+                .unwrap();
+            assert!((5..=30).contains(&result.attack_roll));
+            assert!((3..=18).contains(&result.defence_roll));
+        }
+    }
+
+    #[test]
+    fn attack_result_reports_remaining_dice() {
+        for seed in 0..50 {
+            let mut game = new_game();
+            let ids = add_players(&mut game, 2);
+            game.state = GameState::InProgress { turn: 0 };
+            let (world, from_id, to_id) = world_with_two_adjacent_areas_full(ids[0], ids[1], 5, 3);
+            game.world = world;
+
+            let result = game
+                .attack_with_rng(from_id, to_id, ids[0], &mut SmallRng::seed_from_u64(seed))
+                .unwrap();
+            assert_eq!(result.attacker_remaining_dice, 1);
+            let expected_defender = if result.captured { 4 } else { 3 };
+            assert_eq!(result.defender_remaining_dice, expected_defender);
+            assert_eq!(
+                game.world.areas[&to_id].stack.count(),
+                result.defender_remaining_dice
+            );
+        }
+    }
+
+    #[test]
+    fn attack_with_rng_checks_turn() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        game.state = GameState::InProgress { turn: 0 };
+        let (world, from_id, to_id) = world_with_two_adjacent_areas(ids[1], ids[0], 3);
+        game.world = world;
+
+        let err = game
+            .attack_with_rng(from_id, to_id, ids[1], &mut SmallRng::seed_from_u64(0))
+            .unwrap_err();
+        assert!(matches!(err, GameError::NotPlayerTurn));
+    }
+
+    #[test]
+    fn attack_from_area_not_found() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        game.state = GameState::InProgress { turn: 0 };
+
+        let err = game
+            .attack(Uuid::new_v4(), Uuid::new_v4(), ids[0])
+            .unwrap_err();
+        assert!(matches!(err, GameError::AttackError(_)));
+    }
+
+    #[test]
+    fn attack_not_enough_dice() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        game.state = GameState::InProgress { turn: 0 };
+
+        let (world, from_id, to_id) = world_with_two_adjacent_areas(ids[0], ids[1], 1); // only 1 die
+        game.world = world;
+
+        let err = game.attack(from_id, to_id, ids[0]).unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::AttackError(AttackError::AreaNotEnoughDice(_))
+        ));
+    }
+
+    #[test]
+    fn attack_self_attack_not_allowed() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        game.state = GameState::InProgress { turn: 0 };
+
+        let (world, from_id, to_id) = world_with_two_adjacent_areas(ids[0], ids[0], 3); // same owner
+        game.world = world;
+
+        let err = game.attack(from_id, to_id, ids[0]).unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::AttackError(AttackError::SelfAttackNotAllowed)
+        ));
+    }
+
+    // ==== Attack outcome invariants ====
+
+    #[test]
+    fn attack_from_area_always_has_one_die_after_attack() {
+        // Regardless of win or loss, the attacking area ends up with 1 die.
+        for _ in 0..50 {
+            let mut game = new_game();
+            let ids = add_players(&mut game, 2);
+            game.state = GameState::InProgress { turn: 0 };
 
             let (world, from_id, to_id) = world_with_two_adjacent_areas_full(ids[0], ids[1], 4, 2);
             game.world = world;
@@ -718,6 +1516,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn attack_result_matches_outcome() {
+        for _ in 0..50 {
+            let mut game = new_game();
+            let ids = add_players(&mut game, 2);
+            game.state = GameState::InProgress { turn: 0 };
+
+            let (world, from_id, to_id) = world_with_two_adjacent_areas_full(ids[0], ids[1], 4, 4);
+            game.world = world;
+
+            let result = game.attack(from_id, to_id, ids[0]).unwrap();
+
+            assert!((4..=24).contains(&result.attack_roll));
+            assert!((4..=24).contains(&result.defence_roll));
+            assert_eq!(result.captured, result.attack_roll > result.defence_roll);
+
+            let to_area = game.world.areas.get(&to_id).unwrap();
+            assert_eq!(to_area.owner == Some(ids[0]), result.captured);
+        }
+    }
+
     #[test]
     fn attack_preserves_world_area_count() {
         let mut game = new_game();
@@ -767,6 +1586,296 @@ mod tests {
         }
     }
 
+    // ================================================================
+    // ==== Game::winner ====
+    // ================================================================
+
+    #[test]
+    fn winner_is_none_for_started_game() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        let (world, _, _) = world_with_two_adjacent_areas(ids[0], ids[1], 3);
+        game.world = world;
+        game.start().unwrap();
+
+        assert!(game.winner().is_none());
+    }
+
+    #[test]
+    fn winner_is_last_player_with_areas() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        let (world, _, _) = world_with_two_adjacent_areas(ids[0], ids[0], 3);
+        game.world = world;
+        game.state = GameState::Finished;
+
+        let winner = game.winner().unwrap();
+        assert_eq!(winner.id, ids[0]);
+        assert_eq!(winner.name, "Player 0");
+    }
+
+    #[test]
+    fn winner_is_none_for_timed_out_game() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        let (world, _, _) = world_with_two_adjacent_areas(ids[0], ids[1], 3);
+        game.world = world;
+        game.start().unwrap();
+        game.state = GameState::Finished;
+
+        assert!(game.winner().is_none());
+    }
+
+    #[test]
+    fn check_win_condition_finishes_two_player_game() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        let (world, _, _) = world_with_two_adjacent_areas(ids[0], ids[0], 3);
+        game.world = world;
+        game.state = GameState::InProgress { turn: 0 };
+
+        assert_eq!(game.check_win_condition().unwrap(), Some(ids[0]));
+        assert_eq!(game.state, GameState::Finished);
+        assert_eq!(game.winner().unwrap().id, ids[0]);
+    }
+
+    #[test]
+    fn check_win_condition_continues_while_two_players_have_areas() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 3);
+        let (world, _, _) = world_with_two_adjacent_areas(ids[0], ids[2], 3);
+        game.world = world;
+        game.state = GameState::InProgress { turn: 0 };
+
+        assert_eq!(game.check_win_condition().unwrap(), None);
+        assert_eq!(game.state, GameState::InProgress { turn: 0 });
+    }
+
+    #[test]
+    fn check_win_condition_requires_game_in_progress() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        let (world, _, _) = world_with_two_adjacent_areas(ids[0], ids[0], 3);
+        game.world = world;
+
+        assert!(matches!(
+            game.check_win_condition(),
+            Err(GameError::GameNotStarted)
+        ));
+        game.state = GameState::Finished;
+        assert!(matches!(
+            game.check_win_condition(),
+            Err(GameError::GameFinished)
+        ));
+    }
+
+    #[test]
+    fn capturing_last_enemy_area_finishes_multiplayer_game() {
+        // A third player without areas does not keep the game going.
+        for seed in 0..50 {
+            let mut game = new_game();
+            let ids = add_players(&mut game, 3);
+            let (world, from_id, to_id) = world_with_two_adjacent_areas(ids[0], ids[1], 8);
+            game.world = world;
+            game.state = GameState::InProgress { turn: 0 };
+
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let result = game
+                .attack_with_rng(from_id, to_id, ids[0], &mut rng)
+                .unwrap();
+            if result.captured {
+                assert_eq!(game.state, GameState::Finished);
+                assert_eq!(game.winner().unwrap().id, ids[0]);
+                return;
+            }
+            assert_eq!(game.state, GameState::InProgress { turn: 0 });
+        }
+        panic!("eight dice never beat one in 50 attacks");
+    }
+
+    #[test]
+    fn is_alive_requires_an_owned_area() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        let (world, _, _) = world_with_two_adjacent_areas(ids[0], ids[0], 3);
+        game.world = world;
+
+        assert!(game.is_alive(ids[0]));
+        assert!(!game.is_alive(ids[1]));
+    }
+
+    // ================================================================
+    // ==== Game::snapshot_for_player ====
+    // ================================================================
+
+    /// A world with one area owned by `me`, an adjacent enemy area and a
+    /// distant enemy area, each enemy area holding three dice.
+    fn fog_world(me: Uuid, enemy: Uuid) -> (World, Uuid, Uuid, Uuid) {
+        let area_at = |x, y, owner| {
+            let mut area = Area::new(HashSet::from([Tile::new(x, y)]));
+            area.owner = Some(owner);
+            area.stack.increment().unwrap();
+            area.stack.increment().unwrap();
+            area
+        };
+        let mine = area_at(0, 0, me);
+        let near = area_at(0, 1, enemy);
+        let far = area_at(5, 5, enemy);
+        let ids = (mine.id, near.id, far.id);
+
+        let areas = [mine, near, far].into_iter().map(|a| (a.id, a)).collect();
+        (
+            World {
+                areas,
+                ..World::default()
+            },
+            ids.0,
+            ids.1,
+            ids.2,
+        )
+    }
+
+    #[test]
+    fn snapshot_for_player_hides_distant_enemy_areas() {
+        let mut game = Game::with_config(
+            empty_world(),
+            GameConfig {
+                fog_of_war: true,
+                ..GameConfig::default()
+            },
+        );
+        let ids = add_players(&mut game, 2);
+        let (world, _, _, far_id) = fog_world(ids[0], ids[1]);
+        game.world = world;
+
+        let snapshot = game.snapshot_for_player(ids[0]);
+        assert!(snapshot.world.areas[&far_id].stack.is_hidden());
+    }
+
+    #[test]
+    fn snapshot_for_player_shows_own_and_adjacent_areas() {
+        let mut game = Game::with_config(
+            empty_world(),
+            GameConfig {
+                fog_of_war: true,
+                ..GameConfig::default()
+            },
+        );
+        let ids = add_players(&mut game, 2);
+        let (world, mine_id, near_id, _) = fog_world(ids[0], ids[1]);
+        game.world = world;
+
+        let snapshot = game.snapshot_for_player(ids[0]);
+        assert_eq!(snapshot.world.areas[&mine_id].stack.count(), 3);
+        assert_eq!(snapshot.world.areas[&near_id].stack.count(), 3);
+    }
+
+    #[test]
+    fn visible_areas_are_own_and_adjacent_areas() {
+        let mut game = Game::with_config(
+            empty_world(),
+            GameConfig {
+                fog_of_war: true,
+                ..GameConfig::default()
+            },
+        );
+        let ids = add_players(&mut game, 2);
+        let (world, mine_id, near_id, _) = fog_world(ids[0], ids[1]);
+        game.world = world;
+
+        assert_eq!(
+            game.visible_areas(ids[0]),
+            Some(HashSet::from([mine_id, near_id]))
+        );
+        assert_eq!(game.visible_areas(Uuid::new_v4()), Some(HashSet::new()));
+    }
+
+    #[test]
+    fn visible_areas_without_fog_is_none() {
+        let game = new_game();
+        assert_eq!(game.visible_areas(Uuid::new_v4()), None);
+    }
+
+    #[test]
+    fn fogged_attack_result_hides_what_the_viewer_cannot_see() {
+        let result = AttackResult {
+            attack_roll: 20,
+            defence_roll: 10,
+            captured: true,
+            attacker_remaining_dice: 1,
+            defender_remaining_dice: 5,
+        };
+
+        assert_eq!(result.fogged(true, true), result);
+        let only_from = result.fogged(true, false);
+        assert_eq!(
+            (only_from.attack_roll, only_from.attacker_remaining_dice),
+            (20, 1)
+        );
+        assert_eq!(only_from.defender_remaining_dice, 0);
+        assert_eq!(
+            result.fogged(false, false),
+            AttackResult {
+                attack_roll: 0,
+                defence_roll: 0,
+                captured: true,
+                attacker_remaining_dice: 0,
+                defender_remaining_dice: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn snapshot_for_player_without_fog_shows_everything() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        let (world, _, _, far_id) = fog_world(ids[0], ids[1]);
+        game.world = world;
+
+        let snapshot = game.snapshot_for_player(ids[0]);
+        assert_eq!(snapshot.world.areas[&far_id].stack.count(), 3);
+    }
+
+    // ================================================================
+    // ==== Game::current_player ====
+    // ================================================================
+
+    #[test]
+    fn current_player_is_none_while_waiting_for_players() {
+        let mut game = new_game();
+        add_players(&mut game, 2);
+
+        assert!(game.current_player().is_none());
+        assert!(game.current_player_mut().is_none());
+        assert_eq!(game.current_player_id(), None);
+    }
+
+    #[test]
+    fn current_player_follows_turn_while_in_progress() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 3);
+        game.state = GameState::InProgress { turn: 1 };
+
+        assert_eq!(game.current_player().unwrap().id, ids[1]);
+        assert_eq!(game.current_player_id(), Some(ids[1]));
+
+        game.current_player_mut()
+            .unwrap()
+            .store_dice(2, DEFAULT_BONUS_DICE_CAP);
+        assert_eq!(game.players[1].take_stored_dice(), 2);
+    }
+
+    #[test]
+    fn current_player_is_none_once_finished() {
+        let mut game = new_game();
+        add_players(&mut game, 2);
+        game.state = GameState::Finished;
+
+        assert!(game.current_player().is_none());
+        assert!(game.current_player_mut().is_none());
+        assert_eq!(game.current_player_id(), None);
+    }
+
     // ================================================================
     // ==== GameState ====
     // ================================================================
@@ -800,6 +1909,220 @@ mod tests {
         }
     }
 
+    // ================================================================
+    // ==== MoveRecord ====
+    // ================================================================
+
+    #[test]
+    fn move_record_serialize_deserialize_roundtrip() {
+        let records = vec![
+            MoveRecord::Attack {
+                turn: 3,
+                player_id: Uuid::new_v4(),
+                from_id: Uuid::new_v4(),
+                to_id: Uuid::new_v4(),
+                result: AttackResult {
+                    attack_roll: 12,
+                    defence_roll: 7,
+                    captured: true,
+                    attacker_remaining_dice: 1,
+                    defender_remaining_dice: 3,
+                },
+            },
+            MoveRecord::EndTurn {
+                turn: 3,
+                player_id: Uuid::new_v4(),
+                bonus_dice: 4,
+            },
+        ];
+        for record in records {
+            let json = serde_json::to_string(&record).unwrap();
+            let deser: MoveRecord = serde_json::from_str(&json).unwrap();
+            assert_eq!(record, deser);
+        }
+    }
+
+    #[test]
+    fn move_record_without_turn_or_bonus_dice_deserializes() {
+        let player_id = Uuid::new_v4();
+        let json = serde_json::json!({ "type": "end_turn", "player_id": player_id });
+        let record: MoveRecord = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            record,
+            MoveRecord::EndTurn {
+                turn: 0,
+                player_id,
+                bonus_dice: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn move_log_records_attacks_and_turn_ends() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        let (world, from_id, to_id) = world_with_two_adjacent_areas_full(ids[0], ids[1], 3, 3);
+        game.world = world;
+        // A second area keeps the defender in the game whatever the roll
+        let mut spare = Area::new(HashSet::from([Tile::new(5, 5)]));
+        spare.owner = Some(ids[1]);
+        game.world.areas.insert(spare.id, spare);
+        game.state = GameState::InProgress { turn: 0 };
+        assert!(game.move_log().is_empty());
+
+        let result = game
+            .attack_with_rng(from_id, to_id, ids[0], &mut SmallRng::seed_from_u64(1))
+            .unwrap();
+        assert_eq!(
+            game.move_log(),
+            [MoveRecord::Attack {
+                turn: 0,
+                player_id: ids[0],
+                from_id,
+                to_id,
+                result,
+            }]
+        );
+
+        let bonus_dice = game.end_turn(ids[0]).unwrap().len();
+        assert_eq!(game.move_log().len(), 2);
+        assert_eq!(
+            game.move_log()[1],
+            MoveRecord::EndTurn {
+                turn: 0,
+                player_id: ids[0],
+                bonus_dice,
+            }
+        );
+    }
+
+    #[test]
+    fn failed_moves_are_not_logged() {
+        let mut game = new_game();
+        let ids = add_players(&mut game, 2);
+        game.state = GameState::InProgress { turn: 0 };
+
+        assert!(game.end_turn(ids[1]).is_err());
+        assert!(game.attack(Uuid::new_v4(), Uuid::new_v4(), ids[0]).is_err());
+        assert!(game.move_log().is_empty());
+    }
+
+    // ================================================================
+    // ==== Seeded games ====
+    // ================================================================
+
+    /// Start a two-player game seeded with `seed`, let the first player
+    /// attack the other's area and end their turn. Returns the attack and
+    /// the resulting world.
+    fn play_seeded(seed: u64, ids: &[Uuid], world: &World) -> (AttackResult, serde_json::Value) {
+        let mut game = Game::with_config(
+            world.clone(),
+            GameConfig {
+                seed: Some(seed),
+                ..GameConfig::default()
+            },
+        );
+        for (i, id) in ids.iter().enumerate() {
+            game.join_player(*id, format!("Player {i}")).unwrap();
+        }
+        game.start().unwrap();
+
+        let GameState::InProgress { turn } = game.state else {
+            panic!("game should be in progress");
+        };
+        let attacker = ids[turn];
+        let from_id = game
+            .world
+            .areas
+            .values()
+            .find(|a| a.is_owned_by(attacker))
+            .unwrap()
+            .id;
+        let to_id = game
+            .world
+            .areas
+            .values()
+            .find(|a| !a.is_owned_by(attacker))
+            .unwrap()
+            .id;
+
+        let result = game.attack(from_id, to_id, attacker).unwrap();
+        if game.state != GameState::Finished {
+            end_current_turn(&mut game);
+        }
+        (result, serde_json::to_value(&game.world).unwrap())
+    }
+
+    #[test]
+    fn same_seed_gives_identical_games() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let (world, _, _) = world_with_two_adjacent_areas_full(ids[0], ids[1], 8, 8);
+
+        assert_eq!(play_seeded(7, &ids, &world), play_seeded(7, &ids, &world));
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let (world, _, _) = world_with_two_adjacent_areas_full(ids[0], ids[1], 8, 8);
+
+        let first = play_seeded(0, &ids, &world);
+        assert!((1..20).any(|seed| play_seeded(seed, &ids, &world) != first));
+    }
+
+    #[test]
+    fn config_seed_is_used() {
+        let game = Game::with_config(
+            empty_world(),
+            GameConfig {
+                seed: Some(1234),
+                ..GameConfig::default()
+            },
+        );
+        assert_eq!(game.seed, 1234);
+    }
+
+    #[test]
+    fn seed_is_not_serialized() {
+        let value = serde_json::to_value(new_game()).unwrap();
+        assert!(!json_keys(&value).iter().any(|key| key == "seed"));
+    }
+
+    #[test]
+    fn dice_rng_resumes_at_its_position() {
+        let mut rng = DiceRng::new(3, 0);
+        rng.next_u32();
+        rng.next_u64();
+        rng.fill_bytes(&mut [0; 12]);
+        assert_eq!(rng.draws, 4);
+
+        let mut restored = DiceRng::new(3, rng.draws);
+        assert_eq!(restored.next_u64(), rng.next_u64());
+    }
+
+    #[test]
+    fn restore_dice_continues_after_earlier_draws() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let (world, from_id, to_id) = world_with_two_adjacent_areas_full(ids[0], ids[1], 8, 8);
+        let mut game = Game::new(world);
+        for (i, id) in ids.iter().enumerate() {
+            game.join_player(*id, format!("Player {i}")).unwrap();
+        }
+        game.state = GameState::InProgress { turn: 0 };
+        let mut restored = game.clone();
+
+        game.attack(from_id, to_id, ids[0]).unwrap();
+        let draws = game.dice_draws();
+        assert!(draws > 0);
+
+        restored.restore_dice(game.seed, draws);
+        assert_eq!(restored.dice_draws(), draws);
+        assert_eq!(
+            restored.rng.as_mut().unwrap().next_u64(),
+            game.rng.as_mut().unwrap().next_u64()
+        );
+    }
+
     // ================================================================
     // ==== Game serialization ====
     // ================================================================
@@ -815,10 +2138,192 @@ mod tests {
         assert_eq!(deser.state, GameState::WaitingForPlayers);
     }
 
+    /// Helper: a game with two players, a spectator and two areas in `state`.
+    fn game_in_state(state: GameState) -> Game {
+        let (world, _, _) = world_with_two_adjacent_areas(Uuid::new_v4(), Uuid::new_v4(), 3);
+        let mut game = Game::new(world);
+        add_players(&mut game, 2);
+        game.join_spectator(Uuid::new_v4(), "Watcher".to_string())
+            .unwrap();
+        game.state = state;
+        game
+    }
+
+    /// Helper: every object key anywhere in a JSON value.
+    fn json_keys(value: &serde_json::Value) -> Vec<String> {
+        match value {
+            serde_json::Value::Object(map) => map
+                .iter()
+                .flat_map(|(key, value)| std::iter::once(key.clone()).chain(json_keys(value)))
+                .collect(),
+            serde_json::Value::Array(items) => items.iter().flat_map(json_keys).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn all_states() -> [GameState; 3] {
+        [
+            GameState::WaitingForPlayers,
+            GameState::InProgress { turn: 1 },
+            GameState::Finished,
+        ]
+    }
+
+    #[test]
+    fn game_roundtrip_in_every_state() {
+        for state in all_states() {
+            let game = game_in_state(state.clone());
+            let json = serde_json::to_string(&game).unwrap();
+            let deser: Game = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(deser.id, game.id);
+            assert_eq!(deser.state, state);
+            assert_eq!(deser.players, game.players);
+            assert_eq!(deser.spectators.len(), 1);
+            assert_eq!(deser.world.areas, game.world.areas);
+            assert_eq!(deser.config, game.config);
+            assert_eq!(deser.turn_number, game.turn_number);
+        }
+    }
+
+    #[test]
+    fn turn_number_survives_serialization() {
+        let mut game = game_in_state(GameState::InProgress { turn: 0 });
+        end_current_turn(&mut game);
+        end_current_turn(&mut game);
+
+        let deser: Game = serde_json::from_str(&serde_json::to_string(&game).unwrap()).unwrap();
+        assert_eq!(deser.turn_number, 2);
+    }
+
+    #[test]
+    fn game_without_turn_number_deserializes_at_zero() {
+        let mut value = serde_json::to_value(new_game()).unwrap();
+        value.as_object_mut().unwrap().remove("turn_number");
+        let game: Game = serde_json::from_value(value).unwrap();
+        assert_eq!(game.turn_number, 0);
+    }
+
+    #[test]
+    fn game_json_has_expected_top_level_fields() {
+        let value = serde_json::to_value(game_in_state(GameState::WaitingForPlayers)).unwrap();
+        let mut fields: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+        fields.sort();
+        assert_eq!(
+            fields,
+            [
+                "config",
+                "id",
+                "players",
+                "spectators",
+                "state",
+                "turn_number",
+                "world"
+            ]
+        );
+    }
+
+    #[test]
+    fn game_json_contains_no_sensitive_fields() {
+        for state in all_states() {
+            let value = serde_json::to_value(game_in_state(state)).unwrap();
+            let sensitive = json_keys(&value).into_iter().find(|key| {
+                let key = key.to_lowercase();
+                ["password", "hash", "email", "token", "secret", "seed"]
+                    .iter()
+                    .any(|word| key.contains(word))
+            });
+            assert_eq!(sensitive, None);
+        }
+    }
+
     // ================================================================
     // ==== GameError display messages ====
     // ================================================================
 
+    // ================================================================
+    // ==== Game::resign ====
+    // ================================================================
+
+    /// Helper: a started game with one single-tile area per player, owned
+    /// by that player, and the first player to move.
+    fn game_with_area_per_player(n: usize) -> (Game, Vec<Uuid>) {
+        let mut game = Game::new(unowned_world(n));
+        let ids = add_players(&mut game, n);
+        for (area, id) in game.world.areas.values_mut().zip(&ids) {
+            area.owner = Some(*id);
+            area.stack = Stack::new(2).unwrap();
+        }
+        game.state = GameState::InProgress { turn: 0 };
+        (game, ids)
+    }
+
+    #[test]
+    fn resign_finishes_two_player_game() {
+        let (mut game, ids) = game_with_area_per_player(2);
+
+        assert_eq!(game.resign(ids[1]).unwrap(), Some(ids[0]));
+        assert_eq!(game.state, GameState::Finished);
+        assert_eq!(game.winner().unwrap().id, ids[0]);
+        assert!(game.world.is_player_eliminated(ids[1]));
+    }
+
+    #[test]
+    fn resign_continues_game_with_two_players_left() {
+        let (mut game, ids) = game_with_area_per_player(3);
+
+        assert_eq!(game.resign(ids[2]).unwrap(), None);
+        assert_eq!(game.state, GameState::InProgress { turn: 0 });
+        assert!(game.world.is_player_eliminated(ids[2]));
+        assert!(game.world.areas.values().any(|area| area.is_not_owned()));
+        assert!(game.is_alive(ids[0]) && game.is_alive(ids[1]));
+    }
+
+    #[test]
+    fn resign_on_own_turn_passes_turn_on() {
+        let (mut game, ids) = game_with_area_per_player(3);
+
+        game.resign(ids[0]).unwrap();
+        assert_eq!(game.state, GameState::InProgress { turn: 1 });
+
+        // The resigned player is skipped from now on
+        game.end_turn(ids[1]).unwrap();
+        game.end_turn(ids[2]).unwrap();
+        assert_eq!(game.current_player_id(), Some(ids[1]));
+    }
+
+    #[test]
+    fn resign_is_recorded_in_move_log() {
+        let (mut game, ids) = game_with_area_per_player(3);
+        game.turn_number = 4;
+
+        game.resign(ids[1]).unwrap();
+        assert_eq!(
+            game.move_log(),
+            [MoveRecord::Resign {
+                turn: 4,
+                player_id: ids[1]
+            }]
+        );
+    }
+
+    #[test]
+    fn resign_requires_game_in_progress_and_a_player() {
+        let (mut game, ids) = game_with_area_per_player(2);
+
+        assert!(matches!(
+            game.resign(Uuid::new_v4()),
+            Err(GameError::PlayerNotInGame)
+        ));
+        game.state = GameState::WaitingForPlayers;
+        assert!(matches!(
+            game.resign(ids[0]),
+            Err(GameError::GameNotStarted)
+        ));
+        game.state = GameState::Finished;
+        assert!(matches!(game.resign(ids[0]), Err(GameError::GameFinished)));
+    }
+
     #[test]
     fn game_error_messages() {
         assert_eq!(GameError::GameFull.to_string(), "the game is already full");
@@ -826,6 +2331,10 @@ mod tests {
             GameError::PlayerAlreadyInGame.to_string(),
             "player is already in the game"
         );
+        assert_eq!(
+            GameError::PlayerNotInGame.to_string(),
+            "player is not in the game"
+        );
         assert_eq!(
             GameError::NotPlayerTurn.to_string(),
             "it's not the player's turn"
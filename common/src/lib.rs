@@ -1,15 +1,23 @@
 mod area;
 mod color;
+mod command;
+mod config;
+mod diff;
 mod game;
 mod player;
 mod stack;
+mod terrain;
 mod tile;
 mod world;
 
 pub use area::*;
 pub use color::*;
+pub use command::*;
+pub use config::*;
+pub use diff::*;
 pub use game::*;
 pub use player::*;
 pub use stack::*;
+pub use terrain::*;
 pub use tile::*;
 pub use world::*;
@@ -1,8 +1,9 @@
-use crate::Color;
+use crate::{Color, World};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Player {
     pub id: Uuid,
     pub name: String,
@@ -10,9 +11,15 @@ pub struct Player {
     stored_dice: usize,
 }
 
-impl Player {
-    const MAX_STORED_DICE: usize = 20;
+/// A user watching a game without taking part in it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SpectatorInfo {
+    pub id: Uuid,
+    pub name: String,
+}
 
+impl Player {
     pub fn new(id: Uuid, name: String, color: Color) -> Self {
         Self {
             id,
@@ -22,11 +29,9 @@ impl Player {
         }
     }
 
-    pub fn store_dice(&mut self, amount: usize) {
-        self.stored_dice = self
-            .stored_dice
-            .saturating_add(amount)
-            .clamp(0, Self::MAX_STORED_DICE);
+    /// Keep `amount` dice for a later turn, holding at most `cap` in total.
+    pub fn store_dice(&mut self, amount: usize, cap: usize) {
+        self.stored_dice = self.stored_dice.saturating_add(amount).min(cap);
     }
 
     pub fn take_stored_dice(&mut self) -> usize {
@@ -34,11 +39,30 @@ impl Player {
         self.stored_dice = 0;
         stored
     }
+
+    /// Whether the player owns no areas in `world`.
+    pub fn is_eliminated(&self, world: &World) -> bool {
+        world.is_player_eliminated(self.id)
+    }
+
+    /// How many areas the player owns in `world`.
+    pub fn area_count(&self, world: &World) -> usize {
+        world.player_area_count(self.id)
+    }
+
+    /// The hex color the player is drawn with.
+    ///
+    /// Callers should prefer this over `color.to_hex()` so custom player
+    /// colors can be added later without touching them.
+    pub const fn display_color(&self) -> &str {
+        self.color.to_hex()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::DEFAULT_BONUS_DICE_CAP;
 
     // ==== Helpers ====
 
@@ -57,41 +81,74 @@ mod tests {
     #[test]
     fn store_dice_adds_amount() {
         let mut player = make_player();
-        player.store_dice(5);
+        player.store_dice(5, DEFAULT_BONUS_DICE_CAP);
         assert_eq!(player.stored_dice, 5);
     }
 
     #[test]
     fn store_dice_accumulates() {
         let mut player = make_player();
-        player.store_dice(3);
-        player.store_dice(4);
+        player.store_dice(3, DEFAULT_BONUS_DICE_CAP);
+        player.store_dice(4, DEFAULT_BONUS_DICE_CAP);
         assert_eq!(player.stored_dice, 7);
     }
 
     #[test]
     fn store_dice_caps_at_max() {
         let mut player = make_player();
-        player.store_dice(Player::MAX_STORED_DICE + 5);
-        assert_eq!(player.stored_dice, Player::MAX_STORED_DICE);
+        player.store_dice(DEFAULT_BONUS_DICE_CAP + 5, DEFAULT_BONUS_DICE_CAP);
+        assert_eq!(player.stored_dice, DEFAULT_BONUS_DICE_CAP);
     }
 
     #[test]
     fn store_dice_caps_across_multiple_calls() {
         let mut player = make_player();
-        player.store_dice(15);
-        player.store_dice(10);
-        assert_eq!(player.stored_dice, Player::MAX_STORED_DICE);
+        player.store_dice(15, DEFAULT_BONUS_DICE_CAP);
+        player.store_dice(10, DEFAULT_BONUS_DICE_CAP);
+        assert_eq!(player.stored_dice, DEFAULT_BONUS_DICE_CAP);
+    }
+
+    #[test]
+    fn store_dice_caps_at_given_cap() {
+        let mut player = make_player();
+        player.store_dice(3, 5);
+        player.store_dice(4, 5);
+        assert_eq!(player.stored_dice, 5);
+    }
+
+    #[test]
+    fn store_dice_with_default_cap_keeps_up_to_twenty() {
+        let mut player = make_player();
+        player.store_dice(19, 20);
+        assert_eq!(player.stored_dice, 19);
+        player.store_dice(2, 20);
+        assert_eq!(player.stored_dice, 20);
     }
 
     #[test]
     fn store_dice_zero_is_noop() {
         let mut player = make_player();
-        player.store_dice(3);
-        player.store_dice(0);
+        player.store_dice(3, DEFAULT_BONUS_DICE_CAP);
+        player.store_dice(0, DEFAULT_BONUS_DICE_CAP);
         assert_eq!(player.stored_dice, 3);
     }
 
+    #[test]
+    fn store_dice_below_cap_keeps_exact_sum() {
+        let mut player = make_player();
+        player.store_dice(2, 5);
+        player.store_dice(2, 5);
+        assert_eq!(player.stored_dice, 4);
+    }
+
+    #[test]
+    fn store_dice_does_not_overflow() {
+        let mut player = make_player();
+        player.store_dice(3, usize::MAX);
+        player.store_dice(usize::MAX, usize::MAX);
+        assert_eq!(player.stored_dice, usize::MAX);
+    }
+
     // ==== take_stored_dice ====
 
     #[test]
@@ -103,14 +160,14 @@ mod tests {
     #[test]
     fn take_stored_dice_returns_stored_amount() {
         let mut player = make_player();
-        player.store_dice(7);
+        player.store_dice(7, DEFAULT_BONUS_DICE_CAP);
         assert_eq!(player.take_stored_dice(), 7);
     }
 
     #[test]
     fn take_stored_dice_resets_to_zero() {
         let mut player = make_player();
-        player.store_dice(5);
+        player.store_dice(5, DEFAULT_BONUS_DICE_CAP);
         player.take_stored_dice();
         assert_eq!(player.stored_dice, 0);
     }
@@ -118,7 +175,7 @@ mod tests {
     #[test]
     fn take_stored_dice_second_call_returns_zero() {
         let mut player = make_player();
-        player.store_dice(10);
+        player.store_dice(10, DEFAULT_BONUS_DICE_CAP);
         player.take_stored_dice();
         assert_eq!(player.take_stored_dice(), 0);
     }
@@ -126,10 +183,53 @@ mod tests {
     #[test]
     fn store_and_take_interleaved() {
         let mut player = make_player();
-        player.store_dice(3);
+        player.store_dice(3, DEFAULT_BONUS_DICE_CAP);
         assert_eq!(player.take_stored_dice(), 3);
-        player.store_dice(2);
-        player.store_dice(4);
+        player.store_dice(2, DEFAULT_BONUS_DICE_CAP);
+        player.store_dice(4, DEFAULT_BONUS_DICE_CAP);
         assert_eq!(player.take_stored_dice(), 6);
     }
+
+    // ==== is_eliminated / area_count ====
+
+    #[test]
+    fn area_count_and_elimination_follow_world_ownership() {
+        let player = make_player();
+        let mut world = World::default();
+        assert!(player.is_eliminated(&world));
+        assert_eq!(player.area_count(&world), 0);
+
+        for y in 0..2 {
+            let mut area = crate::Area::new([crate::Tile::new(0, y)].into());
+            area.owner = Some(player.id);
+            world.areas.insert(area.id, area);
+        }
+        let unowned = crate::Area::new([crate::Tile::new(1, 0)].into());
+        world.areas.insert(unowned.id, unowned);
+
+        assert!(!player.is_eliminated(&world));
+        assert_eq!(player.area_count(&world), 2);
+    }
+
+    // ==== display_color ====
+
+    #[test]
+    fn display_color_matches_color_hex() {
+        for color in Color::ALL {
+            let player = Player::new(Uuid::new_v4(), "Alice".into(), color);
+            assert_eq!(player.display_color(), player.color.to_hex());
+        }
+    }
+
+    #[test]
+    fn display_color_is_const_evaluable() {
+        const PLAYER: &Player = &Player {
+            id: Uuid::nil(),
+            name: String::new(),
+            color: Color::Blue,
+            stored_dice: 0,
+        };
+        const HEX: &str = PLAYER.display_color();
+        assert_eq!(HEX, Color::Blue.to_hex());
+    }
 }
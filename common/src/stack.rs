@@ -1,4 +1,9 @@
-use serde::{Deserialize, Serialize};
+use crate::Terrain;
+use rand::{
+    Rng,
+    distr::{Distribution, Uniform},
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
 use thiserror::Error;
 
 #[derive(Debug, Error, Clone)]
@@ -12,11 +17,60 @@ pub enum StackError {
 
 type Result<T> = std::result::Result<T, StackError>;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Which side won an attack, see [`Stack::resolve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackOutcome {
+    AttackerWins,
+    DefenderWins,
+}
+
+/// A stack of dice on an area. Serializes as its plain dice count, with
+/// `0` for a hidden stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Stack {
     count: usize,
 }
 
+impl Serialize for Stack {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.count as u64)
+    }
+}
+
+/// Accepted stack representations. The object form is what stacks were
+/// stored as before, kept so older saved games still load.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StackRepr {
+    Count(usize),
+    Legacy { count: usize },
+}
+
+impl<'de> Deserialize<'de> for Stack {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let count = match StackRepr::deserialize(deserializer)? {
+            StackRepr::Count(count) | StackRepr::Legacy { count } => count,
+        };
+        if count > Self::MAX {
+            return Err(D::Error::custom(format!(
+                "stack of {count} dice exceeds the maximum of {}",
+                Self::MAX
+            )));
+        }
+        Ok(Stack { count })
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for Stack {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        <usize as utoipa::PartialSchema>::schema()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for Stack {}
+
 impl Default for Stack {
     fn default() -> Self {
         Stack { count: 1 }
@@ -27,12 +81,38 @@ impl Stack {
     pub const MAX: usize = 8;
     pub const MIN: usize = 1;
 
+    /// Create a stack holding `count` dice.
+    pub fn new(count: usize) -> Result<Self> {
+        if count < Self::MIN {
+            Err(StackError::Underflow)
+        } else if count > Self::MAX {
+            Err(StackError::Overflow)
+        } else {
+            Ok(Stack { count })
+        }
+    }
+
+    /// A stack whose dice count is unknown to the viewer.
+    pub fn hidden() -> Self {
+        Stack { count: 0 }
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.count == 0
+    }
+
     pub fn count(&self) -> usize {
         self.count
     }
 
     pub fn is_full(&self) -> bool {
-        self.count >= Self::MAX
+        self.is_full_at(Self::MAX)
+    }
+
+    /// Whether the stack holds `max` dice or more, with `max` capped at
+    /// [`Stack::MAX`].
+    pub fn is_full_at(&self, max: usize) -> bool {
+        self.count >= max.min(Self::MAX)
     }
 
     pub fn is_single(&self) -> bool {
@@ -40,7 +120,12 @@ impl Stack {
     }
 
     pub fn increment(&mut self) -> Result<()> {
-        if self.is_full() {
+        self.increment_up_to(Self::MAX)
+    }
+
+    /// Add a die unless the stack already holds `max` dice.
+    pub fn increment_up_to(&mut self, max: usize) -> Result<()> {
+        if self.is_full_at(max) {
             Err(StackError::Overflow)
         } else {
             self.count += 1;
@@ -48,6 +133,18 @@ impl Stack {
         }
     }
 
+    /// Add `n` dice at once. Fails without changing the stack if that would
+    /// exceed [`Stack::MAX`].
+    pub fn add_n(&mut self, n: usize) -> Result<()> {
+        match self.count.checked_add(n) {
+            Some(count) if count <= Self::MAX => {
+                self.count = count;
+                Ok(())
+            }
+            _ => Err(StackError::Overflow),
+        }
+    }
+
     pub fn decrement(&mut self) -> Result<()> {
         if self.is_single() {
             Err(StackError::Underflow)
@@ -70,16 +167,38 @@ impl Stack {
         }
     }
 
-    // Simulate defence roll with all dice in the stack.
-    pub fn roll(&self) -> usize {
-        // Simulate rolling `count` dice and summing the results.
-        (0..self.count).map(|_| rand::random_range(1..=6)).sum()
+    /// Roll all dice in the stack with `rng` and sum the results.
+    pub fn roll(&self, rng: &mut impl Rng) -> usize {
+        let die = Uniform::new_inclusive(1, 6).expect("die range is valid");
+        (0..self.count).map(|_| die.sample(rng)).sum()
+    }
+
+    /// Roll all dice of an attacking stack.
+    pub fn attack_roll(&self, rng: &mut impl Rng) -> usize {
+        self.roll(rng)
+    }
+
+    /// Roll all dice in the stack, adding the defence bonus of `terrain`.
+    pub fn defence_roll(&self, terrain: Terrain, rng: &mut impl Rng) -> usize {
+        self.roll(rng) + terrain.defence_bonus()
+    }
+
+    /// Compare an attack roll with a defence roll. The attacker has to roll
+    /// higher, so ties go to the defender.
+    pub fn resolve(attack: usize, defence: usize) -> AttackOutcome {
+        if attack > defence {
+            AttackOutcome::AttackerWins
+        } else {
+            AttackOutcome::DefenderWins
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
 
     // ==== Default ====
 
@@ -89,6 +208,34 @@ mod tests {
         assert_eq!(stack.count(), Stack::MIN);
     }
 
+    // ==== new ====
+
+    #[test]
+    fn new_accepts_counts_within_bounds() {
+        assert_eq!(Stack::new(Stack::MIN).unwrap().count(), Stack::MIN);
+        assert_eq!(Stack::new(5).unwrap().count(), 5);
+        assert_eq!(Stack::new(Stack::MAX).unwrap().count(), Stack::MAX);
+    }
+
+    #[test]
+    fn new_rejects_counts_out_of_bounds() {
+        assert!(matches!(Stack::new(0), Err(StackError::Underflow)));
+        assert!(matches!(
+            Stack::new(Stack::MAX + 1),
+            Err(StackError::Overflow)
+        ));
+    }
+
+    // ==== hidden ====
+
+    #[test]
+    fn hidden_stack_has_count_zero() {
+        let stack = Stack::hidden();
+        assert_eq!(stack.count(), 0);
+        assert!(stack.is_hidden());
+        assert!(!Stack::default().is_hidden());
+    }
+
     // ==== Constants ====
 
     #[test]
@@ -171,12 +318,71 @@ mod tests {
         assert_eq!(stack.count(), Stack::MAX);
     }
 
+    #[test]
+    fn increment_up_to_rejects_increments_past_given_max() {
+        let mut stack = Stack::default();
+        for _ in 1..4 {
+            stack.increment_up_to(4).unwrap();
+        }
+        assert!(stack.is_full_at(4));
+        assert!(!stack.is_full());
+        assert!(matches!(
+            stack.increment_up_to(4).unwrap_err(),
+            StackError::Overflow
+        ));
+        assert_eq!(stack.count(), 4);
+    }
+
+    #[test]
+    fn is_full_at_caps_max_at_stack_max() {
+        let stack = Stack::new(Stack::MAX).unwrap();
+        assert!(stack.is_full_at(Stack::MAX + 5));
+    }
+
     #[test]
     fn increment_overflow_error_displays_message() {
         let err = StackError::Overflow;
         assert_eq!(err.to_string(), "Stack overflow");
     }
 
+    // ==== add_n ====
+
+    #[test]
+    fn add_n_zero_is_noop() {
+        let mut stack = Stack::default();
+        stack.add_n(0).unwrap();
+        assert_eq!(stack.count(), 1);
+    }
+
+    #[test]
+    fn add_n_up_to_max_succeeds() {
+        let mut stack = Stack::default();
+        stack.add_n(7).unwrap();
+        assert_eq!(stack.count(), Stack::MAX);
+        assert!(stack.is_full());
+    }
+
+    #[test]
+    fn add_n_past_max_returns_overflow() {
+        let mut stack = Stack::default();
+        assert!(matches!(stack.add_n(8), Err(StackError::Overflow)));
+    }
+
+    #[test]
+    fn add_n_failure_leaves_stack_unchanged() {
+        let mut stack = Stack::default();
+        stack.add_n(3).unwrap();
+        assert!(stack.add_n(5).is_err());
+        assert_eq!(stack.count(), 4);
+    }
+
+    #[test]
+    fn add_n_huge_value_does_not_overflow_usize() {
+        let mut stack = Stack::default();
+        assert!(matches!(stack.add_n(usize::MAX), Err(StackError::Overflow)));
+        assert_eq!(stack.count(), 1);
+    }
+
     // ==== decrement ====
 
     #[test]
@@ -268,6 +474,115 @@ mod tests {
         }
     }
 
+    // ==== roll ====
+
+    #[test]
+    fn roll_stays_within_dice_range() {
+        let stack = Stack::new(3).unwrap();
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            assert!((3..=18).contains(&stack.roll(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn roll_is_reproducible_with_same_seed() {
+        let stack = Stack::new(Stack::MAX).unwrap();
+        let rolls = |seed| {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            (0..10).map(|_| stack.roll(&mut rng)).collect::<Vec<_>>()
+        };
+        assert_eq!(rolls(1), rolls(1));
+        assert_ne!(rolls(1), rolls(2));
+    }
+
+    // ==== attack_roll ====
+
+    #[test]
+    fn attack_roll_of_single_die_is_between_one_and_six() {
+        let stack = Stack::default();
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            assert!((1..=6).contains(&stack.attack_roll(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn attack_roll_of_full_stack_sums_all_dice() {
+        let stack = Stack::new(Stack::MAX).unwrap();
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            assert!((Stack::MAX..=6 * Stack::MAX).contains(&stack.attack_roll(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn attack_roll_matches_roll_with_same_seed() {
+        let stack = Stack::new(4).unwrap();
+        let attack = stack.attack_roll(&mut SmallRng::seed_from_u64(7));
+        let roll = stack.roll(&mut SmallRng::seed_from_u64(7));
+        assert_eq!(attack, roll);
+    }
+
+    // ==== defence_roll ====
+
+    #[test]
+    fn defence_roll_of_full_stack_on_plains_sums_all_dice() {
+        let stack = Stack::new(Stack::MAX).unwrap();
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let roll = stack.defence_roll(Terrain::Plains, &mut rng);
+            assert!((Stack::MAX..=6 * Stack::MAX).contains(&roll));
+        }
+    }
+
+    #[test]
+    fn defence_roll_on_mountain_is_at_least_two_for_single_die() {
+        let stack = Stack::default();
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            assert!((2..=7).contains(&stack.defence_roll(Terrain::Mountain, &mut rng)));
+        }
+    }
+
+    #[test]
+    fn defence_roll_on_plains_has_no_bonus() {
+        let stack = Stack::default();
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            assert!((1..=6).contains(&stack.defence_roll(Terrain::Plains, &mut rng)));
+        }
+    }
+
+    // ==== resolve ====
+
+    #[test]
+    fn resolve_higher_attack_wins() {
+        assert_eq!(Stack::resolve(7, 6), AttackOutcome::AttackerWins);
+    }
+
+    #[test]
+    fn resolve_higher_defence_wins() {
+        assert_eq!(Stack::resolve(6, 7), AttackOutcome::DefenderWins);
+    }
+
+    #[test]
+    fn resolve_tie_goes_to_defender() {
+        for roll in [1, 6, 6 * Stack::MAX] {
+            assert_eq!(Stack::resolve(roll, roll), AttackOutcome::DefenderWins);
+        }
+    }
+
+    #[test]
+    fn resolve_extremes() {
+        // A single die can never beat a full stack, and the other way round
+        assert_eq!(Stack::resolve(6, Stack::MAX), AttackOutcome::DefenderWins);
+        assert_eq!(
+            Stack::resolve(6 * Stack::MAX, 6),
+            AttackOutcome::AttackerWins
+        );
+    }
+
     // ==== Serialization ====
 
     #[test]
@@ -280,6 +595,31 @@ mod tests {
         assert_eq!(deserialized.count(), stack.count());
     }
 
+    #[test]
+    fn serializes_as_plain_count() {
+        let stack = Stack::new(5).unwrap();
+        assert_eq!(serde_json::to_string(&stack).unwrap(), "5");
+    }
+
+    #[test]
+    fn hidden_serializes_as_zero() {
+        assert_eq!(serde_json::to_string(&Stack::hidden()).unwrap(), "0");
+        let stack: Stack = serde_json::from_str("0").unwrap();
+        assert!(stack.is_hidden());
+    }
+
+    #[test]
+    fn deserializes_legacy_object_form() {
+        let stack: Stack = serde_json::from_str(r#"{"count":5}"#).unwrap();
+        assert_eq!(stack.count(), 5);
+    }
+
+    #[test]
+    fn deserialize_rejects_count_above_max() {
+        assert!(serde_json::from_str::<Stack>("9").is_err());
+        assert!(serde_json::from_str::<Stack>(r#"{"count":9}"#).is_err());
+    }
+
     #[test]
     fn clone_produces_independent_copy() {
         let mut stack = Stack::default();
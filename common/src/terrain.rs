@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+/// The kind of land an [`Area`](crate::Area) consists of.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Terrain {
+    #[default]
+    Plains,
+    /// Grants its owner an extra bonus die each turn, connected or not.
+    Forest,
+    /// Adds one to every defence roll made from it.
+    Mountain,
+    /// Impassable; can never be owned.
+    Water,
+}
+
+impl Terrain {
+    /// Parse the terrain keyword used in `.world` files, e.g. `forest`.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "plains" => Some(Terrain::Plains),
+            "forest" => Some(Terrain::Forest),
+            "mountain" => Some(Terrain::Mountain),
+            "water" => Some(Terrain::Water),
+            _ => None,
+        }
+    }
+
+    /// The keyword [`Terrain::from_keyword`] parses into this terrain.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Terrain::Plains => "plains",
+            Terrain::Forest => "forest",
+            Terrain::Mountain => "mountain",
+            Terrain::Water => "water",
+        }
+    }
+
+    pub fn is_ownable(self) -> bool {
+        self != Terrain::Water
+    }
+
+    /// Bonus dice granted to the owner of an area with this terrain.
+    pub fn bonus_dice(self) -> usize {
+        match self {
+            Terrain::Forest => 1,
+            _ => 0,
+        }
+    }
+
+    /// Amount added to the defender's roll for an area with this terrain.
+    pub fn defence_bonus(self) -> usize {
+        match self {
+            Terrain::Mountain => 1,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_plains() {
+        assert_eq!(Terrain::default(), Terrain::Plains);
+    }
+
+    #[test]
+    fn from_keyword_parses_all_variants() {
+        assert_eq!(Terrain::from_keyword("plains"), Some(Terrain::Plains));
+        assert_eq!(Terrain::from_keyword("forest"), Some(Terrain::Forest));
+        assert_eq!(Terrain::from_keyword("mountain"), Some(Terrain::Mountain));
+        assert_eq!(Terrain::from_keyword("water"), Some(Terrain::Water));
+        assert_eq!(Terrain::from_keyword("lava"), None);
+    }
+
+    #[test]
+    fn keyword_roundtrips_through_from_keyword() {
+        for terrain in [
+            Terrain::Plains,
+            Terrain::Forest,
+            Terrain::Mountain,
+            Terrain::Water,
+        ] {
+            assert_eq!(Terrain::from_keyword(terrain.keyword()), Some(terrain));
+        }
+    }
+
+    #[test]
+    fn only_water_is_not_ownable() {
+        assert!(Terrain::Plains.is_ownable());
+        assert!(Terrain::Forest.is_ownable());
+        assert!(Terrain::Mountain.is_ownable());
+        assert!(!Terrain::Water.is_ownable());
+    }
+
+    #[test]
+    fn only_forest_grants_bonus_dice() {
+        assert_eq!(Terrain::Forest.bonus_dice(), 1);
+        assert_eq!(Terrain::Plains.bonus_dice(), 0);
+        assert_eq!(Terrain::Mountain.bonus_dice(), 0);
+    }
+
+    #[test]
+    fn only_mountain_grants_defence_bonus() {
+        assert_eq!(Terrain::Mountain.defence_bonus(), 1);
+        assert_eq!(Terrain::Plains.defence_bonus(), 0);
+        assert_eq!(Terrain::Forest.defence_bonus(), 0);
+    }
+}
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 
 // The tile grid is defined with the top-left corner as (0, 0) and the bottom-right corner as (width-1, height-1).
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+// Tiles are ordered by column, then by row.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Tile {
     x: usize,
     y: usize,
@@ -14,6 +16,14 @@ impl Tile {
         Self { x, y }
     }
 
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
     // Converts tile coordinates to world coordinates (center of the tile)
     // For hexagonal tiles, we need to account for the staggered rows. Odd rows are offset by half a tile width.
     pub fn to_world_coordinates(&self) -> (f32, f32) {
@@ -29,6 +39,111 @@ impl Tile {
         )
     }
 
+    /// The tile whose center is nearest to the world coordinates
+    /// `(wx, wy)`, the inverse of [`Tile::to_world_coordinates`]. Returns
+    /// `None` for non-finite coordinates or when the nearest tile would lie
+    /// outside the grid.
+    pub fn from_world_coordinates(wx: f32, wy: f32) -> Option<Tile> {
+        if !wx.is_finite() || !wy.is_finite() {
+            return None;
+        }
+
+        // Columns are staggered, so the nearest center may be in a
+        // neighbouring column of the one `wx` falls in. Candidates are
+        // clamped to the grid and rejected below if too far away.
+        let column = ((wx - Self::SIZE / 2.0) / Self::SIZE).round() as i64;
+        (column - 1..=column + 1)
+            .map(|x| {
+                let x = x.max(0);
+                let offset = if x % 2 == 0 { Self::SIZE / 2.0 } else { 0.0 };
+                let y = ((wy - Self::SIZE / 2.0 - offset) / Self::SIZE).round() as i64;
+                let tile = Tile::new(x as usize, y.max(0) as usize);
+                let (cx, cy) = tile.to_world_coordinates();
+                (tile, (cx - wx).powi(2) + (cy - wy).powi(2))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(tile, _)| tile)
+            .filter(|tile| {
+                let (cx, cy) = tile.to_world_coordinates();
+                (cx - wx).abs() <= Self::SIZE && (cy - wy).abs() <= Self::SIZE
+            })
+    }
+
+    /// World coordinates of the centroid of tiles whose axial coordinates
+    /// average to `(q, r)`, with `odd_share` of them in odd columns. Even
+    /// columns sit half a tile lower in world space, so the share of odd
+    /// columns is needed to undo that shift.
+    pub fn centroid_to_world_coordinates(q: f64, r: f64, odd_share: f64) -> (f32, f32) {
+        let size = Self::SIZE as f64;
+        (
+            (q * size + size / 2.0) as f32,
+            ((r + q / 2.0) * size + size - odd_share * size) as f32,
+        )
+    }
+
+    /// Axial (q, r) coordinates of the tile, as used by most hex grid
+    /// algorithms. Columns are stored in "odd-q" offset layout, where odd
+    /// columns sit half a tile higher.
+    pub fn to_axial(&self) -> (i32, i32) {
+        let q = self.x as i32;
+        let r = self.y as i32 - (q - (q & 1)) / 2;
+        (q, r)
+    }
+
+    /// The tile at axial coordinates (q, r), or `None` if it would have
+    /// negative offset coordinates.
+    pub fn from_axial(q: i32, r: i32) -> Option<Tile> {
+        let y = r + (q - (q & 1)) / 2;
+        (q >= 0 && y >= 0).then(|| Tile::new(q as usize, y as usize))
+    }
+
+    /// Number of steps between two tiles, using the cube distance
+    /// `max(|dq|, |dr|, |dq + dr|)` on axial coordinates.
+    pub fn distance_axial(&self, other: &Tile) -> usize {
+        let (q1, r1) = self.to_axial();
+        let (q2, r2) = other.to_axial();
+        let (dq, dr) = (q2 - q1, r2 - r1);
+        dq.abs().max(dr.abs()).max((dq + dr).abs()) as usize
+    }
+
+    /// Number of steps between two tiles on the hex grid.
+    pub fn distance(&self, other: &Tile) -> usize {
+        self.distance_axial(other)
+    }
+
+    /// Axial directions, ordered so that walking each in turn traces a ring
+    const DIRECTIONS: [(i32, i32); 6] = [(1, -1), (1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1)];
+
+    /// The six tiles directly adjacent to this one, one per hex direction
+    /// in the order upper right, lower right, below, lower left, upper left
+    /// and above. Directions that leave the grid are `None`.
+    pub fn neighbors(&self) -> [Option<Tile>; 6] {
+        let (q, r) = self.to_axial();
+        Self::DIRECTIONS.map(|(dq, dr)| Self::from_axial(q + dq, r + dr))
+    }
+
+    /// All tiles at exactly `radius` steps from this one. Tiles that would
+    /// fall outside the grid (negative coordinates) are skipped.
+    pub fn ring(&self, radius: usize) -> Vec<Tile> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        let radius = radius as i32;
+        let (q, r) = self.to_axial();
+        let (mut q, mut r) = (q - radius, r);
+
+        let mut tiles = Vec::with_capacity(6 * radius as usize);
+        for (dq, dr) in Self::DIRECTIONS {
+            for _ in 0..radius {
+                tiles.extend(Self::from_axial(q, r));
+                q += dq;
+                r += dr;
+            }
+        }
+        tiles
+    }
+
     pub fn is_adjacent(&self, other: &Tile) -> bool {
         let dx = other.x as isize - self.x as isize;
         let dy = other.y as isize - self.y as isize;
@@ -46,6 +161,7 @@ impl Tile {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     /// Helper: create a Tile.
     fn tile(x: usize, y: usize) -> Tile {
@@ -91,6 +207,48 @@ mod tests {
         assert!((wy - 4.0).abs() < f32::EPSILON); // 3 + 0.5 + 0.5
     }
 
+    // ==== Tile::x / Tile::y ====
+
+    #[test]
+    fn tile_accessors_return_coordinates() {
+        let t = tile(3, 7);
+        assert_eq!((t.x(), t.y()), (3, 7));
+    }
+
+    // ==== Tile::from_world_coordinates ====
+
+    #[test]
+    fn tile_from_world_coordinates_inverts_to_world_coordinates() {
+        for x in 0..10 {
+            for y in 0..10 {
+                let (wx, wy) = tile(x, y).to_world_coordinates();
+                assert_eq!(Tile::from_world_coordinates(wx, wy), Some(tile(x, y)));
+            }
+        }
+    }
+
+    #[test]
+    fn tile_from_world_coordinates_snaps_to_nearest_tile() {
+        // Slightly off the center of (2, 3), at (2.5, 4.0)
+        assert_eq!(Tile::from_world_coordinates(2.7, 3.8), Some(tile(2, 3)));
+        // Between the columns, nearer the odd column's center at (1.5, 0.5)
+        assert_eq!(Tile::from_world_coordinates(1.1, 0.5), Some(tile(1, 0)));
+        // Above the first row of an even column, which sits half a tile lower
+        assert_eq!(Tile::from_world_coordinates(0.5, 0.1), Some(tile(0, 0)));
+    }
+
+    #[test]
+    fn tile_from_world_coordinates_outside_grid_is_none() {
+        assert_eq!(Tile::from_world_coordinates(-3.0, 1.0), None);
+        assert_eq!(Tile::from_world_coordinates(0.5, -3.0), None);
+    }
+
+    #[test]
+    fn tile_from_world_coordinates_non_finite_is_none() {
+        assert_eq!(Tile::from_world_coordinates(f32::NAN, 1.0), None);
+        assert_eq!(Tile::from_world_coordinates(0.5, f32::INFINITY), None);
+    }
+
     // ==== Tile::is_adjacent ====
 
     #[test]
@@ -149,6 +307,146 @@ mod tests {
         assert!(!a.is_adjacent(&tile(4, 1)));
     }
 
+    // ==== Tile::to_axial / Tile::from_axial ====
+
+    #[test]
+    fn tile_axial_roundtrip() {
+        for x in 0..20 {
+            for y in 0..20 {
+                let t = tile(x, y);
+                let (q, r) = t.to_axial();
+                assert_eq!(Tile::from_axial(q, r), Some(t));
+            }
+        }
+    }
+
+    #[test]
+    fn tile_axial_of_origin_is_origin() {
+        assert_eq!(tile(0, 0).to_axial(), (0, 0));
+    }
+
+    #[test]
+    fn tile_from_axial_outside_grid_is_none() {
+        assert_eq!(Tile::from_axial(-1, 0), None);
+        assert_eq!(Tile::from_axial(0, -1), None);
+        assert_eq!(Tile::from_axial(2, -2), None);
+    }
+
+    #[test]
+    fn tile_distance_axial_along_column() {
+        assert_eq!(tile(2, 0).distance_axial(&tile(2, 5)), 5);
+    }
+
+    // ==== Tile::distance ====
+
+    #[test]
+    fn tile_distance_to_self_is_zero() {
+        assert_eq!(tile(4, 4).distance(&tile(4, 4)), 0);
+    }
+
+    #[test]
+    fn tile_distance_matches_adjacency() {
+        let center = tile(3, 3);
+        for x in 0..8 {
+            for y in 0..8 {
+                let other = tile(x, y);
+                assert_eq!(center.distance(&other) == 1, center.is_adjacent(&other));
+            }
+        }
+    }
+
+    #[test]
+    fn tile_distance_is_symmetric() {
+        assert_eq!(
+            tile(1, 5).distance(&tile(6, 2)),
+            tile(6, 2).distance(&tile(1, 5))
+        );
+    }
+
+    // ==== Tile::neighbors ====
+
+    #[test]
+    fn tile_neighbors_of_interior_tile() {
+        assert_eq!(
+            tile(4, 4).neighbors(),
+            [
+                Some(tile(5, 3)),
+                Some(tile(5, 4)),
+                Some(tile(4, 5)),
+                Some(tile(3, 4)),
+                Some(tile(3, 3)),
+                Some(tile(4, 3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn tile_neighbors_of_odd_column() {
+        let center = tile(3, 4);
+        let neighbors: Vec<Tile> = center.neighbors().into_iter().flatten().collect();
+        assert_eq!(neighbors.len(), 6);
+        assert!(
+            neighbors
+                .iter()
+                .all(|neighbor| center.is_adjacent(neighbor))
+        );
+    }
+
+    #[test]
+    fn tile_neighbors_of_corner_tile() {
+        assert_eq!(
+            tile(0, 0).neighbors(),
+            [None, Some(tile(1, 0)), Some(tile(0, 1)), None, None, None]
+        );
+    }
+
+    // ==== Tile::ring ====
+
+    #[test]
+    fn tile_ring_zero_is_self() {
+        assert_eq!(tile(2, 2).ring(0), vec![tile(2, 2)]);
+    }
+
+    #[test]
+    fn tile_ring_one_equals_neighbors() {
+        let center = tile(3, 4);
+        let ring: HashSet<_> = center.ring(1).into_iter().collect();
+        let neighbors: HashSet<_> = center.neighbors().into_iter().flatten().collect();
+        assert_eq!(ring, neighbors);
+        assert_eq!(ring.len(), 6);
+    }
+
+    #[test]
+    fn tile_ring_one_is_all_adjacent() {
+        for center in [tile(2, 2), tile(3, 2)] {
+            assert!(center.ring(1).iter().all(|t| center.is_adjacent(t)));
+        }
+    }
+
+    #[test]
+    fn tile_ring_on_boundary_is_clipped() {
+        let ring = tile(0, 0).ring(1);
+        assert!(ring.len() <= 6);
+        assert!(ring.iter().all(|t| tile(0, 0).is_adjacent(t)));
+    }
+
+    #[test]
+    fn tile_rings_do_not_overlap() {
+        let center = tile(5, 5);
+        let ring1: HashSet<_> = center.ring(1).into_iter().collect();
+        assert!(center.ring(2).iter().all(|t| !ring1.contains(t)));
+    }
+
+    #[test]
+    fn tile_ring_tiles_are_at_exact_distance() {
+        let center = tile(6, 6);
+        for radius in 0..5 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.len(), if radius == 0 { 1 } else { 6 * radius });
+            assert!(ring.iter().all(|t| center.distance(t) == radius));
+        }
+    }
+
     // ==== Tile equality & hashing ====
 
     #[test]
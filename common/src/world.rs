@@ -1,32 +1,299 @@
-use crate::{Area, AttackError};
-use rand::seq::IndexedRandom;
+use crate::{Area, AttackError, AttackOutcome, AttackResult, MAX_PLAYERS, Stack, Terrain, Tile};
+use rand::distr::{Distribution, Uniform};
+use rand::rngs::SmallRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::OnceLock;
+use thiserror::Error;
 use uuid::Uuid;
 
+/// Most dice an area starts the game with.
+pub const STARTING_DICE_MAX: usize = 3;
+
+/// Reasons a [`World`] cannot be played on.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum WorldError {
+    #[error("a world needs at least two areas, found {0}")]
+    NotEnoughAreas(usize),
+
+    #[error("area with ID {0} has no tiles")]
+    EmptyArea(Uuid),
+
+    #[error("tiles of area with ID {0} are not connected")]
+    DisconnectedArea(Uuid),
+
+    #[error("tile {0:?} belongs to more than one area")]
+    OverlappingTile(Tile),
+
+    #[error("area with ID {0} is not adjacent to any other area")]
+    IsolatedArea(Uuid),
+
+    #[error("area with ID {0} cannot be reached from the rest of the world")]
+    UnreachableArea(Uuid),
+
+    #[error(transparent)]
+    Parse(#[from] WorldParseError),
+}
+
+/// Reasons [`World::parse`] rejects a world definition.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum WorldParseError {
+    #[error("the {tile_count} tiles of area {area_index} do not form a connected region")]
+    NonContiguousArea {
+        area_index: usize,
+        tile_count: usize,
+    },
+}
+
+/// Descriptive information about a world, read from the `#` header lines
+/// of a `.world` file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct WorldMetadata {
+    pub name: String,
+    pub description: String,
+    pub author: String,
+    pub min_players: usize,
+    pub max_players: usize,
+}
+
+impl Default for WorldMetadata {
+    fn default() -> Self {
+        Self {
+            name: "Untitled".to_string(),
+            description: String::new(),
+            author: "Unknown".to_string(),
+            min_players: 2,
+            max_players: MAX_PLAYERS,
+        }
+    }
+}
+
+impl WorldMetadata {
+    /// Apply a `key: value` header entry. Unknown keys and unparseable
+    /// player counts are ignored.
+    fn apply(&mut self, key: &str, value: &str) {
+        match key {
+            "name" => self.name = value.to_string(),
+            "description" => self.description = value.to_string(),
+            "author" => self.author = value.to_string(),
+            "min_players" => self.min_players = value.parse().unwrap_or(self.min_players),
+            "max_players" => self.max_players = value.parse().unwrap_or(self.max_players),
+            _ => {}
+        }
+    }
+}
+
+/// Lazily computed area adjacency graph of a [`World`], filled in on the
+/// first neighbour lookup. It is only used while the world still has the
+/// same areas, so areas added, removed or replaced after that are picked
+/// up. Area tiles never change once a world is built.
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyCache(OnceLock<HashMap<Uuid, Vec<Uuid>>>);
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct World {
     pub areas: HashMap<Uuid, Area>,
+    #[serde(default)]
+    pub metadata: WorldMetadata,
+    #[serde(skip)]
+    pub precomputed: AdjacencyCache,
 }
 
 impl World {
+    /// Parse a world definition with one area per line. Each line lists the
+    /// area's tiles as whitespace separated `x,y` pairs, optionally preceded
+    /// by a name and a colon, e.g. `Northern Plains: 0,0 1,0`. A terrain
+    /// keyword such as `forest` anywhere on the line sets the area's terrain.
+    ///
+    /// Lines starting with `#` hold metadata as `# key: value`, e.g.
+    /// `# author: Jane`. Missing metadata falls back to defaults.
     pub fn from_string(s: &str) -> Self {
-        let mut areas = HashMap::new();
+        let (areas, metadata) = Self::parse_areas(s);
+        Self {
+            areas: areas.into_iter().map(|area| (area.id, area)).collect(),
+            metadata,
+            precomputed: AdjacencyCache::default(),
+        }
+    }
+
+    /// Parse a world definition like [`World::from_string`], rejecting
+    /// areas whose tiles do not form a connected region. Areas are indexed
+    /// from zero in the order they appear, not counting header or empty
+    /// lines.
+    pub fn parse(s: &str) -> Result<Self, WorldParseError> {
+        let (areas, metadata) = Self::parse_areas(s);
+        if let Some((area_index, area)) = areas.iter().enumerate().find(|(_, a)| !a.is_valid()) {
+            return Err(WorldParseError::NonContiguousArea {
+                area_index,
+                tile_count: area.tiles.len(),
+            });
+        }
+
+        Ok(Self {
+            areas: areas.into_iter().map(|area| (area.id, area)).collect(),
+            metadata,
+            precomputed: AdjacencyCache::default(),
+        })
+    }
+
+    /// Parse a world definition like [`World::parse`] and check that it is
+    /// playable with [`World::validate`].
+    pub fn from_str_checked(s: &str) -> Result<Self, WorldError> {
+        let world = Self::parse(s)?;
+        world.validate()?;
+        Ok(world)
+    }
+
+    /// The areas of a world definition in file order, and its metadata.
+    fn parse_areas(s: &str) -> (Vec<Area>, WorldMetadata) {
+        let mut areas = Vec::new();
+        let mut metadata = WorldMetadata::default();
         for line in s.lines() {
+            if let Some(header) = line.trim_start().strip_prefix('#') {
+                if let Some((key, value)) = header.split_once(':') {
+                    metadata.apply(key.trim(), value.trim());
+                }
+                continue;
+            }
+
+            let (name, tiles_str) = match line.split_once(':') {
+                Some((name, rest)) => (Some(name.trim()).filter(|n| !n.is_empty()), rest),
+                None => (None, line),
+            };
+
             let mut tiles = HashSet::new();
-            for tile_str in line.split_whitespace() {
-                if let Some((x_str, y_str)) = tile_str.split_once(',')
+            let mut terrain = Terrain::default();
+            for tile_str in tiles_str.split_whitespace() {
+                if let Some(keyword) = Terrain::from_keyword(tile_str) {
+                    terrain = keyword;
+                } else if let Some((x_str, y_str)) = tile_str.split_once(',')
                     && let (Ok(x), Ok(y)) = (x_str.parse(), y_str.parse())
                 {
                     tiles.insert(crate::Tile::new(x, y));
                 }
             }
             if !tiles.is_empty() {
-                let area = Area::new(tiles);
-                areas.insert(area.id, area);
+                let mut area = Area::new(tiles);
+                area.name = name.map(str::to_string);
+                area.terrain = terrain;
+                areas.push(area);
+            }
+        }
+        (areas, metadata)
+    }
+
+    /// Generate a `width` by `height` world split into `num_areas` areas.
+    ///
+    /// Area centers are picked at random, then all areas grow one ring of
+    /// tiles at a time until the grid is covered, so every area is
+    /// connected and borders another. The same seed always gives the same
+    /// layout, although area IDs are fresh every time. `num_areas` is
+    /// capped at the number of tiles; fewer than two areas do not pass
+    /// [`World::validate`].
+    pub fn generate(seed: u64, width: usize, height: usize, num_areas: usize) -> World {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let mut centers: Vec<Tile> = (0..width)
+            .flat_map(|x| (0..height).map(move |y| Tile::new(x, y)))
+            .collect();
+        centers.shuffle(&mut rng);
+        centers.truncate(num_areas);
+
+        let mut owners: HashMap<Tile, usize> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for (index, center) in centers.iter().enumerate() {
+            owners.insert(*center, index);
+            queue.push_back(*center);
+        }
+
+        // Growing from all centers at once assigns each tile to the nearest
+        // center, and every tile joins the area of an adjacent tile
+        while let Some(tile) = queue.pop_front() {
+            let owner = owners[&tile];
+            for neighbor in tile.neighbors().into_iter().flatten() {
+                if neighbor.x() < width && neighbor.y() < height && !owners.contains_key(&neighbor)
+                {
+                    owners.insert(neighbor, owner);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        let mut tiles = vec![HashSet::new(); centers.len()];
+        for (tile, owner) in owners {
+            tiles[owner].insert(tile);
+        }
+
+        World {
+            areas: tiles
+                .into_iter()
+                .map(Area::new)
+                .map(|area| (area.id, area))
+                .collect(),
+            metadata: WorldMetadata {
+                name: format!("Generated #{seed}"),
+                ..WorldMetadata::default()
+            },
+            precomputed: AdjacencyCache::default(),
+        }
+    }
+
+    /// Check that the world is playable: at least two areas, each a single
+    /// non-empty connected region, no tile shared between areas and every
+    /// area reachable from every other through adjacent areas.
+    pub fn validate(&self) -> Result<(), WorldError> {
+        if self.areas.len() < 2 {
+            return Err(WorldError::NotEnoughAreas(self.areas.len()));
+        }
+
+        if let Some(area) = self.areas.values().find(|area| area.tiles.is_empty()) {
+            return Err(WorldError::EmptyArea(area.id));
+        }
+
+        if let Some(area) = self.areas.values().find(|area| !area.is_valid()) {
+            return Err(WorldError::DisconnectedArea(area.id));
+        }
+
+        let mut seen = HashSet::new();
+        for area in self.areas.values() {
+            if let Some(tile) = area.tiles.iter().find(|tile| !seen.insert(**tile)) {
+                return Err(WorldError::OverlappingTile(*tile));
+            }
+        }
+
+        for area_id in self.areas.keys() {
+            if self.area_neighbours(*area_id).is_empty() {
+                return Err(WorldError::IsolatedArea(*area_id));
+            }
+        }
+
+        if let Some(area_id) = self.first_unreachable_area() {
+            return Err(WorldError::UnreachableArea(area_id));
+        }
+
+        Ok(())
+    }
+
+    /// An area that cannot be reached from an arbitrary starting area
+    /// through adjacent areas, if the world falls apart into several parts.
+    fn first_unreachable_area(&self) -> Option<Uuid> {
+        let start = *self.areas.keys().next()?;
+        let mut reached = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(area_id) = queue.pop_front() {
+            for neighbour in self.area_neighbours(area_id) {
+                if reached.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
             }
         }
-        Self { areas }
+
+        self.areas.keys().find(|id| !reached.contains(id)).copied()
     }
 
     pub fn validate_attack(
@@ -44,6 +311,10 @@ impl World {
             .get(&to_id)
             .ok_or(AttackError::AreaNotFound(to_id))?;
 
+        if !to_area.terrain.is_ownable() {
+            return Err(AttackError::AreaNotOwnable(to_id));
+        }
+
         if !from_area.is_adjacent(to_area) {
             return Err(AttackError::AreasNotAdjacent(from_id, to_id));
         }
@@ -63,13 +334,86 @@ impl World {
         Ok(())
     }
 
+    /// Validate and resolve an attack, rolling both stacks with `rng`.
+    pub fn apply_attack(
+        &mut self,
+        from_id: Uuid,
+        to_id: Uuid,
+        player_id: Uuid,
+        rng: &mut impl Rng,
+    ) -> Result<AttackResult, AttackError> {
+        self.validate_attack(from_id, to_id, player_id)?;
+
+        let attack_roll = self.areas[&from_id].stack.attack_roll(rng);
+        let to_area = &self.areas[&to_id];
+        let defence_roll = to_area.stack.defence_roll(to_area.terrain, rng);
+
+        self.apply_attack_rolls(from_id, to_id, player_id, attack_roll, defence_roll)
+    }
+
+    /// Validate and resolve an attack with the given dice totals. Attacks are
+    /// deterministic given their rolls, so this can replay recorded moves.
+    ///
+    /// If the attacker rolls higher, the target area is captured and all but
+    /// one of the attacking dice move into it. Otherwise the attacking area
+    /// is reduced to a single die.
+    pub fn apply_attack_rolls(
+        &mut self,
+        from_id: Uuid,
+        to_id: Uuid,
+        player_id: Uuid,
+        attack_roll: usize,
+        defence_roll: usize,
+    ) -> Result<AttackResult, AttackError> {
+        self.validate_attack(from_id, to_id, player_id)?;
+
+        // Borrow both areas mutably by temporarily removing the attacker's area
+        let mut from_area = self
+            .areas
+            .remove(&from_id)
+            .ok_or(AttackError::AreaNotFound(from_id))?;
+        let to_area = self
+            .areas
+            .get_mut(&to_id)
+            .ok_or(AttackError::AreaNotFound(to_id))?;
+
+        let captured = Stack::resolve(attack_roll, defence_roll) == AttackOutcome::AttackerWins;
+        if captured {
+            // Attacker wins: transfer ownership and move dice. Validation
+            // guarantees the attacking stack holds more than one die.
+            if let Ok((remaining_stack, moved_stack)) = from_area.stack.clone().split() {
+                to_area.owner = Some(player_id);
+                to_area.stack = moved_stack;
+                from_area.stack = remaining_stack;
+            }
+        } else {
+            // Defender wins: attacker loses all dice except one
+            from_area.stack.defeat();
+        }
+
+        let attacker_remaining_dice = from_area.stack.count();
+        let defender_remaining_dice = to_area.stack.count();
+
+        // Re-insert the attacking area
+        self.areas.insert(from_id, from_area);
+
+        Ok(AttackResult {
+            attack_roll,
+            defence_roll,
+            captured,
+            attacker_remaining_dice,
+            defender_remaining_dice,
+        })
+    }
+
     pub fn largest_connected_group(&self, player_id: Uuid) -> usize {
+        let adjacency = self.adjacency();
         let mut visited = HashSet::new();
         let mut largest = 0;
 
         for area in self.areas.values() {
             if area.is_owned_by(player_id) && !visited.contains(&area.id) {
-                let size = self.dfs(area.id, player_id, &mut visited);
+                let size = self.dfs(&adjacency, area.id, player_id, &mut visited);
                 largest = largest.max(size);
             }
         }
@@ -77,50 +421,312 @@ impl World {
         largest
     }
 
+    /// The size of every player's largest connected group, computed in a
+    /// single traversal. Players owning no areas are left out.
+    pub fn largest_connected_group_all_players(&self) -> HashMap<Uuid, usize> {
+        let adjacency = self.adjacency();
+        let mut visited = HashSet::new();
+        let mut largest = HashMap::new();
+
+        for area in self.areas.values() {
+            let Some(owner) = area.owner else {
+                continue;
+            };
+            if !visited.insert(area.id) {
+                continue;
+            }
+
+            let mut size = 0;
+            let mut queue = VecDeque::from([area.id]);
+            while let Some(area_id) = queue.pop_front() {
+                size += 1;
+                for &neighbour_id in adjacency.get(&area_id).into_iter().flatten() {
+                    if self.is_area_owned_by(neighbour_id, owner) && visited.insert(neighbour_id) {
+                        queue.push_back(neighbour_id);
+                    }
+                }
+            }
+
+            let best = largest.entry(owner).or_insert(0);
+            *best = size.max(*best);
+        }
+
+        largest
+    }
+
+    /// Bonus dice `player_id` receives at the end of their turn: one per
+    /// area in their largest connected group plus any terrain bonuses.
+    pub fn bonus_dice(&self, player_id: Uuid) -> usize {
+        self.largest_connected_group(player_id) + self.terrain_bonus_dice(player_id)
+    }
+
+    /// Bonus dice granted by the terrain of `player_id`'s areas, regardless
+    /// of whether those areas are connected.
+    pub fn terrain_bonus_dice(&self, player_id: Uuid) -> usize {
+        self.areas
+            .values()
+            .filter(|area| area.is_owned_by(player_id))
+            .map(|area| area.terrain.bonus_dice())
+            .sum()
+    }
+
+    /// IDs of all areas adjacent to the area with `area_id`. Unknown areas
+    /// have no neighbours.
+    pub fn area_neighbours(&self, area_id: Uuid) -> Vec<Uuid> {
+        self.adjacency().get(&area_id).cloned().unwrap_or_default()
+    }
+
+    /// IDs of `player_id`'s areas that border at least one ownable area
+    /// owned by someone else or by nobody, i.e. the areas an attack can be
+    /// launched from once they hold enough dice.
+    pub fn frontier_areas(&self, player_id: Uuid) -> Vec<Uuid> {
+        let adjacency = self.adjacency();
+        self.areas
+            .values()
+            .filter(|area| area.is_owned_by(player_id))
+            .filter(|area| {
+                adjacency
+                    .get(&area.id)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|id| self.areas.get(id))
+                    .any(|neighbour| {
+                        neighbour.terrain.is_ownable() && !neighbour.is_owned_by(player_id)
+                    })
+            })
+            .map(|area| area.id)
+            .collect()
+    }
+
+    /// IDs of the areas `player_id` may attack from the area with
+    /// `from_id`: every adjacent ownable area they don't own. Empty if the
+    /// source area is unknown, not theirs or holds a single die.
+    pub fn attackable_targets(&self, from_id: Uuid, player_id: Uuid) -> Vec<Uuid> {
+        let Some(from_area) = self.areas.get(&from_id) else {
+            return Vec::new();
+        };
+        if !from_area.is_owned_by(player_id) || from_area.stack.is_single() {
+            return Vec::new();
+        }
+
+        self.area_neighbours(from_id)
+            .into_iter()
+            .filter(|id| {
+                self.areas.get(id).is_some_and(|target| {
+                    target.terrain.is_ownable() && !target.is_owned_by(player_id)
+                })
+            })
+            .collect()
+    }
+
+    /// The adjacency graph of all areas, mapping every area ID to the IDs
+    /// of its neighbours. Built from a tile lookup in a single pass over
+    /// all tiles rather than by comparing every pair of areas.
+    pub fn adjacency_list(&self) -> HashMap<Uuid, Vec<Uuid>> {
+        let tile_owners: HashMap<Tile, Uuid> = self
+            .areas
+            .values()
+            .flat_map(|area| area.tiles.iter().map(|tile| (*tile, area.id)))
+            .collect();
+
+        self.areas
+            .values()
+            .map(|area| {
+                let neighbours: HashSet<Uuid> = area
+                    .tiles
+                    .iter()
+                    .flat_map(|tile| tile.neighbors().into_iter().flatten())
+                    .filter_map(|tile| tile_owners.get(&tile).copied())
+                    .filter(|id| *id != area.id)
+                    .collect();
+                (area.id, neighbours.into_iter().collect())
+            })
+            .collect()
+    }
+
+    /// The cached adjacency graph, computed on first use. It is keyed on
+    /// the area IDs: if the areas have changed since, the graph is rebuilt
+    /// on the fly instead.
+    fn adjacency(&self) -> Cow<'_, HashMap<Uuid, Vec<Uuid>>> {
+        let cached = self.precomputed.0.get_or_init(|| self.adjacency_list());
+        if cached.len() == self.areas.len() && self.areas.keys().all(|id| cached.contains_key(id)) {
+            Cow::Borrowed(cached)
+        } else {
+            Cow::Owned(self.adjacency_list())
+        }
+    }
+
+    /// The shortest chain of adjacent areas from `from_id` to `to_id`, both
+    /// included, that are all owned by `player_id`. Found with a
+    /// breadth-first search; `None` if no such chain exists.
+    pub fn shortest_path(&self, from_id: Uuid, to_id: Uuid, player_id: Uuid) -> Option<Vec<Uuid>> {
+        let owned = |id: &Uuid| self.areas.get(id).is_some_and(|a| a.is_owned_by(player_id));
+        if !owned(&from_id) || !owned(&to_id) {
+            return None;
+        }
+
+        let mut previous = HashMap::from([(from_id, from_id)]);
+        let mut queue = VecDeque::from([from_id]);
+        while let Some(area_id) = queue.pop_front() {
+            if area_id == to_id {
+                let mut path = vec![to_id];
+                let mut current = to_id;
+                while current != from_id {
+                    current = previous[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for neighbour_id in self.area_neighbours(area_id) {
+                if owned(&neighbour_id) && !previous.contains_key(&neighbour_id) {
+                    previous.insert(neighbour_id, area_id);
+                    queue.push_back(neighbour_id);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Whether the area with `area_id` exists and is owned by `player_id`.
+    fn is_area_owned_by(&self, area_id: Uuid, player_id: Uuid) -> bool {
+        self.areas
+            .get(&area_id)
+            .is_some_and(|area| area.is_owned_by(player_id))
+    }
+
     /// Depth-first traversal counting how many of `player_id`'s areas are
     /// reachable from the area with `start_id` via adjacency.
-    fn dfs(&self, start_id: Uuid, player_id: Uuid, visited: &mut HashSet<Uuid>) -> usize {
+    fn dfs(
+        &self,
+        adjacency: &HashMap<Uuid, Vec<Uuid>>,
+        start_id: Uuid,
+        player_id: Uuid,
+        visited: &mut HashSet<Uuid>,
+    ) -> usize {
         visited.insert(start_id);
         let mut size = 1;
 
-        let start_area = match self.areas.get(&start_id) {
-            Some(a) => a,
-            None => return size,
-        };
-
-        for other in self.areas.values() {
-            if !visited.contains(&other.id)
-                && other.is_owned_by(player_id)
-                && start_area.is_adjacent(other)
-            {
-                size += self.dfs(other.id, player_id, visited);
+        for &neighbour_id in adjacency.get(&start_id).into_iter().flatten() {
+            if !visited.contains(&neighbour_id) && self.is_area_owned_by(neighbour_id, player_id) {
+                size += self.dfs(adjacency, neighbour_id, player_id, visited);
             }
         }
 
         size
     }
 
-    /// Add a single die to a random non-full area owned by `player_id`.
-    /// Returns `true` if a die was placed, `false` if the player has no areas
-    /// or all of their areas are already at maximum dice.
-    pub fn add_bonus_dice(&mut self, player_id: Uuid) -> bool {
+    /// Add a single die to a random area owned by `player_id` holding fewer
+    /// than `max_dice`, picked with `rng`. Returns the ID of the area the die
+    /// was placed on, or `None` if the player has no areas or all of their
+    /// areas are already full.
+    pub fn add_bonus_dice(
+        &mut self,
+        player_id: Uuid,
+        max_dice: usize,
+        rng: &mut impl Rng,
+    ) -> Option<Uuid> {
         let eligible_ids: Vec<Uuid> = self
             .areas
             .values()
-            .filter(|a| a.is_owned_by(player_id) && !a.stack.is_full())
+            .filter(|a| {
+                a.is_owned_by(player_id) && a.terrain.is_ownable() && !a.stack.is_full_at(max_dice)
+            })
             .map(|a| a.id)
             .collect();
 
-        let Some(&chosen_id) = eligible_ids.choose(&mut rand::rng()) else {
-            return false;
-        };
-
+        let chosen_id = *eligible_ids.choose(rng)?;
         if let Some(area) = self.areas.get_mut(&chosen_id) {
             // increment is safe because we filtered out full stacks
-            let _ = area.stack.increment();
+            let _ = area.stack.increment_up_to(max_dice);
+        }
+
+        Some(chosen_id)
+    }
+
+    /// Hand out every ownable area to `player_ids` as evenly as possible.
+    ///
+    /// Areas are ordered by position, top to bottom and left to right, and
+    /// assigned in a snake draft (1, 2, 3, 3, 2, 1, ...) so that every
+    /// player gets areas from all over the map. Each player receives the
+    /// fair share rounded either down or up. The draft order is shuffled
+    /// with `rng`, so the first pick does not always go to the same player.
+    pub fn distribute_initial_ownership_balanced(
+        &mut self,
+        player_ids: &[Uuid],
+        rng: &mut impl Rng,
+    ) {
+        if player_ids.is_empty() {
+            return;
+        }
+
+        let mut draft_order = player_ids.to_vec();
+        draft_order.shuffle(rng);
+
+        let mut areas: Vec<(f32, f32, Uuid)> = self
+            .areas
+            .values()
+            .filter(|area| area.terrain.is_ownable())
+            .map(|area| {
+                let (x, y) = area.center();
+                (y, x, area.id)
+            })
+            .collect();
+        areas.sort_by(|a, b| {
+            a.0.total_cmp(&b.0)
+                .then(a.1.total_cmp(&b.1))
+                .then(a.2.cmp(&b.2))
+        });
+
+        let players = draft_order.len();
+        for (i, (_, _, area_id)) in areas.into_iter().enumerate() {
+            let round = i / players;
+            let pick = i % players;
+            let player = if round.is_multiple_of(2) {
+                draft_order[pick]
+            } else {
+                draft_order[players - 1 - pick]
+            };
+            if let Some(area) = self.areas.get_mut(&area_id) {
+                area.owner = Some(player);
+            }
+        }
+    }
+
+    /// Give every owned area a random stack of one to
+    /// [`STARTING_DICE_MAX`] dice, but no more than `max_dice`.
+    pub fn distribute_starting_dice(&mut self, max_dice: usize, rng: &mut impl Rng) {
+        let most = STARTING_DICE_MAX.min(max_dice).max(Stack::MIN);
+        let counts = Uniform::new_inclusive(Stack::MIN, most).expect("valid dice range");
+        for area in self.areas.values_mut().filter(|area| area.owner.is_some()) {
+            area.stack = Stack::new(counts.sample(rng)).expect("count within stack bounds");
         }
+    }
+
+    /// Release every area still owned by `player_id`, leaving them unowned
+    /// with a single die so other players can take them over.
+    pub fn eliminate_player(&mut self, player_id: Uuid) {
+        for area in self.areas.values_mut() {
+            if area.is_owned_by(player_id) {
+                area.owner = None;
+                area.stack = Stack::default();
+            }
+        }
+    }
+
+    /// Whether `player_id` owns no areas at all.
+    pub fn is_player_eliminated(&self, player_id: Uuid) -> bool {
+        self.player_area_count(player_id) == 0
+    }
 
-        true
+    /// How many areas `player_id` owns.
+    pub fn player_area_count(&self, player_id: Uuid) -> usize {
+        self.areas
+            .values()
+            .filter(|area| area.is_owned_by(player_id))
+            .count()
     }
 
     pub fn is_winner(&self, player_id: Uuid) -> bool {
@@ -131,10 +737,54 @@ impl World {
     }
 }
 
+/// Writes the world in the `.world` format read by [`World::from_string`]:
+/// the metadata header, then one line per area ordered by its first tile.
+/// Area IDs, owners and dice are not part of the format.
+impl std::fmt::Display for World {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let metadata = &self.metadata;
+        writeln!(f, "# name: {}", metadata.name)?;
+        if !metadata.description.is_empty() {
+            writeln!(f, "# description: {}", metadata.description)?;
+        }
+        writeln!(f, "# author: {}", metadata.author)?;
+        writeln!(f, "# min_players: {}", metadata.min_players)?;
+        writeln!(f, "# max_players: {}", metadata.max_players)?;
+
+        let mut areas: Vec<(Vec<Tile>, &Area)> = self
+            .areas
+            .values()
+            .map(|area| {
+                let mut tiles: Vec<Tile> = area.tiles.iter().copied().collect();
+                tiles.sort();
+                (tiles, area)
+            })
+            .collect();
+        areas.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (tiles, area) in areas {
+            if let Some(name) = &area.name {
+                write!(f, "{name}: ")?;
+            }
+            if area.terrain != Terrain::default() {
+                write!(f, "{} ", area.terrain.keyword())?;
+            }
+            let tiles: Vec<String> = tiles
+                .iter()
+                .map(|tile| format!("{},{}", tile.x(), tile.y()))
+                .collect();
+            writeln!(f, "{}", tiles.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Tile;
+    use crate::Stack;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
 
     fn area_with_tile(x: usize, y: usize) -> Area {
         let mut tiles = HashSet::new();
@@ -144,7 +794,10 @@ mod tests {
 
     fn world_from_areas(areas: Vec<Area>) -> World {
         let map: HashMap<Uuid, Area> = areas.into_iter().map(|a| (a.id, a)).collect();
-        World { areas: map }
+        World {
+            areas: map,
+            ..World::default()
+        }
     }
 
     #[test]
@@ -163,87 +816,967 @@ mod tests {
         assert!(world.validate_attack(from.id, to.id, attacker).is_ok());
     }
 
+    /// Two adjacent areas: `attacker` owns one with `dice` dice, `defender`
+    /// owns the other with a single die.
+    fn attack_world(attacker: Uuid, defender: Uuid, dice: usize) -> (World, Uuid, Uuid) {
+        let mut from = area_with_tile(0, 0).with_stack(Stack::new(dice).unwrap());
+        from.owner = Some(attacker);
+        let mut to = area_with_tile(0, 1);
+        to.owner = Some(defender);
+
+        let ids = (from.id, to.id);
+        (world_from_areas(vec![from, to]), ids.0, ids.1)
+    }
+
     #[test]
-    fn largest_connected_group_counts_owned_cluster() {
-        let player = Uuid::new_v4();
+    fn apply_attack_rolls_capture_moves_dice() {
+        let (attacker, defender) = (Uuid::new_v4(), Uuid::new_v4());
+        let (mut world, from_id, to_id) = attack_world(attacker, defender, 5);
 
-        let mut a1 = area_with_tile(0, 0);
-        a1.owner = Some(player);
-        let mut a2 = area_with_tile(0, 1);
-        a2.owner = Some(player);
-        let mut a3 = area_with_tile(3, 3);
-        a3.owner = Some(player);
+        let result = world
+            .apply_attack_rolls(from_id, to_id, attacker, 20, 3)
+            .unwrap();
 
-        let world = world_from_areas(vec![a1, a2, a3]);
-        assert_eq!(world.largest_connected_group(player), 2);
+        assert!(result.captured);
+        assert!(world.areas[&to_id].is_owned_by(attacker));
+        assert_eq!(world.areas[&to_id].stack.count(), 4);
+        assert_eq!(world.areas[&from_id].stack.count(), 1);
     }
 
     #[test]
-    fn add_bonus_dice_returns_false_when_no_eligible_areas() {
-        let player = Uuid::new_v4();
-        let mut area = area_with_tile(0, 0);
-        area.owner = Some(player);
-        for _ in 1..crate::Stack::MAX {
-            area.stack.increment().unwrap();
-        }
+    fn apply_attack_rolls_tie_favours_defender() {
+        let (attacker, defender) = (Uuid::new_v4(), Uuid::new_v4());
+        let (mut world, from_id, to_id) = attack_world(attacker, defender, 5);
 
-        let mut world = world_from_areas(vec![area]);
-        assert!(!world.add_bonus_dice(player));
+        let result = world
+            .apply_attack_rolls(from_id, to_id, attacker, 6, 6)
+            .unwrap();
+
+        assert!(!result.captured);
+        assert!(world.areas[&to_id].is_owned_by(defender));
+        assert_eq!(world.areas[&to_id].stack.count(), 1);
+        assert_eq!(world.areas[&from_id].stack.count(), 1);
     }
 
     #[test]
-    fn is_winner_true_when_others_unowned() {
-        let player = Uuid::new_v4();
-        let mut mine = area_with_tile(0, 0);
-        mine.owner = Some(player);
-        let other = area_with_tile(1, 0);
+    fn apply_attack_validates_before_rolling() {
+        let (attacker, defender) = (Uuid::new_v4(), Uuid::new_v4());
+        let (mut world, from_id, to_id) = attack_world(attacker, defender, 1);
 
-        let world = world_from_areas(vec![mine, other]);
-        assert!(world.is_winner(player));
+        let err = world
+            .apply_attack(from_id, to_id, attacker, &mut rand::rng())
+            .unwrap_err();
+        assert!(matches!(err, AttackError::AreaNotEnoughDice(id) if id == from_id));
     }
 
     #[test]
-    fn from_string_parses_valid_tiles() {
-        let input = "0,0 1,1\n2,2 3,3";
-        let world = World::from_string(input);
-
-        assert_eq!(world.areas.len(), 2);
+    fn apply_attack_result_matches_world_state() {
+        let (attacker, defender) = (Uuid::new_v4(), Uuid::new_v4());
+        for _ in 0..50 {
+            let (mut world, from_id, to_id) = attack_world(attacker, defender, 3);
+            let result = world
+                .apply_attack(from_id, to_id, attacker, &mut rand::rng())
+                .unwrap();
 
-        for area in world.areas.values() {
-            assert!(area.tiles.len() == 2);
+            assert!((3..=18).contains(&result.attack_roll));
+            assert!((1..=6).contains(&result.defence_roll));
+            assert_eq!(result.captured, result.attack_roll > result.defence_roll);
+            assert_eq!(world.areas[&to_id].is_owned_by(attacker), result.captured);
         }
     }
 
     #[test]
-    fn from_string_ignores_invalid_tile_format() {
-        let input = "0,0 invalid 1,1\nno_comma";
-        let world = World::from_string(input);
-
-        // Should create an area only for lines containing at least one valid tile
-        assert_eq!(world.areas.len(), 1);
+    fn area_neighbours_of_single_area_is_empty() {
+        let area = area_with_tile(0, 0);
+        let id = area.id;
+        let world = world_from_areas(vec![area]);
+        assert!(world.area_neighbours(id).is_empty());
     }
 
     #[test]
-    fn from_string_skips_empty_lines() {
-        let input = "0,0 1,1\n\n2,2 3,3";
-        let world = World::from_string(input);
+    fn area_neighbours_of_adjacent_pair() {
+        let a = area_with_tile(0, 0);
+        let b = area_with_tile(0, 1);
+        let (a_id, b_id) = (a.id, b.id);
+        let world = world_from_areas(vec![a, b]);
 
-        assert_eq!(world.areas.len(), 2);
+        assert_eq!(world.area_neighbours(a_id), vec![b_id]);
+        assert_eq!(world.area_neighbours(b_id), vec![a_id]);
     }
 
     #[test]
-    fn from_string_creates_no_areas_for_empty_input() {
-        let input = "";
-        let world = World::from_string(input);
+    fn area_neighbours_of_chain() {
+        let left = area_with_tile(0, 0);
+        let middle = area_with_tile(0, 1);
+        let right = area_with_tile(0, 2);
+        let (left_id, middle_id, right_id) = (left.id, middle.id, right.id);
+        let world = world_from_areas(vec![left, middle, right]);
 
-        assert_eq!(world.areas.len(), 0);
+        assert_eq!(world.area_neighbours(left_id), vec![middle_id]);
+        assert_eq!(world.area_neighbours(right_id), vec![middle_id]);
+        let mut middle_neighbours = world.area_neighbours(middle_id);
+        middle_neighbours.sort();
+        let mut ends = vec![left_id, right_id];
+        ends.sort();
+        assert_eq!(middle_neighbours, ends);
     }
 
     #[test]
-    fn from_string_creates_one_area_per_line() {
-        let input = "0,0 1,0\n2,0 3,0\n4,0 5,0";
-        let world = World::from_string(input);
+    fn area_neighbours_of_unknown_area_is_empty() {
+        let world = world_from_areas(vec![area_with_tile(0, 0)]);
+        assert!(world.area_neighbours(Uuid::new_v4()).is_empty());
+    }
 
-        assert_eq!(world.areas.len(), 3);
+    // ==== adjacency_list ====
+
+    #[test]
+    fn adjacency_list_matches_pairwise_adjacency() {
+        let world = World::generate(7, 20, 15, 50);
+        let adjacency = world.adjacency_list();
+        assert_eq!(adjacency.len(), 50);
+
+        for area in world.areas.values() {
+            let mut expected: Vec<Uuid> = world
+                .areas
+                .values()
+                .filter(|other| other.id != area.id && area.is_adjacent(other))
+                .map(|other| other.id)
+                .collect();
+            let mut actual = adjacency[&area.id].clone();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn adjacency_cache_picks_up_added_areas() {
+        let mut world = world_from_areas(vec![area_with_tile(0, 0)]);
+        let id = *world.areas.keys().next().unwrap();
+        assert!(world.area_neighbours(id).is_empty());
+
+        let neighbour = area_with_tile(0, 1);
+        let neighbour_id = neighbour.id;
+        world.areas.insert(neighbour_id, neighbour);
+        assert_eq!(world.area_neighbours(id), vec![neighbour_id]);
+    }
+
+    #[test]
+    fn adjacency_cache_picks_up_replaced_areas() {
+        let player = Uuid::new_v4();
+        let mut first = area_with_tile(0, 0);
+        first.owner = Some(player);
+        let first_id = first.id;
+        let mut world = world_from_areas(vec![first, area_with_tile(0, 1)]);
+        assert_eq!(world.largest_connected_group(player), 1);
+
+        // Same number of areas, but not the ones the graph was built for
+        world.areas.retain(|id, _| *id == first_id);
+        let mut replacement = area_with_tile(0, 1);
+        replacement.owner = Some(player);
+        let replacement_id = replacement.id;
+        world.areas.insert(replacement_id, replacement);
+
+        assert_eq!(world.area_neighbours(first_id), vec![replacement_id]);
+        assert_eq!(world.largest_connected_group(player), 2);
+        assert!(world.frontier_areas(player).is_empty());
+    }
+
+    #[test]
+    fn largest_connected_group_on_generated_world_matches_all_players() {
+        let mut world = World::generate(11, 20, 15, 50);
+        let players = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        for (i, area) in world.areas.values_mut().enumerate() {
+            area.owner = Some(players[i % players.len()]);
+        }
+
+        let all = world.largest_connected_group_all_players();
+        for player in players {
+            assert_eq!(world.largest_connected_group(player), all[&player]);
+        }
+    }
+
+    // ==== frontier_areas / attackable_targets ====
+
+    #[test]
+    fn frontier_areas_of_single_area_world_is_empty() {
+        let player = Uuid::new_v4();
+        let mut area = area_with_tile(0, 0);
+        area.owner = Some(player);
+        let world = world_from_areas(vec![area]);
+
+        assert!(world.frontier_areas(player).is_empty());
+    }
+
+    #[test]
+    fn frontier_areas_skips_fully_surrounded_area() {
+        let player = Uuid::new_v4();
+        let enemy = Uuid::new_v4();
+        let mut areas: Vec<Area> = (0..3).map(|y| area_with_tile(0, y)).collect();
+        areas[0].owner = Some(enemy);
+        areas[1].owner = Some(player);
+        areas[2].owner = Some(player);
+        let (border_id, inner_id) = (areas[1].id, areas[2].id);
+        let world = world_from_areas(areas);
+
+        assert_eq!(world.frontier_areas(player), vec![border_id]);
+        assert!(!world.frontier_areas(player).contains(&inner_id));
+    }
+
+    #[test]
+    fn frontier_areas_includes_areas_bordering_neutral_land() {
+        let player = Uuid::new_v4();
+        let mut mine = area_with_tile(0, 0);
+        mine.owner = Some(player);
+        let mine_id = mine.id;
+        let world = world_from_areas(vec![mine, area_with_tile(0, 1)]);
+
+        assert_eq!(world.frontier_areas(player), vec![mine_id]);
+    }
+
+    #[test]
+    fn attackable_targets_lists_enemy_and_neutral_neighbours() {
+        let player = Uuid::new_v4();
+        let enemy = Uuid::new_v4();
+        let mut areas: Vec<Area> = (0..3).map(|y| area_with_tile(0, y)).collect();
+        areas[0].owner = Some(enemy);
+        areas[1].owner = Some(player);
+        areas[1].stack.add_n(2).unwrap();
+        let ids: Vec<Uuid> = areas.iter().map(|a| a.id).collect();
+        let world = world_from_areas(areas);
+
+        let mut targets = world.attackable_targets(ids[1], player);
+        let mut expected = vec![ids[0], ids[2]];
+        targets.sort();
+        expected.sort();
+        assert_eq!(targets, expected);
+    }
+
+    #[test]
+    fn attackable_targets_requires_owned_source_with_dice() {
+        let player = Uuid::new_v4();
+        let mut areas: Vec<Area> = (0..2).map(|y| area_with_tile(0, y)).collect();
+        areas[0].owner = Some(player);
+        let (single_id, neutral_id) = (areas[0].id, areas[1].id);
+        let world = world_from_areas(areas);
+
+        assert!(world.attackable_targets(single_id, player).is_empty());
+        assert!(world.attackable_targets(neutral_id, player).is_empty());
+        assert!(world.attackable_targets(Uuid::new_v4(), player).is_empty());
+    }
+
+    // ==== shortest_path ====
+
+    /// A column of single-tile areas, each owned by the given owner.
+    fn column(owners: &[Option<Uuid>]) -> (World, Vec<Uuid>) {
+        let areas: Vec<Area> = owners
+            .iter()
+            .enumerate()
+            .map(|(y, owner)| {
+                let mut area = area_with_tile(0, y);
+                area.owner = *owner;
+                area
+            })
+            .collect();
+        let ids = areas.iter().map(|a| a.id).collect();
+        (world_from_areas(areas), ids)
+    }
+
+    #[test]
+    fn shortest_path_to_self_is_single_area() {
+        let player = Uuid::new_v4();
+        let (world, ids) = column(&[Some(player)]);
+        assert_eq!(
+            world.shortest_path(ids[0], ids[0], player),
+            Some(vec![ids[0]])
+        );
+    }
+
+    #[test]
+    fn shortest_path_between_neighbours() {
+        let player = Uuid::new_v4();
+        let (world, ids) = column(&[Some(player), Some(player)]);
+        assert_eq!(
+            world.shortest_path(ids[0], ids[1], player),
+            Some(vec![ids[0], ids[1]])
+        );
+    }
+
+    #[test]
+    fn shortest_path_follows_chain() {
+        let player = Uuid::new_v4();
+        let (world, ids) = column(&[Some(player); 4]);
+        assert_eq!(
+            world.shortest_path(ids[0], ids[3], player),
+            Some(ids.clone())
+        );
+        let mut reversed = ids.clone();
+        reversed.reverse();
+        assert_eq!(world.shortest_path(ids[3], ids[0], player), Some(reversed));
+    }
+
+    #[test]
+    fn shortest_path_none_between_separate_regions() {
+        let (player, enemy) = (Uuid::new_v4(), Uuid::new_v4());
+        let (world, ids) = column(&[Some(player), Some(enemy), Some(player)]);
+        assert_eq!(world.shortest_path(ids[0], ids[2], player), None);
+    }
+
+    #[test]
+    fn shortest_path_none_for_foreign_endpoint() {
+        let (player, enemy) = (Uuid::new_v4(), Uuid::new_v4());
+        let (world, ids) = column(&[Some(player), Some(enemy)]);
+        assert_eq!(world.shortest_path(ids[0], ids[1], player), None);
+        assert_eq!(world.shortest_path(ids[1], ids[0], player), None);
+    }
+
+    #[test]
+    fn largest_connected_group_counts_owned_cluster() {
+        let player = Uuid::new_v4();
+
+        let mut a1 = area_with_tile(0, 0);
+        a1.owner = Some(player);
+        let mut a2 = area_with_tile(0, 1);
+        a2.owner = Some(player);
+        let mut a3 = area_with_tile(3, 3);
+        a3.owner = Some(player);
+
+        let world = world_from_areas(vec![a1, a2, a3]);
+        assert_eq!(world.largest_connected_group(player), 2);
+    }
+
+    #[test]
+    fn largest_connected_group_all_players_matches_individual_results() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let owners = [
+            ((0, 0), alice),
+            ((0, 1), alice),
+            ((0, 2), bob),
+            ((1, 2), bob),
+            ((2, 2), bob),
+            ((4, 0), alice),
+        ];
+        let areas = owners
+            .into_iter()
+            .map(|((x, y), owner)| {
+                let mut area = area_with_tile(x, y);
+                area.owner = Some(owner);
+                area
+            })
+            .collect();
+
+        let world = world_from_areas(areas);
+        let all = world.largest_connected_group_all_players();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[&alice], world.largest_connected_group(alice));
+        assert_eq!(all[&bob], world.largest_connected_group(bob));
+    }
+
+    #[test]
+    fn largest_connected_group_all_players_skips_players_without_areas() {
+        let player = Uuid::new_v4();
+        let eliminated = Uuid::new_v4();
+        let mut owned = area_with_tile(0, 0);
+        owned.owner = Some(player);
+
+        let world = world_from_areas(vec![owned, area_with_tile(0, 1)]);
+        let all = world.largest_connected_group_all_players();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[&player], 1);
+        assert!(!all.contains_key(&eliminated));
+    }
+
+    #[test]
+    fn largest_connected_group_all_players_uses_largest_component() {
+        let player = Uuid::new_v4();
+        let areas = [(0, 0), (0, 1), (0, 2), (5, 5), (5, 6)]
+            .into_iter()
+            .map(|(x, y)| {
+                let mut area = area_with_tile(x, y);
+                area.owner = Some(player);
+                area
+            })
+            .collect();
+
+        let world = world_from_areas(areas);
+        assert_eq!(world.largest_connected_group_all_players()[&player], 3);
+    }
+
+    fn grid_world(width: usize, height: usize) -> World {
+        let areas = (0..width)
+            .flat_map(|x| (0..height).map(move |y| area_with_tile(x, y)))
+            .collect();
+        world_from_areas(areas)
+    }
+
+    #[test]
+    fn balanced_distribution_gives_everyone_a_fair_share() {
+        let mut world = grid_world(6, 5);
+        let players: Vec<Uuid> = (0..6).map(|_| Uuid::new_v4()).collect();
+        world.distribute_initial_ownership_balanced(&players, &mut StdRng::seed_from_u64(7));
+
+        let limit = 30usize.div_ceil(6) + 2;
+        for player in &players {
+            let owned = world
+                .areas
+                .values()
+                .filter(|a| a.is_owned_by(*player))
+                .count();
+            assert!(owned <= limit, "player owns {owned} areas");
+        }
+    }
+
+    #[test]
+    fn balanced_distribution_assigns_every_area() {
+        let mut world = grid_world(6, 5);
+        let players: Vec<Uuid> = (0..6).map(|_| Uuid::new_v4()).collect();
+        world.distribute_initial_ownership_balanced(&players, &mut StdRng::seed_from_u64(7));
+
+        assert!(world.areas.values().all(|area| area.owner.is_some()));
+    }
+
+    #[test]
+    fn balanced_distribution_skips_water() {
+        let mut water = area_with_tile(0, 1);
+        water.terrain = Terrain::Water;
+        let water_id = water.id;
+        let mut world = world_from_areas(vec![area_with_tile(0, 0), water, area_with_tile(0, 2)]);
+
+        let players = [Uuid::new_v4(), Uuid::new_v4()];
+        world.distribute_initial_ownership_balanced(&players, &mut StdRng::seed_from_u64(1));
+
+        assert!(world.areas[&water_id].is_not_owned());
+        for player in players {
+            assert_eq!(
+                world
+                    .areas
+                    .values()
+                    .filter(|a| a.is_owned_by(player))
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn balanced_distribution_is_deterministic_for_a_seed() {
+        let world = grid_world(6, 5);
+        let players: Vec<Uuid> = (0..6).map(|_| Uuid::new_v4()).collect();
+
+        let mut first = world.clone();
+        first.distribute_initial_ownership_balanced(&players, &mut StdRng::seed_from_u64(42));
+        let mut second = world.clone();
+        second.distribute_initial_ownership_balanced(&players, &mut StdRng::seed_from_u64(42));
+
+        for (id, area) in &first.areas {
+            assert_eq!(area.owner, second.areas[id].owner);
+        }
+    }
+
+    #[test]
+    fn starting_dice_are_between_one_and_three() {
+        let mut world = grid_world(6, 5);
+        let players = [Uuid::new_v4(), Uuid::new_v4()];
+        let mut rng = StdRng::seed_from_u64(3);
+        world.distribute_initial_ownership_balanced(&players, &mut rng);
+        world.distribute_starting_dice(Stack::MAX, &mut rng);
+
+        assert!(
+            world
+                .areas
+                .values()
+                .all(|area| (Stack::MIN..=STARTING_DICE_MAX).contains(&area.stack.count()))
+        );
+    }
+
+    #[test]
+    fn starting_dice_respect_max_dice_and_skip_unowned_areas() {
+        let mut world = grid_world(3, 3);
+        let owned_id = *world.areas.keys().next().unwrap();
+        world.areas.get_mut(&owned_id).unwrap().owner = Some(Uuid::new_v4());
+        world.distribute_starting_dice(1, &mut StdRng::seed_from_u64(3));
+
+        assert!(world.areas.values().all(|area| area.stack.is_single()));
+    }
+
+    #[test]
+    fn validate_attack_rejects_water_target() {
+        let attacker = Uuid::new_v4();
+
+        let mut from = area_with_tile(0, 0);
+        from.owner = Some(attacker);
+        from.stack.increment().unwrap();
+
+        let mut to = area_with_tile(0, 1);
+        to.terrain = Terrain::Water;
+
+        let world = world_from_areas(vec![from.clone(), to.clone()]);
+        assert!(matches!(
+            world.validate_attack(from.id, to.id, attacker),
+            Err(AttackError::AreaNotOwnable(id)) if id == to.id
+        ));
+    }
+
+    #[test]
+    fn disconnected_forests_each_grant_a_bonus_die() {
+        let player = Uuid::new_v4();
+        let forests: Vec<Area> = [(0, 0), (3, 3), (6, 6)]
+            .into_iter()
+            .map(|(x, y)| {
+                let mut area = area_with_tile(x, y);
+                area.owner = Some(player);
+                area.terrain = Terrain::Forest;
+                area
+            })
+            .collect();
+
+        let world = world_from_areas(forests);
+        assert_eq!(world.largest_connected_group(player), 1);
+        assert_eq!(world.terrain_bonus_dice(player), 3);
+        assert_eq!(world.bonus_dice(player), 4);
+    }
+
+    #[test]
+    fn add_bonus_dice_returns_none_when_no_eligible_areas() {
+        let player = Uuid::new_v4();
+        let mut area = area_with_tile(0, 0);
+        area.owner = Some(player);
+        area.stack.add_n(crate::Stack::MAX - 1).unwrap();
+
+        let mut world = world_from_areas(vec![area]);
+        assert!(
+            world
+                .add_bonus_dice(player, Stack::MAX, &mut rand::rng())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn add_bonus_dice_returns_the_area_it_filled() {
+        let player = Uuid::new_v4();
+        let mut area = area_with_tile(0, 0);
+        area.owner = Some(player);
+        let area_id = area.id;
+
+        let mut world = world_from_areas(vec![area]);
+        assert_eq!(
+            world.add_bonus_dice(player, Stack::MAX, &mut rand::rng()),
+            Some(area_id)
+        );
+        assert_eq!(world.areas[&area_id].stack.count(), 2);
+    }
+
+    // ==== eliminate_player ====
+
+    #[test]
+    fn eliminate_player_releases_only_their_areas() {
+        let loser = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let mut areas: Vec<Area> = (0..3).map(|y| area_with_tile(0, y)).collect();
+        areas[0].owner = Some(loser);
+        areas[0].stack.add_n(4).unwrap();
+        areas[1].owner = Some(loser);
+        areas[2].owner = Some(other);
+        areas[2].stack.add_n(2).unwrap();
+        let ids: Vec<Uuid> = areas.iter().map(|a| a.id).collect();
+        let mut world = world_from_areas(areas);
+
+        assert!(!world.is_player_eliminated(loser));
+        world.eliminate_player(loser);
+
+        assert!(world.is_player_eliminated(loser));
+        for id in &ids[..2] {
+            assert!(world.areas[id].is_not_owned());
+            assert_eq!(world.areas[id].stack.count(), Stack::MIN);
+        }
+        assert!(world.areas[&ids[2]].is_owned_by(other));
+        assert_eq!(world.areas[&ids[2]].stack.count(), Stack::MIN + 2);
+    }
+
+    #[test]
+    fn eliminated_players_areas_become_attackable() {
+        let loser = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let mut areas: Vec<Area> = (0..2).map(|y| area_with_tile(0, y)).collect();
+        areas[0].owner = Some(loser);
+        areas[1].owner = Some(other);
+        areas[1].stack.add_n(2).unwrap();
+        let (lost_id, attacker_id) = (areas[0].id, areas[1].id);
+        let mut world = world_from_areas(areas);
+
+        world.eliminate_player(loser);
+
+        assert_eq!(world.attackable_targets(attacker_id, other), vec![lost_id]);
+        assert!(world.validate_attack(attacker_id, lost_id, other).is_ok());
+        assert!(world.is_winner(other));
+    }
+
+    #[test]
+    fn player_area_count_counts_only_their_areas() {
+        let player = Uuid::new_v4();
+        let mut areas: Vec<Area> = (0..4).map(|y| area_with_tile(0, y)).collect();
+        areas[0].owner = Some(player);
+        areas[2].owner = Some(player);
+        areas[3].owner = Some(Uuid::new_v4());
+        let world = world_from_areas(areas);
+
+        assert_eq!(world.player_area_count(player), 2);
+        assert_eq!(world.player_area_count(Uuid::new_v4()), 0);
+    }
+
+    #[test]
+    fn player_without_areas_is_eliminated() {
+        let world = world_from_areas(vec![area_with_tile(0, 0)]);
+        assert!(world.is_player_eliminated(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn is_winner_true_when_others_unowned() {
+        let player = Uuid::new_v4();
+        let mut mine = area_with_tile(0, 0);
+        mine.owner = Some(player);
+        let other = area_with_tile(1, 0);
+
+        let world = world_from_areas(vec![mine, other]);
+        assert!(world.is_winner(player));
+    }
+
+    #[test]
+    fn validate_accepts_adjacent_areas() {
+        let world = World::from_string("0,0 1,0\n2,0 3,0");
+        assert_eq!(world.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_single_area() {
+        let world = World::from_string("0,0 1,0");
+        assert_eq!(world.validate(), Err(WorldError::NotEnoughAreas(1)));
+    }
+
+    #[test]
+    fn validate_rejects_disconnected_area() {
+        let world = World::from_string("0,0 1,0\n2,0 4,0");
+        assert!(matches!(
+            world.validate(),
+            Err(WorldError::DisconnectedArea(_))
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_overlapping_tiles() {
+        let world = World::from_string("0,0 1,0\n1,0 2,0");
+        assert_eq!(
+            world.validate(),
+            Err(WorldError::OverlappingTile(Tile::new(1, 0)))
+        );
+    }
+
+    #[test]
+    fn validate_rejects_empty_area() {
+        let mut world = World::from_string("0,0 1,0\n2,0 3,0");
+        let empty = Area::new(HashSet::new());
+        let empty_id = empty.id;
+        world.areas.insert(empty.id, empty);
+        assert_eq!(world.validate(), Err(WorldError::EmptyArea(empty_id)));
+    }
+
+    #[test]
+    fn validate_rejects_world_split_in_two() {
+        // Two pairs of adjacent areas with water between them
+        let world = World::from_string("0,0\n1,0\n6,0\n7,0");
+        assert!(matches!(
+            world.validate(),
+            Err(WorldError::UnreachableArea(_))
+        ));
+    }
+
+    #[test]
+    fn validate_accepts_chain_of_areas() {
+        let world = World::from_string("0,0\n1,0\n2,0\n3,0");
+        assert_eq!(world.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_isolated_area() {
+        let world = World::from_string("0,0 1,0\n2,0 3,0\n8,8");
+        assert!(matches!(world.validate(), Err(WorldError::IsolatedArea(_))));
+    }
+
+    #[test]
+    fn from_string_parses_valid_tiles() {
+        let input = "0,0 1,1\n2,2 3,3";
+        let world = World::from_string(input);
+
+        assert_eq!(world.areas.len(), 2);
+
+        for area in world.areas.values() {
+            assert!(area.tiles.len() == 2);
+        }
+    }
+
+    #[test]
+    fn from_string_ignores_invalid_tile_format() {
+        let input = "0,0 invalid 1,1\nno_comma";
+        let world = World::from_string(input);
+
+        // Should create an area only for lines containing at least one valid tile
+        assert_eq!(world.areas.len(), 1);
+    }
+
+    #[test]
+    fn from_string_skips_empty_lines() {
+        let input = "0,0 1,1\n\n2,2 3,3";
+        let world = World::from_string(input);
+
+        assert_eq!(world.areas.len(), 2);
+    }
+
+    #[test]
+    fn from_string_creates_no_areas_for_empty_input() {
+        let input = "";
+        let world = World::from_string(input);
+
+        assert_eq!(world.areas.len(), 0);
+    }
+
+    #[test]
+    fn from_string_parses_area_names() {
+        let input = "Forest of Doom: 0,0 1,0\nNorthern Plains: 2,0 3,0";
+        let world = World::from_string(input);
+
+        let mut names: Vec<_> = world
+            .areas
+            .values()
+            .map(|area| area.name.as_deref().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Forest of Doom", "Northern Plains"]);
+        assert!(world.areas.values().all(|area| area.tiles.len() == 2));
+    }
+
+    #[test]
+    fn from_string_unnamed_areas_have_no_name() {
+        let input = "0,0 1,0\n : 2,0 3,0";
+        let world = World::from_string(input);
+
+        assert_eq!(world.areas.len(), 2);
+        assert!(world.areas.values().all(|area| area.name.is_none()));
+    }
+
+    #[test]
+    fn area_names_survive_serde_roundtrip() {
+        let world = World::from_string("Forest of Doom: 0,0 1,0");
+        let json = serde_json::to_string(&world).unwrap();
+        let deser: World = serde_json::from_str(&json).unwrap();
+
+        let area = deser.areas.values().next().unwrap();
+        assert_eq!(area.name.as_deref(), Some("Forest of Doom"));
+    }
+
+    #[test]
+    fn from_string_parses_terrain_keywords() {
+        let input = "Dark Wood: forest 0,0 1,0\n2,0 3,0 mountain\n4,0";
+        let world = World::from_string(input);
+
+        let mut terrains: Vec<_> = world.areas.values().map(|area| area.terrain).collect();
+        terrains.sort_by_key(|terrain| *terrain as usize);
+        assert_eq!(
+            terrains,
+            vec![Terrain::Plains, Terrain::Forest, Terrain::Mountain]
+        );
+    }
+
+    #[test]
+    fn from_string_parses_metadata_header() {
+        let input = "# name: Archipelago\n\
+                     # description: Islands in a shallow sea\n\
+                     # author: Jane Doe\n\
+                     # min_players: 3\n\
+                     # max_players: 5\n\
+                     0,0 1,0\n2,0 3,0";
+        let world = World::from_string(input);
+
+        assert_eq!(
+            world.metadata,
+            WorldMetadata {
+                name: "Archipelago".to_string(),
+                description: "Islands in a shallow sea".to_string(),
+                author: "Jane Doe".to_string(),
+                min_players: 3,
+                max_players: 5,
+            }
+        );
+        assert_eq!(world.areas.len(), 2);
+    }
+
+    #[test]
+    fn from_string_without_metadata_uses_defaults() {
+        let world = World::from_string("0,0 1,0\n2,0 3,0");
+        assert_eq!(world.metadata, WorldMetadata::default());
+    }
+
+    #[test]
+    fn from_string_ignores_unknown_or_invalid_metadata() {
+        let world = World::from_string("# colour: blue\n# max_players: many\n0,0");
+        assert_eq!(world.metadata, WorldMetadata::default());
+        assert_eq!(world.areas.len(), 1);
+    }
+
+    #[test]
+    fn from_string_creates_one_area_per_line() {
+        let input = "0,0 1,0\n2,0 3,0\n4,0 5,0";
+        let world = World::from_string(input);
+
+        assert_eq!(world.areas.len(), 3);
+    }
+
+    #[test]
+    fn parse_accepts_well_formed_world() {
+        let world = World::parse("# name: Ridge\n0,0 1,0\n\nHills: 2,0 3,0 mountain").unwrap();
+        assert_eq!(world.areas.len(), 2);
+        assert_eq!(world.metadata.name, "Ridge");
+    }
+
+    #[test]
+    fn parse_rejects_area_with_isolated_tile_clusters() {
+        let input = "# name: Broken\n0,0 1,0\n\n2,0 3,0\n4,0 5,0 8,0 9,0";
+        assert_eq!(
+            World::parse(input).unwrap_err(),
+            WorldParseError::NonContiguousArea {
+                area_index: 2,
+                tile_count: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn from_str_checked_accepts_playable_world() {
+        let world = World::from_str_checked("# name: Ridge\n0,0 1,0\n2,0 3,0").unwrap();
+        assert_eq!(world.areas.len(), 2);
+        assert_eq!(world.metadata.name, "Ridge");
+    }
+
+    #[test]
+    fn from_str_checked_rejects_overlapping_tiles() {
+        assert_eq!(
+            World::from_str_checked("0,0 1,0\n1,0 2,0").unwrap_err(),
+            WorldError::OverlappingTile(Tile::new(1, 0))
+        );
+    }
+
+    #[test]
+    fn from_str_checked_reports_parse_errors() {
+        assert!(matches!(
+            World::from_str_checked("0,0 1,0\n3,0 5,0"),
+            Err(WorldError::Parse(WorldParseError::NonContiguousArea { .. }))
+        ));
+    }
+
+    #[test]
+    fn to_string_writes_canonical_world_file() {
+        let source = "\
+# name: Four Corners
+# description: A compact map.
+# author: rsdice
+# min_players: 2
+# max_players: 4
+Northern Plains: 1,1 1,2 2,1 2,2
+Southern Fields: 1,3 1,4 2,3 2,4
+Forest of Doom: forest 3,1 3,2 4,1 4,2
+Grey Peaks: mountain 3,3 3,4 4,3 4,4
+";
+        assert_eq!(World::from_string(source).to_string(), source);
+    }
+
+    #[test]
+    fn to_string_roundtrips_through_from_string() {
+        let world = World::from_string(
+            "# name: Ridge\n# author: Jane\n7,0 5,0 6,0\nLake: 2,0 water\nHills: 3,0 mountain 4,0",
+        );
+        let reparsed = World::from_string(&world.to_string());
+
+        let areas = |world: &World| {
+            let mut areas: Vec<_> = world
+                .areas
+                .values()
+                .map(|area| {
+                    let mut tiles: Vec<Tile> = area.tiles.iter().copied().collect();
+                    tiles.sort();
+                    (tiles, area.name.clone(), area.terrain)
+                })
+                .collect();
+            areas.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+            areas
+        };
+        assert_eq!(areas(&reparsed), areas(&world));
+        assert_eq!(reparsed.metadata, world.metadata);
+    }
+
+    #[test]
+    fn to_string_of_empty_world_has_only_metadata() {
+        let text = World::default().to_string();
+        assert!(text.lines().all(|line| line.starts_with('#')));
+        assert_eq!(World::from_string(&text).metadata, WorldMetadata::default());
+    }
+
+    // ==== generate ====
+
+    /// The tiles of every area, sorted, so worlds can be compared without
+    /// their area IDs.
+    fn layout(world: &World) -> Vec<Vec<Tile>> {
+        let mut areas: Vec<Vec<Tile>> = world
+            .areas
+            .values()
+            .map(|area| {
+                let mut tiles: Vec<Tile> = area.tiles.iter().copied().collect();
+                tiles.sort();
+                tiles
+            })
+            .collect();
+        areas.sort();
+        areas
+    }
+
+    #[test]
+    fn generate_is_reproducible_with_same_seed() {
+        assert_eq!(
+            layout(&World::generate(42, 12, 10, 15)),
+            layout(&World::generate(42, 12, 10, 15))
+        );
+    }
+
+    #[test]
+    fn generate_differs_between_seeds() {
+        let first = layout(&World::generate(0, 12, 10, 15));
+        assert!((1..10).any(|seed| layout(&World::generate(seed, 12, 10, 15)) != first));
+    }
+
+    #[test]
+    fn generate_creates_requested_number_of_areas() {
+        let world = World::generate(7, 12, 10, 15);
+        assert_eq!(world.areas.len(), 15);
+        assert!(world.areas.values().all(|area| !area.tiles.is_empty()));
+    }
+
+    #[test]
+    fn generate_covers_the_whole_grid() {
+        let world = World::generate(7, 12, 10, 15);
+        let tiles: usize = world.areas.values().map(|area| area.tiles.len()).sum();
+        assert_eq!(tiles, 12 * 10);
+    }
+
+    #[test]
+    fn generated_worlds_are_valid() {
+        for seed in 0..20 {
+            assert_eq!(World::generate(seed, 12, 10, 15).validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn generate_caps_areas_at_tile_count() {
+        let world = World::generate(1, 2, 2, 10);
+        assert_eq!(world.areas.len(), 4);
+        assert!(world.areas.values().all(|area| area.tiles.len() == 1));
     }
 }
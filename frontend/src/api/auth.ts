@@ -60,8 +60,8 @@ export async function request<T>(path: string, init?: RequestInit, options?: Req
 
     const response = await fetch(path, {
         credentials: "same-origin",
-        headers,
         ...init,
+        headers,
     });
 
     if (response.status === 401 && options?.auth && options.retryOnUnauthorized !== false) {
@@ -7,13 +7,31 @@ export type GameCreator = {
     name: string;
 };
 
+export type PlayerPreview = {
+    name: string;
+    /** Hex color, e.g. "#FF0000". */
+    color: string;
+};
+
 export type GameListItem = {
     id: string;
     creator: GameCreator;
     player_count: number;
+    players: PlayerPreview[];
+    spectator_count: number;
     state: GameState;
 };
 
+export type WorldMeta = {
+    name: string;
+    title: string;
+    description: string;
+    author: string;
+    min_players: number;
+    max_players: number;
+    area_count: number;
+};
+
 export type GameSnapshot = {
     id: string;
 };
@@ -46,10 +64,27 @@ export function gameStateLabel(state: GameState): string {
     return "Unknown";
 }
 
+export type Page<T> = {
+    items: T[];
+    total: number;
+    offset: number;
+    limit: number;
+};
+
 export async function listGames(): Promise<GameListItem[]> {
-    return request<GameListItem[]>("/api/games");
+    const page = await request<Page<GameListItem>>("/api/games?limit=100");
+    return page.items;
 }
 
-export async function createGame(): Promise<GameSnapshot> {
-    return request<GameSnapshot>("/api/games", { method: "PUT" }, { auth: true });
+export async function listWorlds(): Promise<WorldMeta[]> {
+    return request<WorldMeta[]>("/api/games/worlds");
+}
+
+export async function createGame(worldName?: string): Promise<GameSnapshot> {
+    const init: RequestInit = { method: "PUT" };
+    if (worldName) {
+        init.headers = { "Content-Type": "application/json" };
+        init.body = JSON.stringify({ world_name: worldName });
+    }
+    return request<GameSnapshot>("/api/games", init, { auth: true });
 }
@@ -140,6 +140,19 @@ export default function Games({ authLoading, isAuthenticated }: GamesProps) {
                                 <p className="text-sm text-ctp-subtext1">
                                     Players: {game.player_count} · State: {gameStateLabel(game.state)}
                                 </p>
+                                {game.players.length > 0 && (
+                                    <ul className="flex flex-wrap gap-3 text-sm">
+                                        {game.players.map((player) => (
+                                            <li key={player.name} className="flex items-center gap-1.5">
+                                                <span
+                                                    className="inline-block h-3 w-3 rounded-full"
+                                                    style={{ backgroundColor: player.color }}
+                                                />
+                                                {player.name}
+                                            </li>
+                                        ))}
+                                    </ul>
+                                )}
                             </div>
 
                             {!authLoading && (
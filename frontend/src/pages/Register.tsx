@@ -33,7 +33,7 @@ export default function Register({ onRegistered }: RegisterProps) {
     const { submitting, error, wrapSubmit } = useFormSubmit("Unable to register user.");
 
     const emailValid = /^[^\s@]+@[^\s@]+\.[^\s@]+$/.test(email);
-    const usernameValid = username.trim().length >= 3;
+    const usernameValid = /^[A-Za-z0-9_-]{3,}$/.test(username.trim());
 
     const handleSubmit = async (event: FormEvent<HTMLFormElement>) => {
         event.preventDefault();
@@ -68,7 +68,7 @@ export default function Register({ onRegistered }: RegisterProps) {
                 id="register-username"
                 touched={touched.username}
                 valid={usernameValid}
-                error="Username must be at least 3 characters."
+                error="Username must be at least 3 characters: letters, digits, underscores and hyphens."
             >
                 <input
                     id="register-username"
@@ -0,0 +1,99 @@
+use bevy::color::palettes::css::{LIME, RED, YELLOW};
+use bevy::prelude::*;
+
+/// State of the WebSocket connection to the game server.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    #[default]
+    Connected,
+    Reconnecting {
+        attempt: u32,
+    },
+    Failed,
+}
+
+impl ConnectionStatus {
+    /// Color of the status dot: green, yellow or red.
+    pub fn color(&self) -> Color {
+        match self {
+            ConnectionStatus::Connected => LIME.into(),
+            ConnectionStatus::Reconnecting { .. } => YELLOW.into(),
+            ConnectionStatus::Failed => RED.into(),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ConnectionStatusDot;
+
+/// Spawn the status dot in the top right corner of the screen.
+pub fn spawn_connection_status(mut commands: Commands, status: Res<ConnectionStatus>) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12.0),
+            right: px(12.0),
+            width: px(12.0),
+            height: px(12.0),
+            ..default()
+        },
+        BackgroundColor(status.color()),
+        ConnectionStatusDot,
+    ));
+}
+
+/// Recolor the status dot whenever the connection status changes.
+pub fn render_connection_status(
+    status: Res<ConnectionStatus>,
+    mut dots: Query<&mut BackgroundColor, With<ConnectionStatusDot>>,
+) {
+    if !status.is_changed() {
+        return;
+    }
+
+    for mut color in &mut dots {
+        color.0 = status.color();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_with_status(status: ConnectionStatus) -> App {
+        let mut app = App::new();
+        app.insert_resource(status)
+            .add_systems(Startup, spawn_connection_status)
+            .add_systems(Update, render_connection_status);
+        app.update();
+        app
+    }
+
+    fn dot_color(app: &mut App) -> Color {
+        let world = app.world_mut();
+        let mut dots = world.query_filtered::<&BackgroundColor, With<ConnectionStatusDot>>();
+        dots.single(world).unwrap().0
+    }
+
+    #[test]
+    fn connected_shows_green_dot() {
+        let mut app = app_with_status(ConnectionStatus::Connected);
+        assert_eq!(dot_color(&mut app), Color::from(LIME));
+    }
+
+    #[test]
+    fn reconnecting_shows_yellow_dot() {
+        let mut app = app_with_status(ConnectionStatus::Connected);
+        app.insert_resource(ConnectionStatus::Reconnecting { attempt: 1 });
+        app.update();
+        assert_eq!(dot_color(&mut app), Color::from(YELLOW));
+    }
+
+    #[test]
+    fn failed_shows_red_dot() {
+        let mut app = app_with_status(ConnectionStatus::Reconnecting { attempt: 3 });
+        app.insert_resource(ConnectionStatus::Failed);
+        app.update();
+        assert_eq!(dot_color(&mut app), Color::from(RED));
+    }
+}
@@ -1,7 +1,18 @@
 use bevy::prelude::*;
+use connection_status::{render_connection_status, spawn_connection_status};
+use shortcuts::{
+    SurrenderConfirmed, handle_keyboard_shortcuts, handle_surrender_dialog, send_surrender,
+};
 use std::sync::{Mutex, OnceLock};
 use wasm_bindgen::prelude::*;
 
+mod connection_status;
+mod shortcuts;
+mod state;
+
+pub use connection_status::ConnectionStatus;
+pub use state::{ClientGame, OutgoingCommands, SelectedArea};
+
 #[derive(Resource, Debug, Clone)]
 pub struct GameSession {
     pub game_id: Option<String>,
@@ -43,8 +54,21 @@ pub fn build_app() -> App {
     .insert_resource(GameSession {
         game_id: current_game_id(),
     })
-    .add_systems(Startup, setup)
-    .add_systems(Update, sync_game_id_label);
+    .init_resource::<ConnectionStatus>()
+    .init_resource::<ClientGame>()
+    .init_resource::<OutgoingCommands>()
+    .init_resource::<SelectedArea>()
+    .add_message::<SurrenderConfirmed>()
+    .add_systems(Startup, (setup, spawn_connection_status))
+    .add_systems(
+        Update,
+        (
+            sync_game_id_label,
+            render_connection_status,
+            handle_keyboard_shortcuts,
+            (handle_surrender_dialog, send_surrender).chain(),
+        ),
+    );
 
     app
 }
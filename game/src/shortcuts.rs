@@ -0,0 +1,215 @@
+use crate::state::{ClientGame, OutgoingCommands, SelectedArea};
+use bevy::prelude::*;
+use common::GameCommand;
+
+/// Sent when the player confirms that they want to surrender.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SurrenderConfirmed;
+
+#[derive(Component)]
+pub struct SurrenderDialog;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurrenderDialogButton {
+    Confirm,
+    Cancel,
+}
+
+/// `Enter` ends the turn, `Escape` deselects the current area and closes
+/// the surrender dialog, `S` opens the surrender dialog.
+pub fn handle_keyboard_shortcuts(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    client: Res<ClientGame>,
+    mut outgoing: ResMut<OutgoingCommands>,
+    mut selected: ResMut<SelectedArea>,
+    dialogs: Query<Entity, With<SurrenderDialog>>,
+) {
+    if keys.just_pressed(KeyCode::Enter) && client.is_players_turn() {
+        outgoing.0.push(GameCommand::EndTurn);
+    }
+
+    if keys.just_pressed(KeyCode::Escape) {
+        selected.0 = None;
+        for dialog in &dialogs {
+            commands.entity(dialog).despawn();
+        }
+    }
+
+    if keys.just_pressed(KeyCode::KeyS) && dialogs.is_empty() {
+        spawn_surrender_dialog(&mut commands);
+    }
+}
+
+fn spawn_surrender_dialog(commands: &mut Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(40.0),
+            left: percent(50.0),
+            margin: UiRect::left(px(-120.0)),
+            width: px(240.0),
+            padding: UiRect::all(px(16.0)),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(12.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+        SurrenderDialog,
+        children![
+            Text::new("Surrender this game?"),
+            (
+                Node {
+                    column_gap: px(8.0),
+                    justify_content: JustifyContent::End,
+                    ..default()
+                },
+                children![
+                    dialog_button("Confirm", SurrenderDialogButton::Confirm),
+                    dialog_button("Cancel", SurrenderDialogButton::Cancel),
+                ],
+            ),
+        ],
+    ));
+}
+
+fn dialog_button(label: &str, button: SurrenderDialogButton) -> impl Bundle {
+    (
+        Button,
+        Node {
+            padding: UiRect::axes(px(12.0), px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+        button,
+        children![Text::new(label)],
+    )
+}
+
+/// Close the surrender dialog when one of its buttons is pressed,
+/// announcing the surrender if it was confirmed.
+pub fn handle_surrender_dialog(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &SurrenderDialogButton), Changed<Interaction>>,
+    dialogs: Query<Entity, With<SurrenderDialog>>,
+    mut surrender: MessageWriter<SurrenderConfirmed>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if *button == SurrenderDialogButton::Confirm {
+            surrender.write(SurrenderConfirmed);
+        }
+        for dialog in &dialogs {
+            commands.entity(dialog).despawn();
+        }
+    }
+}
+
+/// Resign from the game once the player has confirmed their surrender.
+pub fn send_surrender(
+    mut surrender: MessageReader<SurrenderConfirmed>,
+    mut outgoing: ResMut<OutgoingCommands>,
+) {
+    if surrender.read().count() > 0 {
+        outgoing.0.push(GameCommand::Resign);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{GameState, World};
+    use uuid::Uuid;
+
+    const PLAYER: Uuid = Uuid::from_u128(1);
+    const OPPONENT: Uuid = Uuid::from_u128(2);
+
+    fn app_with_turn(turn: usize) -> App {
+        let mut game = common::Game::new(World::default());
+        game.join_player(PLAYER, "player".to_string()).unwrap();
+        game.join_player(OPPONENT, "opponent".to_string()).unwrap();
+        game.state = GameState::InProgress { turn };
+
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<OutgoingCommands>()
+            .init_resource::<SelectedArea>()
+            .insert_resource(ClientGame {
+                player_id: Some(PLAYER),
+                game: Some(game),
+            })
+            .add_systems(Update, handle_keyboard_shortcuts);
+        app
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        app.update();
+    }
+
+    fn dialog_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world
+            .query_filtered::<(), With<SurrenderDialog>>()
+            .iter(world)
+            .count()
+    }
+
+    #[test]
+    fn enter_on_players_turn_queues_end_turn() {
+        let mut app = app_with_turn(0);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            app.world().resource::<OutgoingCommands>().0,
+            vec![GameCommand::EndTurn]
+        );
+    }
+
+    #[test]
+    fn enter_on_opponents_turn_does_nothing() {
+        let mut app = app_with_turn(1);
+        press(&mut app, KeyCode::Enter);
+        assert!(app.world().resource::<OutgoingCommands>().0.is_empty());
+    }
+
+    #[test]
+    fn escape_deselects_area() {
+        let mut app = app_with_turn(0);
+        app.world_mut().resource_mut::<SelectedArea>().0 = Some(Uuid::from_u128(3));
+        press(&mut app, KeyCode::Escape);
+        assert_eq!(app.world().resource::<SelectedArea>().0, None);
+    }
+
+    #[test]
+    fn confirmed_surrender_queues_resign_once() {
+        let mut app = App::new();
+        app.init_resource::<OutgoingCommands>()
+            .add_message::<SurrenderConfirmed>()
+            .add_systems(Update, send_surrender);
+
+        app.world_mut().write_message(SurrenderConfirmed);
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<OutgoingCommands>().0,
+            vec![GameCommand::Resign]
+        );
+    }
+
+    #[test]
+    fn s_opens_surrender_dialog_once() {
+        let mut app = app_with_turn(0);
+        press(&mut app, KeyCode::KeyS);
+        assert_eq!(dialog_count(&mut app), 1);
+
+        // Nothing clears the input between updates, so `S` is still pressed
+        app.update();
+        assert_eq!(dialog_count(&mut app), 1);
+    }
+}
@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use common::{GameCommand, GameState};
+use uuid::Uuid;
+
+/// The game as last received from the server, and who is playing it.
+#[derive(Resource, Debug, Default)]
+pub struct ClientGame {
+    pub player_id: Option<Uuid>,
+    pub game: Option<common::Game>,
+}
+
+impl ClientGame {
+    /// Whether the game is in progress and it is this client's turn.
+    pub fn is_players_turn(&self) -> bool {
+        let (Some(player_id), Some(game)) = (self.player_id, &self.game) else {
+            return false;
+        };
+        let GameState::InProgress { turn } = game.state else {
+            return false;
+        };
+        game.players.get(turn).is_some_and(|p| p.id == player_id)
+    }
+}
+
+/// Commands waiting to be sent to the server.
+#[derive(Resource, Debug, Default)]
+pub struct OutgoingCommands(pub Vec<GameCommand>);
+
+/// The area the player has picked, e.g. to attack from.
+#[derive(Resource, Debug, Default)]
+pub struct SelectedArea(pub Option<Uuid>);